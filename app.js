@@ -15,6 +15,24 @@ class RobotVisualizerApp {
         this.linkLengths = [2.0, 1.5];
         this.dhParams = [];
         this.jointAngles = [0, 0];
+
+        // Workspace shading (recomputed when the robot changes)
+        this.showWorkspace = false;
+        this.workspace = null;
+
+        // Manipulability ellipsoid overlay
+        this.showEllipsoid = false;
+
+        // Static obstacles for the collision check (world frame)
+        this.obstacles = [];
+
+        // Physics simulation (arm falling and swinging under gravity)
+        this.physicsRunning = false;
+        // Control law driving the physics simulation ('' = passive arm)
+        this.controlLaw = '';
+
+        // PID servo simulation (sliders set the setpoints)
+        this.servoRunning = false;
     }
 
     async initialize() {
@@ -35,6 +53,7 @@ class RobotVisualizerApp {
 
         // Setup event listeners
         this.setupControls();
+        this.setupEndEffectorDrag();
 
         // Initial render
         this.render();
@@ -155,7 +174,17 @@ class RobotVisualizerApp {
                 const value = parseInt(e.target.value);
                 valueDisplay.textContent = value;
                 this.jointAngles[i] = value;
+                if (this.servoRunning) {
+                    this.updateSetpoints();
+                    return;
+                }
+                if (this.physicsRunning && this.controlLaw) {
+                    this.updateControlReference();
+                    return;
+                }
                 this.updateAngles();
+                // Dragging a slider catches the arm; it swings again from rest
+                if (this.physicsRunning) this.simulator.reset_simulation();
             });
         }
     }
@@ -196,6 +225,55 @@ class RobotVisualizerApp {
             this.generateUI();
         });
 
+        // Workspace shading toggle
+        document.getElementById('show-workspace').addEventListener('change', (e) => {
+            this.showWorkspace = e.target.checked;
+            this.updateWorkspace();
+            this.render();
+        });
+
+        // Manipulability ellipsoid toggle
+        document.getElementById('show-ellipsoid').addEventListener('change', (e) => {
+            this.showEllipsoid = e.target.checked;
+            this.render();
+        });
+
+        // Box obstacle toggle
+        document.getElementById('show-obstacle').addEventListener('change', (e) => {
+            this.obstacles = e.target.checked
+                ? [{ name: 'box', shape: { Box: { center: [1.8, 1.2, 0], half_extents: [0.4, 0.4, 0.5] } } }]
+                : [];
+            this.applyObstacles();
+            this.render();
+        });
+
+        // Physics simulation toggle
+        document.getElementById('simulate-physics').addEventListener('change', (e) => {
+            if (e.target.checked) {
+                this.stopServos();
+                this.startPhysics();
+            } else {
+                this.physicsRunning = false;
+            }
+        });
+
+        // Controller driving the physics simulation
+        document.getElementById('physics-controller').addEventListener('change', (e) => {
+            this.controlLaw = e.target.value;
+            if (this.physicsRunning) this.applyController();
+        });
+
+        // PID servo toggle
+        document.getElementById('servo-mode').addEventListener('change', (e) => {
+            if (e.target.checked) {
+                this.physicsRunning = false;
+                document.getElementById('simulate-physics').checked = false;
+                this.startServos();
+            } else {
+                this.stopServos();
+            }
+        });
+
         // Update robot button
         document.getElementById('update-robot').addEventListener('click', () => {
             this.createSimulator();
@@ -218,17 +296,85 @@ class RobotVisualizerApp {
                 const preset = presets[presetName];
 
                 if (preset) {
-                    for (let i = 0; i < 2; i++) {
-                        document.getElementById(`theta${i}`).value = preset.angles[i];
-                        document.getElementById(`theta${i}-value`).textContent = preset.angles[i];
-                        this.jointAngles[i] = preset.angles[i];
-                    }
-                    this.updateAngles();
+                    this.animateTo(preset.angles);
                 }
             });
         });
     }
 
+    setupEndEffectorDrag() {
+        let dragging = false;
+
+        const toWorld = (event) => {
+            const rect = this.canvas.getBoundingClientRect();
+            const canvasX = (event.clientX - rect.left) * (this.canvas.width / rect.width);
+            const canvasY = (event.clientY - rect.top) * (this.canvas.height / rect.height);
+            return {
+                x: (canvasX - this.centerX) / this.scale,
+                y: (this.centerY - canvasY) / this.scale // Flip Y for world
+            };
+        };
+
+        this.canvas.addEventListener('mousedown', (e) => {
+            if (!this.simulator) return;
+
+            // Only start dragging when grabbing the end-effector marker
+            const endEffector = this.simulator.get_end_effector_position();
+            const pointer = toWorld(e);
+            const distancePx = Math.hypot(pointer.x - endEffector.x, pointer.y - endEffector.y) * this.scale;
+            dragging = distancePx < 15;
+        });
+
+        this.canvas.addEventListener('mousemove', (e) => {
+            if (!dragging || !this.simulator) return;
+
+            const endEffector = this.simulator.get_end_effector_position();
+            const pointer = toWorld(e);
+
+            try {
+                // Keep the current height; the canvas only shows the XY plane
+                this.simulator.solve_ik({ x: pointer.x, y: pointer.y, z: endEffector.z });
+                this.syncSlidersFromSimulator();
+                this.render();
+            } catch (error) {
+                console.error('Failed to solve IK:', error);
+            }
+        });
+
+        window.addEventListener('mouseup', () => {
+            dragging = false;
+        });
+    }
+
+    applyJointLimitsToSliders() {
+        // Bounded joints use their model limits, unbounded ones keep (-180°, 180°]
+        const limits = this.simulator.get_joint_limits();
+
+        limits.forEach((limit, i) => {
+            const slider = document.getElementById(`theta${i}`);
+            if (!slider) return;
+
+            slider.min = Number.isFinite(limit.lower) ? Math.ceil(limit.lower * 180 / Math.PI) : -180;
+            slider.max = Number.isFinite(limit.upper) ? Math.floor(limit.upper * 180 / Math.PI) : 180;
+        });
+    }
+
+    syncSlidersFromSimulator() {
+        const anglesRad = this.simulator.get_angles();
+
+        anglesRad.forEach((rad, i) => {
+            const deg = rad * 180 / Math.PI;
+            // Wrap into the slider range (-180°, 180°]
+            const wrapped = deg - 360 * Math.ceil((deg - 180) / 360);
+            this.jointAngles[i] = wrapped;
+
+            const slider = document.getElementById(`theta${i}`);
+            const valueDisplay = document.getElementById(`theta${i}-value`);
+            if (slider) slider.value = Math.round(wrapped);
+            if (valueDisplay) valueDisplay.textContent = Math.round(wrapped);
+        });
+    }
+
     updateModeUI() {
         const simpleConfig = document.getElementById('simple-config');
         const dhConfig = document.getElementById('dh-config');
@@ -243,6 +389,11 @@ class RobotVisualizerApp {
     }
 
     createSimulator() {
+        // A new robot starts at rest
+        this.physicsRunning = false;
+        document.getElementById('simulate-physics').checked = false;
+        this.stopServos();
+
         try {
             if (this.mode === 'simple') {
                 // Read link lengths from UI
@@ -287,12 +438,24 @@ class RobotVisualizerApp {
                 this.simulator = RobotSimulator.new_dh(dhParams);
                 console.log(`Created ${this.numJoints}-DOF DH robot`);
             }
+
+            this.applyJointLimitsToSliders();
+            this.applyObstacles();
+            this.updateWorkspace();
         } catch (error) {
             console.error('Failed to create robot:', error);
             alert('Failed to create robot: ' + error);
         }
     }
 
+    applyObstacles() {
+        try {
+            this.simulator.set_obstacles(this.obstacles);
+        } catch (error) {
+            console.error(`Failed to set obstacles (${error.code || 'UNKNOWN'}):`, error.message || error);
+        }
+    }
+
     updateAngles() {
         if (!this.simulator) return;
 
@@ -310,15 +473,334 @@ class RobotVisualizerApp {
 
             this.render();
         } catch (error) {
-            console.error('Failed to update angles:', error);
+            // RoboticsError exceptions carry a machine-readable code
+            console.error(`Failed to update angles (${error.code || 'UNKNOWN'}):`, error.message || error);
+        }
+    }
+
+    animateTo(anglesDeg) {
+        if (!this.simulator) return;
+
+        // In servo mode the preset becomes a step in the setpoints
+        if (this.servoRunning) {
+            this.jointAngles = [...anglesDeg];
+            this.updateSetpoints();
+            return;
+        }
+        // Likewise a step in the controller's reference while the physics runs
+        if (this.physicsRunning && this.controlLaw) {
+            this.jointAngles = [...anglesDeg];
+            this.updateControlReference();
+            return;
+        }
+
+        const target = anglesDeg.map(deg => deg * Math.PI / 180);
+        if (this.obstacles.length > 0) {
+            this.animateAroundObstacles(target);
+            return;
+        }
+
+        try {
+            const { duration } = this.simulator.plan_trajectory([target], { profile: 'Quintic' });
+            const start = performance.now();
+
+            const step = (now) => {
+                const t = Math.min((now - start) / 1000, duration);
+                this.simulator.follow_trajectory(t);
+                this.syncSlidersFromSimulator();
+                this.render();
+                if (t < duration) requestAnimationFrame(step);
+            };
+            requestAnimationFrame(step);
+        } catch (error) {
+            console.error(`Failed to animate (${error.code || 'UNKNOWN'}):`, error.message || error);
+        }
+    }
+
+    // Follow a collision-free joint path to the target at constant joint-space speed
+    animateAroundObstacles(target) {
+        try {
+            const { path, length } = this.simulator.plan_motion(target, { seed: 1 });
+            const speed = 1.5; // rad/s along the path
+            const duration = Math.max(length / speed, 0.3);
+            const start = performance.now();
+
+            // Arc length at which every waypoint is reached
+            const distances = [0];
+            for (let i = 1; i < path.length; i++) {
+                const step = Math.hypot(...path[i].map((value, j) => value - path[i - 1][j]));
+                distances.push(distances[i - 1] + step);
+            }
+
+            const step = (now) => {
+                const s = Math.min((now - start) / 1000 / duration, 1) * length;
+                let i = 1;
+                while (i < path.length - 1 && distances[i] < s) i++;
+                const span = distances[i] - distances[i - 1];
+                const f = span > 0 ? (s - distances[i - 1]) / span : 1;
+                this.simulator.set_angles_array(path[i].map((value, j) => path[i - 1][j] + (value - path[i - 1][j]) * f));
+                this.syncSlidersFromSimulator();
+                this.render();
+                if (s < length) requestAnimationFrame(step);
+            };
+            requestAnimationFrame(step);
+        } catch (error) {
+            console.error(`Failed to plan around obstacles (${error.code || 'UNKNOWN'}):`, error.message || error);
+        }
+    }
+
+    startPhysics() {
+        if (!this.simulator) return;
+
+        try {
+            const lengths = [];
+            for (let i = 0; i < this.numJoints; i++) {
+                lengths.push(this.mode === 'simple'
+                    ? this.linkLengths[i]
+                    : parseFloat(document.getElementById(`dh-a-${i}`).value));
+            }
+
+            if (this.mode === 'simple') {
+                // Simple robots carry no inertias: switch to the equivalent planar DH chain
+                const angles = this.simulator.get_angles();
+                this.simulator = RobotSimulator.new_dh(lengths.map(length => ({
+                    a: length, alpha: 0, d: 0, theta: 0, joint_type: 'Revolute', joint_offset: 0
+                })));
+                this.simulator.set_angles_array(angles);
+                this.applyObstacles();
+            }
+
+            // Every link is a thin rod of 1 kg/m (2 cm radius) lying along its X axis
+            this.simulator.set_link_inertias(lengths.map(length => {
+                const mass = Math.max(Math.abs(length), 0.1);
+                const radius = 0.02;
+                const transverse = mass * (3 * radius * radius + length * length) / 12;
+                return {
+                    mass,
+                    center_of_mass: [-length / 2, 0, 0],
+                    inertia: [mass * radius * radius / 2, transverse, transverse, 0, 0, 0]
+                };
+            }));
+
+            // The canvas shows the XY plane with +Y up
+            this.simulator.set_gravity(0, -9.81, 0);
+            this.simulator.reset_simulation();
+            this.applyController();
+        } catch (error) {
+            console.error(`Failed to start physics (${error.code || 'UNKNOWN'}):`, error.message || error);
+            document.getElementById('simulate-physics').checked = false;
+            return;
+        }
+
+        this.physicsRunning = true;
+        let last = performance.now();
+
+        const step = (now) => {
+            if (!this.physicsRunning) return;
+
+            // Cap the step so a hidden tab does not make the arm jump
+            const dt = Math.min((now - last) / 1000, 0.05);
+            last = now;
+
+            try {
+                if (dt > 0) this.simulator.step(dt);
+            } catch (error) {
+                console.error(`Physics step failed (${error.code || 'UNKNOWN'}):`, error.message || error);
+                this.physicsRunning = false;
+                document.getElementById('simulate-physics').checked = false;
+                return;
+            }
+
+            // Under control the sliders show the reference; only the drawing follows the arm
+            if (!this.controlLaw) this.syncSlidersFromSimulator();
+            this.render();
+            requestAnimationFrame(step);
+        };
+        requestAnimationFrame(step);
+    }
+
+    applyController() {
+        try {
+            // Default gains for the selected law, or a passive arm
+            this.simulator.set_controller(this.controlLaw ? { [this.controlLaw]: {} } : null);
+            if (this.controlLaw) {
+                this.simulator.reset_simulation();
+                this.updateControlReference();
+            }
+        } catch (error) {
+            console.error(`Failed to set controller (${error.code || 'UNKNOWN'}):`, error.message || error);
+        }
+    }
+
+    updateControlReference() {
+        try {
+            const angles = this.jointAngles.map(deg => deg * Math.PI / 180);
+            this.simulator.set_control_reference(angles);
+
+            // The operational-space target is where the sliders would put the tool;
+            // the slider angles also serve as the null-space posture
+            const positions = this.simulator.batch_joint_positions(new Float64Array(angles));
+            const n = positions.length;
+            this.simulator.set_control_target({ position: { x: positions[n - 3], y: positions[n - 2], z: positions[n - 1] } });
+            this.simulator.set_control_posture(angles);
+        } catch (error) {
+            console.error(`Failed to set control reference (${error.code || 'UNKNOWN'}):`, error.message || error);
+        }
+    }
+
+    startServos() {
+        if (!this.simulator) return;
+
+        try {
+            this.simulator.reset_servos();
+            this.updateSetpoints();
+        } catch (error) {
+            console.error(`Failed to start servos (${error.code || 'UNKNOWN'}):`, error.message || error);
+            document.getElementById('servo-mode').checked = false;
+            return;
+        }
+
+        this.servoRunning = true;
+        document.getElementById('error-plot').style.display = 'block';
+        let last = performance.now();
+
+        const step = (now) => {
+            if (!this.servoRunning) return;
+
+            const dt = Math.min((now - last) / 1000, 0.05);
+            last = now;
+
+            try {
+                if (dt > 0) this.simulator.servo_step(dt);
+            } catch (error) {
+                console.error(`Servo step failed (${error.code || 'UNKNOWN'}):`, error.message || error);
+                this.stopServos();
+                return;
+            }
+
+            // Sliders keep showing the setpoints; only the drawing follows the servos
+            this.render();
+            this.drawErrorPlot();
+            requestAnimationFrame(step);
+        };
+        requestAnimationFrame(step);
+    }
+
+    stopServos() {
+        this.servoRunning = false;
+        document.getElementById('servo-mode').checked = false;
+        document.getElementById('error-plot').style.display = 'none';
+    }
+
+    updateSetpoints() {
+        try {
+            this.simulator.set_servo_setpoints(this.jointAngles.map(deg => deg * Math.PI / 180));
+        } catch (error) {
+            console.error(`Failed to set setpoints (${error.code || 'UNKNOWN'}):`, error.message || error);
         }
     }
 
+    drawErrorPlot() {
+        const canvas = document.getElementById('error-plot');
+        const ctx = canvas.getContext('2d');
+        const history = this.simulator.get_tracking_history();
+        ctx.clearRect(0, 0, canvas.width, canvas.height);
+        if (history.length < 2) return;
+
+        // Tracking error of every joint in degrees, symmetric scale around zero
+        const errors = history.map(sample => sample.errors.map(e => e * 180 / Math.PI));
+        const range = Math.max(1, ...errors.flat().map(Math.abs));
+        const t0 = history[0].time;
+        const span = Math.max(history[history.length - 1].time - t0, 1e-6);
+        const toX = (time) => (time - t0) / span * canvas.width;
+        const toY = (error) => canvas.height / 2 - error / range * (canvas.height / 2 - 4);
+
+        ctx.strokeStyle = '#bdc3c7';
+        ctx.beginPath();
+        ctx.moveTo(0, canvas.height / 2);
+        ctx.lineTo(canvas.width, canvas.height / 2);
+        ctx.stroke();
+
+        const colors = ['#e74c3c', '#3498db', '#27ae60', '#f39c12', '#9b59b6', '#1abc9c', '#34495e'];
+        for (let joint = 0; joint < errors[0].length; joint++) {
+            ctx.strokeStyle = colors[joint % colors.length];
+            ctx.beginPath();
+            history.forEach((sample, k) => {
+                const x = toX(sample.time);
+                const y = toY(errors[k][joint]);
+                if (k === 0) ctx.moveTo(x, y); else ctx.lineTo(x, y);
+            });
+            ctx.stroke();
+        }
+
+        ctx.fillStyle = '#555';
+        ctx.font = '11px sans-serif';
+        ctx.fillText(`tracking error (±${range.toFixed(1)}°)`, 6, 14);
+    }
+
+    updateWorkspace() {
+        this.workspace = null;
+        if (!this.showWorkspace || !this.simulator) return;
+
+        try {
+            // Alpha radius relative to the arm's total length
+            const positions = this.simulator.get_joint_positions();
+            let reach = 0;
+            for (let i = 1; i < positions.length; i++) {
+                const a = positions[i - 1];
+                const b = positions[i];
+                reach += Math.hypot(b.x - a.x, b.y - a.y, b.z - a.z);
+            }
+
+            this.workspace = this.simulator.analyze_workspace({
+                sampling: { Random: { samples: 3000, seed: 1 } },
+                alpha: Math.max(reach, 0.1) * 0.08
+            });
+        } catch (error) {
+            console.error(`Failed to analyze workspace (${error.code || 'UNKNOWN'}):`, error.message || error);
+        }
+    }
+
+    drawWorkspace() {
+        const shape = this.workspace && this.workspace.alpha_shape;
+        if (!shape) return;
+
+        const ctx = this.ctx;
+        const toCanvas = ([x, y]) => [this.centerX + x * this.scale, this.centerY - y * this.scale];
+
+        // Reachable area
+        ctx.fillStyle = 'rgba(52, 152, 219, 0.12)';
+        ctx.beginPath();
+        shape.triangles.forEach(triangle => {
+            const [a, b, c] = triangle.map(i => toCanvas(shape.points[i]));
+            ctx.moveTo(a[0], a[1]);
+            ctx.lineTo(b[0], b[1]);
+            ctx.lineTo(c[0], c[1]);
+            ctx.closePath();
+        });
+        ctx.fill();
+
+        // Outer (and inner) boundary
+        ctx.strokeStyle = 'rgba(52, 152, 219, 0.6)';
+        ctx.lineWidth = 1.5;
+        ctx.beginPath();
+        shape.boundary.forEach(([i, j]) => {
+            const [a, b] = [toCanvas(shape.points[i]), toCanvas(shape.points[j])];
+            ctx.moveTo(a[0], a[1]);
+            ctx.lineTo(b[0], b[1]);
+        });
+        ctx.stroke();
+    }
+
     render() {
         this.clearCanvas();
         this.drawGrid();
         this.drawAxes();
+        this.drawWorkspace();
+        this.drawObstacles();
         this.drawRobot();
+        this.drawFrameTriads();
+        this.drawEllipsoid();
         this.updateInfo();
     }
 
@@ -410,6 +892,14 @@ class RobotVisualizerApp {
             y: this.centerY - pos.y * this.scale // Flip Y for canvas
         }));
 
+        // Links in collision are drawn solid red
+        let colliding = [];
+        try {
+            colliding = this.simulator.check_collisions().colliding_links;
+        } catch (error) {
+            console.error(`Failed to check collisions (${error.code || 'UNKNOWN'}):`, error.message || error);
+        }
+
         // Draw links
         this.ctx.lineWidth = 8;
         for (let i = 0; i < canvasPositions.length - 1; i++) {
@@ -421,7 +911,7 @@ class RobotVisualizerApp {
             gradient.addColorStop(0, '#3498db');
             gradient.addColorStop(1, '#e74c3c');
 
-            this.ctx.strokeStyle = gradient;
+            this.ctx.strokeStyle = colliding.includes(i) ? '#c0392b' : gradient;
             this.ctx.beginPath();
             this.ctx.moveTo(from.x, from.y);
             this.ctx.lineTo(to.x, to.y);
@@ -443,6 +933,86 @@ class RobotVisualizerApp {
         });
     }
 
+    drawObstacles() {
+        const ctx = this.ctx;
+        ctx.fillStyle = 'rgba(127, 140, 141, 0.5)';
+        ctx.strokeStyle = '#7f8c8d';
+        ctx.lineWidth = 2;
+
+        // Axis-aligned boxes seen from above the XY plane
+        this.obstacles.forEach(({ shape }) => {
+            if (!shape.Box) return;
+            const { center, half_extents } = shape.Box;
+            const x = this.centerX + (center[0] - half_extents[0]) * this.scale;
+            const y = this.centerY - (center[1] + half_extents[1]) * this.scale;
+            const width = 2 * half_extents[0] * this.scale;
+            const height = 2 * half_extents[1] * this.scale;
+            ctx.fillRect(x, y, width, height);
+            ctx.strokeRect(x, y, width, height);
+        });
+    }
+
+    drawFrameTriads() {
+        if (!this.simulator) return;
+
+        const frames = this.simulator.get_link_frames();
+        if (!frames) return;
+
+        const axisLength = 0.4; // meters
+        const ctx = this.ctx;
+        ctx.lineWidth = 2;
+
+        frames.forEach(frame => {
+            const m = frame.matrix; // row-major 4x4
+            const origin = {
+                x: this.centerX + m[3] * this.scale,
+                y: this.centerY - m[7] * this.scale
+            };
+
+            // Project the frame's X (red) and Y (green) axes onto the XY plane
+            [[m[0], m[4], '#e74c3c'], [m[1], m[5], '#27ae60']].forEach(([ax, ay, color]) => {
+                ctx.strokeStyle = color;
+                ctx.beginPath();
+                ctx.moveTo(origin.x, origin.y);
+                ctx.lineTo(origin.x + ax * axisLength * this.scale, origin.y - ay * axisLength * this.scale);
+                ctx.stroke();
+            });
+        });
+    }
+
+    drawEllipsoid() {
+        if (!this.showEllipsoid || !this.simulator) return;
+
+        let ellipsoid;
+        try {
+            ellipsoid = this.simulator.get_manipulability_ellipsoid();
+        } catch (error) {
+            console.error(`Failed to compute ellipsoid (${error.code || 'UNKNOWN'}):`, error.message || error);
+            return;
+        }
+
+        // Project the two largest semi-axes onto the XY plane
+        const drawScale = 0.3; // meters drawn per (m/s per rad/s)
+        const [a, b] = ellipsoid.axes;
+        const [ra, rb] = ellipsoid.radii;
+        const ctx = this.ctx;
+
+        ctx.strokeStyle = 'rgba(142, 68, 173, 0.8)';
+        ctx.fillStyle = 'rgba(142, 68, 173, 0.12)';
+        ctx.lineWidth = 2;
+        ctx.beginPath();
+        for (let i = 0; i <= 64; i++) {
+            const t = (i / 64) * 2 * Math.PI;
+            const x = ellipsoid.center.x + drawScale * (ra * Math.cos(t) * a[0] + rb * Math.sin(t) * b[0]);
+            const y = ellipsoid.center.y + drawScale * (ra * Math.cos(t) * a[1] + rb * Math.sin(t) * b[1]);
+            const cx = this.centerX + x * this.scale;
+            const cy = this.centerY - y * this.scale;
+            if (i === 0) ctx.moveTo(cx, cy); else ctx.lineTo(cx, cy);
+        }
+        ctx.fill();
+        ctx.stroke();
+    }
+
     drawJoint(x, y, radius, color) {
         this.ctx.fillStyle = color;
         this.ctx.beginPath();
@@ -471,6 +1041,39 @@ class RobotVisualizerApp {
             document.getElementById('pos-z').textContent = z.toFixed(3);
             document.getElementById('pos-dist').textContent = distance.toFixed(3);
         }
+
+        try {
+            const report = this.simulator.check_collisions();
+            const status = document.getElementById('collision-status');
+            if (report.in_collision) {
+                status.textContent = `Collision (links ${report.colliding_links.map(i => i + 1).join(', ')})`;
+                status.classList.add('warning');
+            } else {
+                status.textContent = Number.isFinite(report.min_distance) ? `${report.min_distance.toFixed(3)} m` : '-';
+                status.classList.remove('warning');
+            }
+        } catch (error) {
+            console.error(`Failed to check collisions (${error.code || 'UNKNOWN'}):`, error.message || error);
+        }
+
+        try {
+            const metrics = this.simulator.get_manipulability();
+            const singularity = this.simulator.check_singularity(1e-2);
+            const status = document.getElementById('singularity-status');
+
+            document.getElementById('manipulability').textContent = metrics.manipulability.toFixed(3);
+            if (singularity.singular) {
+                const kind = singularity.singularities[0].kind.toLowerCase();
+                const joints = singularity.singularities[0].joints.map(j => j + 1).join(', ');
+                status.textContent = `Near ${kind} singularity (joints ${joints})`;
+                status.classList.add('warning');
+            } else {
+                status.textContent = 'OK';
+                status.classList.remove('warning');
+            }
+        } catch (error) {
+            console.error(`Failed to check singularity (${error.code || 'UNKNOWN'}):`, error.message || error);
+        }
     }
 }
 
@@ -0,0 +1,81 @@
+import init, { RobotSimulator } from './pkg/robotics_wasm.js';
+
+const ROUNDS = 10;
+
+// Best wall-clock time in milliseconds over ROUNDS runs
+function bestOf(run) {
+    let best = Infinity;
+    for (let round = 0; round < ROUNDS; round++) {
+        const start = performance.now();
+        run();
+        best = Math.min(best, performance.now() - start);
+    }
+    return best;
+}
+
+function randomConfigurations(count, dof) {
+    const values = new Float64Array(count * dof);
+    for (let i = 0; i < values.length; i++) {
+        values[i] = (Math.random() * 2 - 1) * 3;
+    }
+    return values;
+}
+
+function runBenchmark(presetName, count) {
+    const simulator = RobotSimulator.from_preset(presetName);
+    const dof = simulator.num_joints();
+    const values = randomConfigurations(count, dof);
+    // Keep results alive so the work cannot be optimized away
+    let sink = 0;
+
+    // Object path: one set_angles_array + get_joint_positions round trip per configuration
+    // (limits are lifted so both paths see the same joint values)
+    simulator.set_joint_limits(Array.from({ length: dof }, () => ({})));
+    const objects = bestOf(() => {
+        for (let k = 0; k < count; k++) {
+            simulator.set_angles_array(Array.from(values.subarray(k * dof, (k + 1) * dof)));
+            const positions = simulator.get_joint_positions();
+            sink += positions[positions.length - 1].x;
+        }
+    });
+
+    // Batch path: one call, results read straight out of WASM memory
+    const positions = bestOf(() => {
+        const out = simulator.batch_joint_positions(values);
+        sink += out[out.length - 3];
+    });
+    const frames = bestOf(() => {
+        const out = simulator.batch_link_frames(values);
+        sink += out[out.length - 13];
+    });
+
+    simulator.free();
+
+    const perConfig = (ms) => `${((ms * 1e6) / count).toFixed(0).padStart(8)} ns/config`;
+    return [
+        `${presetName}: ${count} configurations, ${dof} joints (checksum ${sink.toFixed(3)})`,
+        `  get_joint_positions    ${perConfig(objects)}  1.00x`,
+        `  batch_joint_positions  ${perConfig(positions)}  ${(objects / positions).toFixed(2)}x`,
+        `  batch_link_frames      ${perConfig(frames)}  ${(objects / frames).toFixed(2)}x`,
+    ].join('\n');
+}
+
+async function main() {
+    await init();
+
+    const output = document.getElementById('bench-output');
+    output.textContent = 'Ready.';
+
+    document.getElementById('run-bench').addEventListener('click', () => {
+        const presetName = document.getElementById('preset-select').value;
+        const count = parseInt(document.getElementById('config-count').value, 10);
+        output.textContent = 'Running...';
+
+        // Let the status repaint before the benchmark blocks the main thread
+        setTimeout(() => {
+            output.textContent = runBenchmark(presetName, count);
+        }, 0);
+    });
+}
+
+main();
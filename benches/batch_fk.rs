@@ -0,0 +1,72 @@
+//! Object-based vs batch forward kinematics
+//!
+//! Run with `cargo bench --bench batch_fk`. The object path mirrors what
+//! `get_joint_positions` does per configuration (set the angles, build a
+//! `Vec<JointPosition3D>`); the batch path writes every configuration into one
+//! reused flat buffer. The browser-side comparison, including the
+//! serde-wasm-bindgen cost, lives in `bench.html`.
+
+use robotics_wasm::batch::{batch_forward_kinematics, BatchOutput};
+use robotics_wasm::kinematics::{forward_kinematics, link_frames};
+use robotics_wasm::presets::Preset;
+use robotics_wasm::rng::SplitMix64;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const CONFIGURATIONS: usize = 10_000;
+const ROUNDS: usize = 20;
+
+/// Best wall-clock time of `ROUNDS` runs of `run`
+fn best_of(mut run: impl FnMut()) -> Duration {
+    (0..ROUNDS)
+        .map(|_| {
+            let start = Instant::now();
+            run();
+            start.elapsed()
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+fn report(label: &str, elapsed: Duration, baseline: Duration) {
+    let per_config = elapsed.as_secs_f64() * 1e9 / CONFIGURATIONS as f64;
+    let speedup = baseline.as_secs_f64() / elapsed.as_secs_f64();
+    println!("  {:<28} {:>10.1} ns/config  {:>5.2}x", label, per_config, speedup);
+}
+
+fn main() {
+    for preset in [Preset::Ur5, Preset::Puma560, Preset::Panda] {
+        let mut robot = preset.build();
+        let dof = robot.num_joints();
+        let mut rng = SplitMix64::new(42);
+        let joint_values: Vec<f64> = (0..CONFIGURATIONS * dof).map(|_| rng.range(-3.0, 3.0)).collect();
+        let mut buffer = Vec::new();
+
+        let objects = best_of(|| {
+            for config in joint_values.chunks_exact(dof) {
+                robot.joint_angles.copy_from_slice(config);
+                black_box(forward_kinematics(black_box(&robot)));
+            }
+        });
+        let frame_objects = best_of(|| {
+            for config in joint_values.chunks_exact(dof) {
+                robot.joint_angles.copy_from_slice(config);
+                black_box(link_frames(black_box(&robot)));
+            }
+        });
+        let batch_positions = best_of(|| {
+            batch_forward_kinematics(&robot, black_box(&joint_values), BatchOutput::Positions, &mut buffer).unwrap();
+            black_box(&buffer);
+        });
+        let batch_frames = best_of(|| {
+            batch_forward_kinematics(&robot, black_box(&joint_values), BatchOutput::Frames, &mut buffer).unwrap();
+            black_box(&buffer);
+        });
+
+        println!("{} ({} configurations, {} joints)", preset.name(), CONFIGURATIONS, dof);
+        report("forward_kinematics", objects, objects);
+        report("batch positions", batch_positions, objects);
+        report("link_frames", frame_objects, frame_objects);
+        report("batch frames", batch_frames, frame_objects);
+    }
+}
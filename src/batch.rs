@@ -0,0 +1,163 @@
+use crate::error::{check_finite, RoboticsError};
+use crate::kinematics::visit_link_frames;
+use crate::robot::RobotArm;
+
+/// What `batch_forward_kinematics` writes for every frame of a configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchOutput {
+    /// Frame origin: x, y, z (3 values per frame)
+    Positions,
+    /// Homogeneous 4x4 transform, row-major (16 values per frame)
+    Frames,
+}
+
+impl BatchOutput {
+    /// Number of values written per frame
+    pub fn values_per_frame(self) -> usize {
+        match self {
+            BatchOutput::Positions => 3,
+            BatchOutput::Frames => 16,
+        }
+    }
+}
+
+/// Forward kinematics for many joint configurations at once, into a flat buffer
+///
+/// `joint_values` holds `count` configurations of `num_joints` values each,
+/// back to back (row-major count x DOF). For every configuration the base and
+/// link frames (N+1 frames, see `link_frames`) are written to `out` in order,
+/// so configuration k starts at `k * (N+1) * output.values_per_frame()`.
+///
+/// The joint values are used as given: the robot's limit policy is not
+/// applied. `out` is resized to fit and its capacity is reused, so calling
+/// this repeatedly with the same buffer does not allocate once it has grown.
+///
+/// Returns the number of configurations.
+pub fn batch_forward_kinematics(
+    robot: &RobotArm,
+    joint_values: &[f64],
+    output: BatchOutput,
+    out: &mut Vec<f64>,
+) -> Result<usize, RoboticsError> {
+    let dof = robot.num_joints();
+    if dof == 0 {
+        return Err(RoboticsError::InvalidArgument(
+            "batch forward kinematics requires a robot with at least one joint".to_string(),
+        ));
+    }
+    if !joint_values.len().is_multiple_of(dof) {
+        return Err(RoboticsError::InvalidArgument(format!(
+            "batch of {} joint values is not a multiple of the {} joints",
+            joint_values.len(),
+            dof
+        )));
+    }
+    check_finite(joint_values)?;
+
+    let count = joint_values.len() / dof;
+    let stride = output.values_per_frame();
+    let config_len = (dof + 1) * stride;
+    out.resize(count * config_len, 0.0);
+
+    for (config, values) in out.chunks_exact_mut(config_len).zip(joint_values.chunks_exact(dof)) {
+        visit_link_frames(robot, values, |i, frame| {
+            let slot = &mut config[i * stride..(i + 1) * stride];
+            match output {
+                BatchOutput::Positions => {
+                    let position = frame.position();
+                    slot.copy_from_slice(&[position.x, position.y, position.z]);
+                }
+                BatchOutput::Frames => slot.copy_from_slice(&frame.to_row_major()),
+            }
+        });
+    }
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry3d::Transform3D;
+    use crate::kinematics::{forward_kinematics, link_frames};
+    use crate::presets::Preset;
+    use crate::rng::SplitMix64;
+
+    const EPSILON: f64 = 1e-12;
+
+    fn random_configs(robot: &RobotArm, count: usize, seed: u64) -> Vec<f64> {
+        let mut rng = SplitMix64::new(seed);
+        (0..count * robot.num_joints()).map(|_| rng.range(-2.0, 2.0)).collect()
+    }
+
+    #[test]
+    fn test_positions_match_forward_kinematics() {
+        let mut robot = Preset::Ur5.build();
+        robot.base = Transform3D::from_xyz_rpy([0.1, -0.2, 0.3], [0.0, 0.0, 0.5]);
+        let values = random_configs(&robot, 5, 7);
+        let mut out = Vec::new();
+
+        let count = batch_forward_kinematics(&robot, &values, BatchOutput::Positions, &mut out).unwrap();
+
+        assert_eq!(count, 5);
+        assert_eq!(out.len(), 5 * 7 * 3);
+        for (k, config) in values.chunks(6).enumerate() {
+            robot.joint_angles = config.to_vec();
+            for (i, position) in forward_kinematics(&robot).iter().enumerate() {
+                let offset = (k * 7 + i) * 3;
+                assert!((out[offset] - position.x).abs() < EPSILON);
+                assert!((out[offset + 1] - position.y).abs() < EPSILON);
+                assert!((out[offset + 2] - position.z).abs() < EPSILON);
+            }
+        }
+    }
+
+    #[test]
+    fn test_frames_match_link_frames() {
+        for preset in [Preset::Puma560, Preset::Panda, Preset::Scara] {
+            let mut robot = preset.build();
+            let dof = robot.num_joints();
+            let values = random_configs(&robot, 3, 11);
+            let mut out = Vec::new();
+
+            batch_forward_kinematics(&robot, &values, BatchOutput::Frames, &mut out).unwrap();
+
+            for (k, config) in values.chunks(dof).enumerate() {
+                robot.joint_angles = config.to_vec();
+                for (i, frame) in link_frames(&robot).iter().enumerate() {
+                    let offset = (k * (dof + 1) + i) * 16;
+                    let expected = frame.to_row_major();
+                    assert!(out[offset..offset + 16].iter().zip(expected.iter()).all(|(a, b)| (a - b).abs() < EPSILON));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_buffer_is_reused() {
+        let robot = RobotArm::new(vec![1.0, 1.0]);
+        let mut out = Vec::new();
+
+        batch_forward_kinematics(&robot, &[0.0; 20], BatchOutput::Frames, &mut out).unwrap();
+        let capacity = out.capacity();
+        let pointer = out.as_ptr();
+        batch_forward_kinematics(&robot, &[0.0, 0.0], BatchOutput::Positions, &mut out).unwrap();
+
+        assert_eq!(out, vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 2.0, 0.0, 0.0]);
+        assert_eq!(out.capacity(), capacity);
+        assert_eq!(out.as_ptr(), pointer);
+    }
+
+    #[test]
+    fn test_rejects_bad_batches() {
+        let robot = RobotArm::new(vec![1.0, 1.0]);
+        let mut out = Vec::new();
+
+        let err = batch_forward_kinematics(&robot, &[0.0; 3], BatchOutput::Positions, &mut out).unwrap_err();
+        assert_eq!(err.code(), "INVALID_ARGUMENT");
+        let err = batch_forward_kinematics(&robot, &[0.0, f64::NAN], BatchOutput::Positions, &mut out).unwrap_err();
+        assert_eq!(err.code(), "NON_FINITE");
+        let empty = RobotArm::new(Vec::new());
+        assert!(batch_forward_kinematics(&empty, &[], BatchOutput::Positions, &mut out).is_err());
+    }
+}
@@ -0,0 +1,508 @@
+use crate::error::{check_finite, RoboticsError};
+use crate::geometry3d::{Point3D, Quaternion, Transform3D};
+use crate::inverse_kinematics::{solve_ik, IkOptions, IkTarget};
+use crate::kinematics::end_effector_transform;
+use crate::manipulability::{classify_singularity, TaskSpace};
+use crate::robot::RobotArm;
+use nalgebra::Vector3;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+/// Largest number of samples a single Cartesian path may be split into
+const MAX_PATH_SAMPLES: usize = 100_000;
+
+/// End-effector goal given from JS: a position and an optional orientation
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CartesianTarget {
+    pub position: Point3D,
+    /// Goal orientation (the start orientation is kept when omitted)
+    pub quaternion: Option<Quaternion>,
+}
+
+impl CartesianTarget {
+    /// Goal pose, taking the orientation from `start` when none is given
+    pub fn to_transform(&self, start: &Transform3D) -> Transform3D {
+        let rotation = match &self.quaternion {
+            Some(q) => q.to_rotation_matrix(),
+            None => start.rotation_matrix(),
+        };
+
+        Transform3D::from_parts(&rotation, &Vector3::new(self.position.x, self.position.y, self.position.z))
+    }
+}
+
+/// Geometric path of the end-effector
+#[derive(Debug, Clone, Copy)]
+pub enum CartesianPath {
+    /// Straight line between two poses (orientation interpolated by SLERP)
+    Linear { start: Transform3D, end: Transform3D },
+    /// Circular arc from `start` through `via` to `end` (orientation interpolated by SLERP)
+    Arc {
+        start: Transform3D,
+        via: Point3D,
+        end: Transform3D,
+    },
+}
+
+/// Circle through three points, parameterized by the angle swept from the first one
+#[derive(Debug, Clone, Copy)]
+struct Circle {
+    center: Vector3<f64>,
+    radius: f64,
+    /// Unit vector from the center to the start point
+    u: Vector3<f64>,
+    /// Unit vector completing the plane of the arc (direction of travel at the start)
+    w: Vector3<f64>,
+    /// Angle swept from the start to the end point, in (0, 2π)
+    sweep: f64,
+}
+
+impl Circle {
+    fn through(a: &Vector3<f64>, b: &Vector3<f64>, c: &Vector3<f64>) -> Result<Self, RoboticsError> {
+        check_finite(&[a.as_slice(), b.as_slice(), c.as_slice()].concat())?;
+        let (ab, ac) = (b - a, c - a);
+        let normal = ab.cross(&ac);
+        let scale = ab.norm().max(ac.norm()).max(1.0);
+        if normal.norm() < 1e-9 * scale * scale {
+            return Err(RoboticsError::InvalidArgument(
+                "arc points are collinear or coincide".to_string(),
+            ));
+        }
+
+        let center = a + (ac * ab.norm_squared() - ab * ac.norm_squared()).cross(&normal) / (2.0 * normal.norm_squared());
+        let radius = (a - center).norm();
+        let u = (a - center) / radius;
+        let w = normal.normalize().cross(&u);
+
+        // Going counter-clockwise about the normal visits a, b, c in that order
+        let angle = |p: &Vector3<f64>| {
+            let angle = (p - center).dot(&w).atan2((p - center).dot(&u));
+            if angle < 0.0 {
+                angle + 2.0 * PI
+            } else {
+                angle
+            }
+        };
+
+        Ok(Circle {
+            center,
+            radius,
+            u,
+            w,
+            sweep: angle(c),
+        })
+    }
+
+    fn point_at(&self, fraction: f64) -> Vector3<f64> {
+        let angle = fraction * self.sweep;
+        self.center + (self.u * angle.cos() + self.w * angle.sin()) * self.radius
+    }
+}
+
+impl CartesianPath {
+    fn start(&self) -> &Transform3D {
+        match self {
+            CartesianPath::Linear { start, .. } | CartesianPath::Arc { start, .. } => start,
+        }
+    }
+
+    fn end(&self) -> &Transform3D {
+        match self {
+            CartesianPath::Linear { end, .. } | CartesianPath::Arc { end, .. } => end,
+        }
+    }
+
+    /// Length of the position path
+    pub fn length(&self) -> Result<f64, RoboticsError> {
+        Ok(match self {
+            CartesianPath::Linear { start, end } => (translation(end) - translation(start)).norm(),
+            CartesianPath::Arc { start, via, end } => {
+                let circle = Circle::through(&translation(start), &to_vector(via), &translation(end))?;
+                circle.radius * circle.sweep
+            }
+        })
+    }
+
+    /// Rotation angle between the start and end orientations
+    pub fn rotation_angle(&self) -> f64 {
+        self.start().rotation_distance(self.end())
+    }
+
+    /// Poses at `count + 1` evenly spaced fractions of the path (both ends included)
+    pub fn sample(&self, count: usize) -> Result<Vec<Transform3D>, RoboticsError> {
+        let count = count.max(1);
+        let (start, end) = (self.start(), self.end());
+        let circle = match self {
+            CartesianPath::Linear { .. } => None,
+            CartesianPath::Arc { start, via, end } => {
+                Some(Circle::through(&translation(start), &to_vector(via), &translation(end))?)
+            }
+        };
+
+        Ok((0..=count)
+            .map(|i| {
+                let fraction = i as f64 / count as f64;
+                let pose = start.interpolate(end, fraction);
+                match &circle {
+                    Some(circle) => Transform3D::from_parts(&pose.rotation_matrix(), &circle.point_at(fraction)),
+                    None => pose,
+                }
+            })
+            .collect())
+    }
+}
+
+/// Options for Cartesian path following
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CartesianOptions {
+    /// Largest distance between consecutive path samples (meters)
+    pub resolution: f64,
+    /// Largest rotation between consecutive path samples (radians)
+    pub angular_resolution: f64,
+    /// Follow the orientation as well as the position (needs enough joints)
+    pub track_orientation: bool,
+    /// Relative singular value below which a sample is reported as singular
+    pub singularity_tolerance: f64,
+    /// Largest change of a joint between samples before it is reported as a jump
+    pub max_joint_step: f64,
+    /// Solver settings for every sample
+    pub ik: IkOptions,
+}
+
+impl Default for CartesianOptions {
+    fn default() -> Self {
+        CartesianOptions {
+            resolution: 0.01,
+            angular_resolution: 0.05,
+            track_orientation: false,
+            singularity_tolerance: 1e-2,
+            max_joint_step: 0.5,
+            ik: IkOptions::default(),
+        }
+    }
+}
+
+/// Why a path sample is problematic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PathIssueKind {
+    /// IK did not converge: the path leaves the reachable workspace
+    Unreachable,
+    /// The arm is at (or near) a singular configuration
+    Singular,
+    /// A joint moves more than `max_joint_step` between consecutive samples
+    JointJump,
+}
+
+/// A problem found at one sample of the path
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathIssue {
+    pub kind: PathIssueKind,
+    /// Sample index
+    pub index: usize,
+    /// Fraction of the path (0 at the start, 1 at the end)
+    pub fraction: f64,
+    /// Residual IK error at the sample
+    pub error: f64,
+}
+
+/// Joint-space realization of a Cartesian path
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CartesianPlan {
+    /// Path length (meters)
+    pub length: f64,
+    /// Sampled end-effector positions
+    pub positions: Vec<Point3D>,
+    /// Joint values reaching every sample, starting at the robot's configuration
+    pub joint_path: Vec<Vec<f64>>,
+    /// Every problematic sample, in path order
+    pub issues: Vec<PathIssue>,
+    /// Whether every sample was reached (no `Unreachable` issue)
+    pub reachable: bool,
+}
+
+/// Converts a Cartesian path to joint values by solving IK at every sample
+///
+/// The path is split so that consecutive samples are at most `resolution` apart
+/// and rotate at most `angular_resolution`. Each IK solve is seeded with the
+/// previous sample's solution, so the arm follows the path continuously; samples
+/// that cannot be reached, that are close to a singularity or that need a large
+/// joint jump are reported in `issues`. The robot itself is not modified.
+pub fn plan_cartesian_path(
+    robot: &RobotArm,
+    path: &CartesianPath,
+    options: &CartesianOptions,
+) -> Result<CartesianPlan, RoboticsError> {
+    for (name, value) in [
+        ("resolution", options.resolution),
+        ("angular resolution", options.angular_resolution),
+        ("maximum joint step", options.max_joint_step),
+    ] {
+        if !(value > 0.0 && value.is_finite()) {
+            return Err(RoboticsError::InvalidArgument(format!("{} must be positive, got {}", name, value)));
+        }
+    }
+    check_finite(&path.start().to_row_major())?;
+    check_finite(&path.end().to_row_major())?;
+
+    let length = path.length()?;
+    let count = (length / options.resolution)
+        .ceil()
+        .max((path.rotation_angle() / options.angular_resolution).ceil())
+        .max(1.0);
+    if count > MAX_PATH_SAMPLES as f64 {
+        return Err(RoboticsError::InvalidArgument(format!(
+            "path needs {} samples at this resolution, more than {}",
+            count, MAX_PATH_SAMPLES
+        )));
+    }
+    let count = count as usize;
+    let poses = path.sample(count)?;
+
+    let task_space = TaskSpace::for_robot(robot);
+    let mut arm = robot.clone();
+    let mut joint_path = Vec::with_capacity(poses.len());
+    let mut issues = Vec::new();
+
+    for (index, pose) in poses.iter().enumerate() {
+        let fraction = index as f64 / count as f64;
+        let target = if options.track_orientation {
+            IkTarget::Pose(*pose)
+        } else {
+            IkTarget::Position(pose.position())
+        };
+
+        let solution = solve_ik(&arm, &target, &options.ik);
+        let mut issue = |kind| {
+            issues.push(PathIssue {
+                kind,
+                index,
+                fraction,
+                error: solution.error,
+            })
+        };
+
+        if !solution.converged {
+            issue(PathIssueKind::Unreachable);
+        }
+        if arm
+            .joint_angles
+            .iter()
+            .zip(&solution.joint_values)
+            .any(|(before, after)| (after - before).abs() > options.max_joint_step)
+        {
+            issue(PathIssueKind::JointJump);
+        }
+
+        arm.joint_angles.clone_from(&solution.joint_values);
+        if classify_singularity(&arm, task_space, options.singularity_tolerance)?.singular {
+            issue(PathIssueKind::Singular);
+        }
+
+        joint_path.push(solution.joint_values);
+    }
+
+    Ok(CartesianPlan {
+        length,
+        positions: poses.iter().map(Transform3D::position).collect(),
+        joint_path,
+        reachable: !issues.iter().any(|issue| issue.kind == PathIssueKind::Unreachable),
+        issues,
+    })
+}
+
+/// Straight line from the robot's current end-effector pose to `target`
+pub fn linear_path(robot: &RobotArm, target: &CartesianTarget) -> CartesianPath {
+    let start = end_effector_transform(robot);
+    CartesianPath::Linear {
+        end: target.to_transform(&start),
+        start,
+    }
+}
+
+/// Circular arc from the robot's current end-effector pose through `via` to `target`
+pub fn arc_path(robot: &RobotArm, via: Point3D, target: &CartesianTarget) -> CartesianPath {
+    let start = end_effector_transform(robot);
+    CartesianPath::Arc {
+        end: target.to_transform(&start),
+        start,
+        via,
+    }
+}
+
+fn translation(transform: &Transform3D) -> Vector3<f64> {
+    to_vector(&transform.position())
+}
+
+fn to_vector(point: &Point3D) -> Vector3<f64> {
+    Vector3::new(point.x, point.y, point.z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dh_parameters::DHParameter;
+
+    const EPSILON: f64 = 1e-6;
+
+    fn planar_2r() -> RobotArm {
+        let mut robot = RobotArm::planar(vec![1.0, 1.0]);
+        robot.set_joint_angles(vec![0.3, 1.2]).unwrap();
+        robot
+    }
+
+    fn target(x: f64, y: f64, z: f64) -> CartesianTarget {
+        CartesianTarget {
+            position: Point3D::new(x, y, z),
+            quaternion: None,
+        }
+    }
+
+    #[test]
+    fn test_line_is_followed_exactly() {
+        let robot = planar_2r();
+        let path = linear_path(&robot, &target(1.2, 0.4, 0.0));
+        let plan = plan_cartesian_path(&robot, &path, &CartesianOptions::default()).unwrap();
+
+        assert!(plan.reachable);
+        assert!(plan.issues.is_empty());
+        assert_eq!(plan.joint_path.len(), plan.positions.len());
+        assert!(plan.positions.len() as f64 >= plan.length / 0.01);
+
+        // Every joint sample puts the tool on the straight line
+        let (start, end) = (plan.positions[0], plan.positions[plan.positions.len() - 1]);
+        let direction = Vector3::new(end.x - start.x, end.y - start.y, 0.0).normalize();
+        let mut arm = robot.clone();
+        for values in &plan.joint_path {
+            arm.joint_angles.clone_from(values);
+            let p = end_effector_transform(&arm).position();
+            let offset = Vector3::new(p.x - start.x, p.y - start.y, p.z - start.z);
+            assert!(offset.cross(&direction).norm() < EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_arc_through_three_points() {
+        let start = Transform3D::translation(1.0, 0.0, 0.0);
+        let end = Transform3D::translation(-1.0, 0.0, 0.0);
+        let path = CartesianPath::Arc {
+            start,
+            via: Point3D::new(0.0, 1.0, 0.0),
+            end,
+        };
+
+        assert!((path.length().unwrap() - PI).abs() < 1e-12);
+        let samples = path.sample(8).unwrap();
+        for pose in &samples {
+            let p = pose.position();
+            assert!(((p.x * p.x + p.y * p.y).sqrt() - 1.0).abs() < 1e-12);
+            assert!(p.y >= -1e-12);
+        }
+        // Halfway is the top of the unit circle
+        let middle = samples[4].position();
+        assert!(middle.x.abs() < 1e-12 && (middle.y - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_arc_long_way_round() {
+        // Via point below the chord: the arc sweeps 3/4 of the circle
+        let path = CartesianPath::Arc {
+            start: Transform3D::translation(1.0, 0.0, 0.0),
+            via: Point3D::new(0.0, -1.0, 0.0),
+            end: Transform3D::translation(0.0, 1.0, 0.0),
+        };
+        assert!((path.length().unwrap() - 1.5 * PI).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_collinear_arc_is_rejected() {
+        let path = CartesianPath::Arc {
+            start: Transform3D::translation(0.0, 0.0, 0.0),
+            via: Point3D::new(1.0, 0.0, 0.0),
+            end: Transform3D::translation(2.0, 0.0, 0.0),
+        };
+        assert_eq!(path.length().unwrap_err().code(), "INVALID_ARGUMENT");
+    }
+
+    #[test]
+    fn test_non_finite_via_point_is_rejected() {
+        let robot = RobotArm::planar(vec![1.0, 1.0]);
+        let path = CartesianPath::Arc {
+            start: Transform3D::translation(1.0, 0.0, 0.0),
+            via: Point3D::new(f64::NAN, 1.0, 0.0),
+            end: Transform3D::translation(0.0, 1.0, 0.0),
+        };
+        assert_eq!(path.length().unwrap_err().code(), "NON_FINITE");
+        assert_eq!(path.sample(10).unwrap_err().code(), "NON_FINITE");
+        let error = plan_cartesian_path(&robot, &path, &CartesianOptions::default()).unwrap_err();
+        assert_eq!(error.code(), "NON_FINITE");
+    }
+
+    #[test]
+    fn test_orientation_is_slerped() {
+        let start = Transform3D::identity();
+        let end = Transform3D::translation(1.0, 0.0, 0.0).compose(&Transform3D::rotation_z(PI / 2.0));
+        let samples = CartesianPath::Linear { start, end }.sample(2).unwrap();
+
+        let (_, _, yaw) = samples[1].rpy();
+        assert!((yaw - PI / 4.0).abs() < 1e-12);
+        assert!((samples[1].position().x - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_line_leaving_the_workspace_is_reported() {
+        let robot = planar_2r();
+        let path = linear_path(&robot, &target(2.5, 0.0, 0.0));
+        let plan = plan_cartesian_path(&robot, &path, &CartesianOptions::default()).unwrap();
+
+        assert!(!plan.reachable);
+        let first = plan.issues.iter().find(|issue| issue.kind == PathIssueKind::Unreachable).unwrap();
+        // The tool leaves the 2 m reach circle close to x = 2
+        let leaving = plan.positions[first.index];
+        assert!((leaving.x * leaving.x + leaving.y * leaving.y).sqrt() > 1.99);
+        // The stretched arm at the boundary is singular
+        assert!(plan.issues.iter().any(|issue| issue.kind == PathIssueKind::Singular));
+    }
+
+    #[test]
+    fn test_pose_tracking_with_spatial_arm() {
+        let mut robot = RobotArm::from_dh_params(vec![
+            DHParameter::revolute(0.0, PI / 2.0, 0.4, 0.0),
+            DHParameter::revolute(0.6, 0.0, 0.0, 0.0),
+            DHParameter::revolute(0.5, 0.0, 0.0, 0.0),
+            DHParameter::revolute(0.0, PI / 2.0, 0.0, PI / 2.0),
+            DHParameter::revolute(0.0, -PI / 2.0, 0.3, 0.0),
+            DHParameter::revolute(0.0, 0.0, 0.1, 0.0),
+        ]);
+        robot.set_joint_angles(vec![0.1, 0.6, -1.0, 0.4, 0.7, 0.2]).unwrap();
+        let start = end_effector_transform(&robot);
+        let p = start.position();
+
+        let options = CartesianOptions {
+            track_orientation: true,
+            ..CartesianOptions::default()
+        };
+        let path = linear_path(&robot, &target(p.x + 0.1, p.y - 0.1, p.z + 0.05));
+        let plan = plan_cartesian_path(&robot, &path, &options).unwrap();
+        assert!(plan.reachable, "{:?}", plan.issues);
+
+        // Orientation is held along the whole line
+        let mut arm = robot.clone();
+        for values in &plan.joint_path {
+            arm.joint_angles.clone_from(values);
+            let rotation = end_effector_transform(&arm).rotation_matrix();
+            assert!((rotation - start.rotation_matrix()).abs().max() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_invalid_options() {
+        let robot = planar_2r();
+        let path = linear_path(&robot, &target(1.0, 0.5, 0.0));
+        let options = CartesianOptions {
+            resolution: 0.0,
+            ..CartesianOptions::default()
+        };
+        assert_eq!(plan_cartesian_path(&robot, &path, &options).unwrap_err().code(), "INVALID_ARGUMENT");
+    }
+}
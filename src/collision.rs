@@ -0,0 +1,678 @@
+use crate::dh_parameters::DHConvention;
+use crate::error::{check_dimension, check_finite, RoboticsError};
+use crate::geometry3d::Transform3D;
+use crate::kinematics::visit_link_frames;
+use crate::robot::RobotArm;
+use nalgebra::{Matrix3, Vector3};
+use serde::{Deserialize, Serialize};
+
+/// Capsule radius of the default link geometry (meters)
+pub const DEFAULT_LINK_RADIUS: f64 = 0.05;
+
+/// Segments shorter than this are left out of the capsule geometry
+const DEGENERATE_LENGTH: f64 = 1e-9;
+
+/// Convex collision shape, in the frame it is attached to
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Shape {
+    Sphere { center: [f64; 3], radius: f64 },
+    /// Segment from `start` to `end` swept by a sphere of `radius`
+    Capsule { start: [f64; 3], end: [f64; 3], radius: f64 },
+    /// Box centered at `center`, rotated by `rpy` and extending `half_extents` along its own axes
+    Box {
+        center: [f64; 3],
+        #[serde(default)]
+        rpy: [f64; 3],
+        half_extents: [f64; 3],
+    },
+}
+
+impl Shape {
+    /// Check that every value is finite and every size non-negative
+    pub fn validate(&self) -> Result<(), String> {
+        let (values, sizes): (Vec<f64>, Vec<f64>) = match self {
+            Shape::Sphere { center, radius } => (center.to_vec(), vec![*radius]),
+            Shape::Capsule { start, end, radius } => ([&start[..], &end[..]].concat(), vec![*radius]),
+            Shape::Box {
+                center,
+                rpy,
+                half_extents,
+            } => ([&center[..], &rpy[..]].concat(), half_extents.to_vec()),
+        };
+
+        if values.iter().chain(&sizes).any(|value| !value.is_finite()) {
+            return Err("shape values must be finite".to_string());
+        }
+        if sizes.iter().any(|&size| size < 0.0) {
+            return Err("shape sizes must be non-negative".to_string());
+        }
+        Ok(())
+    }
+
+    /// The shape placed in the world by `frame`
+    fn place(&self, frame: &Transform3D) -> Solid {
+        let rotation = frame.rotation_matrix();
+        let point = |p: &[f64; 3]| rotation * Vector3::from(*p) + frame.translation_vector();
+
+        match self {
+            Shape::Sphere { center, radius } => Solid {
+                core: Core::Point(point(center)),
+                radius: *radius,
+            },
+            Shape::Capsule { start, end, radius } => Solid {
+                core: Core::Segment(point(start), point(end)),
+                radius: *radius,
+            },
+            Shape::Box {
+                center,
+                rpy,
+                half_extents,
+            } => Solid {
+                core: Core::Box {
+                    center: point(center),
+                    axes: rotation * Transform3D::from_rpy(rpy[0], rpy[1], rpy[2]).rotation_matrix(),
+                    half_extents: Vector3::from(*half_extents),
+                },
+                radius: 0.0,
+            },
+        }
+    }
+}
+
+/// Collision geometry of one link
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LinkGeometry {
+    /// Capsules of the given radius following the link from its joint frame to
+    /// the next one; a DH link gets one capsule along its d offset and one
+    /// along its a offset, and zero-length links get none
+    Capsules { radius: f64 },
+    /// Shapes fixed in the link's frame (entry i+1 of `link_frames` for link i,
+    /// which includes the tool frame for the last link)
+    Shapes(Vec<Shape>),
+}
+
+/// Static shape in the world frame
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Obstacle {
+    #[serde(default)]
+    pub name: String,
+    pub shape: Shape,
+}
+
+/// A body taking part in a collision query
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "index")]
+pub enum Body {
+    Link(usize),
+    Obstacle(usize),
+}
+
+/// Separation between two bodies
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BodyPair {
+    pub first: Body,
+    pub second: Body,
+    /// Gap between the closest shapes, or minus their penetration depth when they overlap
+    pub distance: f64,
+}
+
+/// Result of a collision query for one configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollisionReport {
+    pub in_collision: bool,
+    /// Every pair touching or overlapping (distance ≤ 0), deepest first
+    pub pairs: Vec<BodyPair>,
+    /// Links in at least one of `pairs`, ascending
+    pub colliding_links: Vec<usize>,
+    /// Smallest separation over all checked pairs (infinite when nothing was checked)
+    pub min_distance: f64,
+    /// The pair at `min_distance`
+    pub closest: Option<BodyPair>,
+}
+
+/// Link geometry and world obstacles of a robot
+///
+/// Every pair of links is checked for self-collision except neighbours: links
+/// with no geometry between them along the chain always touch at their shared
+/// joint. Every link is checked against every obstacle.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollisionModel {
+    /// One entry per link (link i is moved by joint i)
+    pub links: Vec<LinkGeometry>,
+    #[serde(default)]
+    pub obstacles: Vec<Obstacle>,
+}
+
+impl CollisionModel {
+    /// Capsules of `radius` around every link, and no obstacles
+    pub fn for_robot(robot: &RobotArm, radius: f64) -> Self {
+        CollisionModel {
+            links: vec![LinkGeometry::Capsules { radius }; robot.num_joints()],
+            obstacles: Vec::new(),
+        }
+    }
+
+    /// Check the model against the robot: one geometry per link and valid shapes
+    pub fn validate(&self, robot: &RobotArm) -> Result<(), RoboticsError> {
+        check_dimension(robot.num_joints(), self.links.len())?;
+
+        for (i, geometry) in self.links.iter().enumerate() {
+            let result = match geometry {
+                LinkGeometry::Capsules { radius } if !radius.is_finite() || *radius < 0.0 => {
+                    Err(format!("capsule radius must be finite and non-negative, got {}", radius))
+                }
+                LinkGeometry::Capsules { .. } => Ok(()),
+                LinkGeometry::Shapes(shapes) => shapes.iter().try_for_each(Shape::validate),
+            };
+            result.map_err(|reason| RoboticsError::InvalidArgument(format!("link {}: {}", i, reason)))?;
+        }
+        for (i, obstacle) in self.obstacles.iter().enumerate() {
+            obstacle
+                .shape
+                .validate()
+                .map_err(|reason| RoboticsError::InvalidArgument(format!("obstacle {}: {}", i, reason)))?;
+        }
+        Ok(())
+    }
+
+    /// Collisions and separation of the robot at the given joint values
+    ///
+    /// Joint values are used as given, without the limit policy.
+    pub fn check(&self, robot: &RobotArm, joint_values: &[f64]) -> Result<CollisionReport, RoboticsError> {
+        let num_joints = robot.num_joints();
+        check_dimension(num_joints, joint_values.len())?;
+        check_finite(joint_values)?;
+        check_dimension(num_joints, self.links.len())?;
+
+        let mut frames = Vec::with_capacity(num_joints + 1);
+        visit_link_frames(robot, joint_values, |_, frame| frames.push(*frame));
+        let links: Vec<Vec<Solid>> = (0..num_joints).map(|i| self.link_solids(robot, &frames, i)).collect();
+        let obstacles: Vec<Solid> = self
+            .obstacles
+            .iter()
+            .map(|obstacle| obstacle.shape.place(&Transform3D::identity()))
+            .collect();
+
+        let mut checked = Vec::new();
+        for i in 0..num_joints {
+            for j in i + 1..num_joints {
+                if links[i + 1..j].iter().all(Vec::is_empty) {
+                    continue;
+                }
+                if let Some(distance) = min_separation(&links[i], &links[j]) {
+                    checked.push(BodyPair {
+                        first: Body::Link(i),
+                        second: Body::Link(j),
+                        distance,
+                    });
+                }
+            }
+            for (k, obstacle) in obstacles.iter().enumerate() {
+                if let Some(distance) = min_separation(&links[i], std::slice::from_ref(obstacle)) {
+                    checked.push(BodyPair {
+                        first: Body::Link(i),
+                        second: Body::Obstacle(k),
+                        distance,
+                    });
+                }
+            }
+        }
+
+        let closest = checked.iter().copied().min_by(|a, b| a.distance.total_cmp(&b.distance));
+        let mut pairs: Vec<BodyPair> = checked.into_iter().filter(|pair| pair.distance <= 0.0).collect();
+        pairs.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+
+        let mut colliding_links: Vec<usize> = pairs
+            .iter()
+            .flat_map(|pair| [pair.first, pair.second])
+            .filter_map(|body| match body {
+                Body::Link(i) => Some(i),
+                Body::Obstacle(_) => None,
+            })
+            .collect();
+        colliding_links.sort_unstable();
+        colliding_links.dedup();
+
+        Ok(CollisionReport {
+            in_collision: !pairs.is_empty(),
+            pairs,
+            colliding_links,
+            min_distance: closest.map_or(f64::INFINITY, |pair| pair.distance),
+            closest,
+        })
+    }
+
+    /// World-frame shapes of link `i` given the frames from `visit_link_frames`
+    fn link_solids(&self, robot: &RobotArm, frames: &[Transform3D], i: usize) -> Vec<Solid> {
+        let frame = &frames[i + 1];
+
+        match &self.links[i] {
+            LinkGeometry::Shapes(shapes) => shapes.iter().map(|shape| shape.place(frame)).collect(),
+            LinkGeometry::Capsules { radius } => {
+                let previous = &frames[i];
+                let last = i + 1 == robot.num_joints();
+                // The last frame carries the tool; the link itself ends at its joint frame
+                let joint_end = if last { frame.compose(&robot.tool.inverse()) } else { *frame };
+                let start = previous.translation_vector();
+                let end = joint_end.translation_vector();
+
+                // Corner between the two DH offsets, found from the frames alone so
+                // prismatic joints need no special case
+                let convention = match (&robot.screw_model, &robot.dh_params) {
+                    (None, Some(dh_params)) => Some(dh_params[i].convention),
+                    _ => None,
+                };
+                let corner = match convention {
+                    Some(DHConvention::Standard) => {
+                        let z: Vector3<f64> = previous.rotation_matrix().column(2).into_owned();
+                        start + z * (end - start).dot(&z)
+                    }
+                    Some(DHConvention::Modified) => {
+                        let x: Vector3<f64> = previous.rotation_matrix().column(0).into_owned();
+                        start + x * (end - start).dot(&x)
+                    }
+                    None => start,
+                };
+
+                let mut points = vec![start, corner, end];
+                if last {
+                    points.push(frame.translation_vector());
+                }
+                points
+                    .windows(2)
+                    .filter(|pair| (pair[1] - pair[0]).norm() > DEGENERATE_LENGTH)
+                    .map(|pair| Solid {
+                        core: Core::Segment(pair[0], pair[1]),
+                        radius: *radius,
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Shape in the world frame: a convex core grown by `radius`
+#[derive(Debug, Clone, Copy)]
+struct Solid {
+    core: Core,
+    radius: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Core {
+    Point(Vector3<f64>),
+    Segment(Vector3<f64>, Vector3<f64>),
+    /// Box whose edges run along the columns of `axes`
+    Box {
+        center: Vector3<f64>,
+        axes: Matrix3<f64>,
+        half_extents: Vector3<f64>,
+    },
+}
+
+impl Core {
+    fn corners(&self) -> Vec<Vector3<f64>> {
+        match self {
+            Core::Point(p) => vec![*p],
+            Core::Segment(a, b) => vec![*a, *b],
+            Core::Box {
+                center,
+                axes,
+                half_extents,
+            } => (0..8)
+                .map(|bits: usize| {
+                    let sign = |k: usize| if bits & (1 << k) == 0 { -1.0 } else { 1.0 };
+                    (0..3).fold(*center, |corner, k| corner + axes.column(k) * (sign(k) * half_extents[k]))
+                })
+                .collect(),
+        }
+    }
+
+    /// Edges as segments (a point is a single zero-length edge)
+    fn edges(&self) -> Vec<(Vector3<f64>, Vector3<f64>)> {
+        match self {
+            Core::Point(p) => vec![(*p, *p)],
+            Core::Segment(a, b) => vec![(*a, *b)],
+            Core::Box { .. } => {
+                let corners = self.corners();
+                // Corners whose indices differ in one bit share an edge
+                (0..8)
+                    .flat_map(|i: usize| (0..3).map(move |k| (i, i | (1 << k))))
+                    .filter(|&(i, j)| i != j)
+                    .map(|(i, j)| (corners[i], corners[j]))
+                    .collect()
+            }
+        }
+    }
+
+    /// Directions the core extends along
+    fn directions(&self) -> Vec<Vector3<f64>> {
+        match self {
+            Core::Point(_) => Vec::new(),
+            Core::Segment(a, b) => vec![b - a],
+            Core::Box { axes, .. } => (0..3).map(|k| axes.column(k).into_owned()).collect(),
+        }
+    }
+
+    /// Range of the core projected onto `axis`
+    fn interval(&self, axis: &Vector3<f64>) -> (f64, f64) {
+        match self {
+            Core::Point(p) => (p.dot(axis), p.dot(axis)),
+            Core::Segment(a, b) => {
+                let (a, b) = (a.dot(axis), b.dot(axis));
+                (a.min(b), a.max(b))
+            }
+            Core::Box {
+                center,
+                axes,
+                half_extents,
+            } => {
+                let middle = center.dot(axis);
+                let reach: f64 = (0..3).map(|k| half_extents[k] * axes.column(k).dot(axis).abs()).sum();
+                (middle - reach, middle + reach)
+            }
+        }
+    }
+}
+
+/// Smallest separation between two sets of shapes (None if either is empty)
+fn min_separation(first: &[Solid], second: &[Solid]) -> Option<f64> {
+    first
+        .iter()
+        .flat_map(|a| second.iter().map(move |b| core_distance(&a.core, &b.core) - a.radius - b.radius))
+        .min_by(f64::total_cmp)
+}
+
+/// Distance between two cores, or minus their penetration depth when they overlap
+fn core_distance(a: &Core, b: &Core) -> f64 {
+    let has_box = |core: &Core| matches!(core, Core::Box { .. });
+    if !has_box(a) && !has_box(b) {
+        // Points and segments have no volume to penetrate
+        let ((a0, a1), (b0, b1)) = (a.edges()[0], b.edges()[0]);
+        return segment_distance(&a0, &a1, &b0, &b1);
+    }
+
+    if let Some(depth) = penetration_depth(a, b) {
+        return -depth;
+    }
+
+    // Disjoint polytopes: the closest points are a corner against the other
+    // core, or a point on an edge of each
+    let corners = a
+        .corners()
+        .iter()
+        .map(|corner| point_distance(corner, b))
+        .chain(b.corners().iter().map(|corner| point_distance(corner, a)))
+        .fold(f64::INFINITY, f64::min);
+    let edges = a
+        .edges()
+        .iter()
+        .flat_map(|(a0, a1)| b.edges().into_iter().map(move |(b0, b1)| segment_distance(a0, a1, &b0, &b1)))
+        .fold(f64::INFINITY, f64::min);
+
+    corners.min(edges)
+}
+
+/// Separating axis test: the smallest overlap over the candidate axes (face
+/// normals and edge cross products), or None when some axis separates the cores
+fn penetration_depth(a: &Core, b: &Core) -> Option<f64> {
+    let face_normals = |core: &Core| match core {
+        Core::Box { .. } => core.directions(),
+        _ => Vec::new(),
+    };
+    let mut axes = face_normals(a);
+    axes.extend(face_normals(b));
+    for u in a.directions() {
+        for v in b.directions() {
+            let cross = u.cross(&v);
+            if cross.norm() > DEGENERATE_LENGTH {
+                axes.push(cross);
+            }
+        }
+    }
+
+    let mut depth = f64::INFINITY;
+    for axis in axes {
+        let axis = axis.normalize();
+        let (a_min, a_max) = a.interval(&axis);
+        let (b_min, b_max) = b.interval(&axis);
+        // Shortest push along the axis that separates the intervals
+        let overlap = (a_max - b_min).min(b_max - a_min);
+        if overlap < 0.0 {
+            return None;
+        }
+        depth = depth.min(overlap);
+    }
+    Some(depth)
+}
+
+/// Distance from a point to a core (zero inside a box)
+fn point_distance(point: &Vector3<f64>, core: &Core) -> f64 {
+    match core {
+        Core::Point(p) => (point - p).norm(),
+        Core::Segment(a, b) => segment_distance(point, point, a, b),
+        Core::Box {
+            center,
+            axes,
+            half_extents,
+        } => {
+            let local = axes.transpose() * (point - center);
+            let outside = Vector3::from_fn(|k, _| (local[k].abs() - half_extents[k]).max(0.0));
+            outside.norm()
+        }
+    }
+}
+
+/// Distance between segments [p0, p1] and [q0, q1] (either may be a point)
+fn segment_distance(p0: &Vector3<f64>, p1: &Vector3<f64>, q0: &Vector3<f64>, q1: &Vector3<f64>) -> f64 {
+    let d1 = p1 - p0;
+    let d2 = q1 - q0;
+    let r = p0 - q0;
+    let a = d1.norm_squared();
+    let e = d2.norm_squared();
+    let f = d2.dot(&r);
+    let epsilon = DEGENERATE_LENGTH * DEGENERATE_LENGTH;
+
+    // Closest points p0 + s d1 and q0 + t d2 (Ericson, Real-Time Collision Detection 5.1.9)
+    let (s, t) = if a <= epsilon && e <= epsilon {
+        (0.0, 0.0)
+    } else if a <= epsilon {
+        (0.0, (f / e).clamp(0.0, 1.0))
+    } else {
+        let c = d1.dot(&r);
+        if e <= epsilon {
+            ((-c / a).clamp(0.0, 1.0), 0.0)
+        } else {
+            let b = d1.dot(&d2);
+            let denominator = a * e - b * b;
+            let mut s = if denominator > epsilon * a * e {
+                ((b * f - c * e) / denominator).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let mut t = (b * s + f) / e;
+            if t < 0.0 {
+                t = 0.0;
+                s = (-c / a).clamp(0.0, 1.0);
+            } else if t > 1.0 {
+                t = 1.0;
+                s = ((b - c) / a).clamp(0.0, 1.0);
+            }
+            (s, t)
+        }
+    };
+
+    ((p0 + d1 * s) - (q0 + d2 * t)).norm()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dh_parameters::DHParameter;
+    use std::f64::consts::FRAC_PI_2;
+
+    const EPSILON: f64 = 1e-9;
+
+    fn solid(shape: Shape) -> Solid {
+        shape.place(&Transform3D::identity())
+    }
+
+    fn separation(a: Shape, b: Shape) -> f64 {
+        min_separation(&[solid(a)], &[solid(b)]).unwrap()
+    }
+
+    fn cube(center: [f64; 3], half: f64) -> Shape {
+        Shape::Box {
+            center,
+            rpy: [0.0; 3],
+            half_extents: [half; 3],
+        }
+    }
+
+    #[test]
+    fn test_sphere_and_capsule_distances() {
+        let sphere = |center, radius| Shape::Sphere { center, radius };
+        let capsule = |start, end| Shape::Capsule { start, end, radius: 0.1 };
+
+        assert!((separation(sphere([0.0; 3], 1.0), sphere([3.0, 4.0, 0.0], 1.0)) - 3.0).abs() < EPSILON);
+        // Overlapping spheres report their penetration depth
+        assert!((separation(sphere([0.0; 3], 1.0), sphere([1.5, 0.0, 0.0], 1.0)) + 0.5).abs() < EPSILON);
+
+        // Crossing skew segments one unit apart, parallel segments side by side
+        let crossing = separation(capsule([-1.0, 0.0, 0.0], [1.0, 0.0, 0.0]), capsule([0.0, -1.0, 1.0], [0.0, 1.0, 1.0]));
+        assert!((crossing - 0.8).abs() < EPSILON);
+        let parallel = separation(capsule([0.0; 3], [2.0, 0.0, 0.0]), capsule([1.0, 0.5, 0.0], [3.0, 0.5, 0.0]));
+        assert!((parallel - 0.3).abs() < EPSILON);
+        // Past the end of a segment the closest point is its endpoint
+        let beyond = separation(capsule([0.0; 3], [1.0, 0.0, 0.0]), sphere([4.0, 4.0, 0.0], 0.0));
+        assert!((beyond - 4.9).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_box_distances() {
+        // Corner to point, face to face, and edge to edge of a rotated box
+        let corner = separation(cube([0.0; 3], 1.0), Shape::Sphere { center: [2.0, 2.0, 2.0], radius: 0.0 });
+        assert!((corner - 3f64.sqrt()).abs() < EPSILON);
+        assert!((separation(cube([0.0; 3], 1.0), cube([3.5, 0.2, -0.3], 1.0)) - 1.5).abs() < EPSILON);
+
+        let diamond = Shape::Box {
+            center: [0.0, 3.0, 0.0],
+            rpy: [0.0, 0.0, FRAC_PI_2 / 2.0],
+            half_extents: [1.0; 3],
+        };
+        assert!((separation(cube([0.0; 3], 1.0), diamond) - (2.0 - 2f64.sqrt())).abs() < EPSILON);
+
+        // A capsule passing through a box must be pushed out sideways by the thinner half extent
+        let slab = Shape::Box {
+            center: [0.0; 3],
+            rpy: [0.0; 3],
+            half_extents: [1.0, 0.3, 2.0],
+        };
+        let rod = Shape::Capsule {
+            start: [-3.0, 0.0, 0.0],
+            end: [3.0, 0.0, 0.0],
+            radius: 0.1,
+        };
+        assert!((separation(slab, rod) + 0.4).abs() < EPSILON);
+        assert!((separation(cube([0.0; 3], 1.0), cube([1.5, 0.0, 0.0], 1.0)) + 0.5).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_folded_arm_collides_with_itself() {
+        let mut robot = RobotArm::planar(vec![1.0, 1.0, 1.0]);
+        let model = CollisionModel::for_robot(&robot, 0.1);
+
+        // Stretched out, only the first and last links are far enough apart to be checked
+        let straight = model.check(&robot, &[0.0, 0.0, 0.0]).unwrap();
+        assert!(!straight.in_collision);
+        assert!((straight.min_distance - 0.8).abs() < EPSILON);
+
+        // The third link folds back over the first
+        robot.joint_angles = vec![0.0, 3.0, 3.0];
+        let folded = model.check(&robot, &robot.joint_angles).unwrap();
+        assert!(folded.in_collision);
+        assert_eq!(folded.colliding_links, vec![0, 2]);
+        assert_eq!(folded.pairs[0].first, Body::Link(0));
+        assert_eq!(folded.pairs[0].second, Body::Link(2));
+        assert_eq!(folded.closest, Some(folded.pairs[0]));
+        assert!(folded.min_distance < 0.0);
+    }
+
+    #[test]
+    fn test_obstacles_are_reported_per_link() {
+        let robot = RobotArm::planar(vec![1.0, 1.0]);
+        let mut model = CollisionModel::for_robot(&robot, 0.05);
+        model.obstacles.push(Obstacle {
+            name: "block".to_string(),
+            shape: cube([2.0, 0.0, 0.0], 0.25),
+        });
+
+        // Straight along X the tip reaches into the block
+        let report = model.check(&robot, &[0.0, 0.0]).unwrap();
+        assert_eq!(report.colliding_links, vec![1]);
+        assert_eq!(report.pairs.len(), 1);
+        assert_eq!(report.pairs[0].second, Body::Obstacle(0));
+        assert!((report.min_distance + 0.3).abs() < EPSILON, "{:?}", report);
+
+        // Raised by 90° the arm clears it
+        let report = model.check(&robot, &[FRAC_PI_2, 0.0]).unwrap();
+        assert!(!report.in_collision);
+        assert!((report.min_distance - 1.7).abs() < EPSILON, "{:?}", report);
+    }
+
+    #[test]
+    fn test_links_meeting_at_a_joint_are_not_checked() {
+        // The middle link has no length, so the outer links share a joint
+        let robot = RobotArm::from_dh_params(vec![
+            DHParameter::revolute(1.0, 0.0, 0.0, 0.0),
+            DHParameter::revolute(0.0, FRAC_PI_2, 0.0, 0.0),
+            DHParameter::revolute(1.0, 0.0, 0.0, 0.0),
+        ]);
+        let model = CollisionModel::for_robot(&robot, 0.1);
+
+        let report = model.check(&robot, &[0.0, 0.0, 0.0]).unwrap();
+        assert!(!report.in_collision);
+        assert!(report.closest.is_none());
+    }
+
+    #[test]
+    fn test_dh_capsules_follow_both_offsets() {
+        // d lifts the link 1 m before it reaches out 1 m along X
+        let robot = RobotArm::from_dh_params(vec![DHParameter::revolute(1.0, 0.0, 1.0, 0.0)]);
+        let mut model = CollisionModel::for_robot(&robot, 0.1);
+        model.obstacles.push(Obstacle {
+            name: String::new(),
+            shape: Shape::Sphere {
+                center: [1.0, 0.0, 0.0],
+                radius: 0.2,
+            },
+        });
+
+        // A straight capsule from base to tip would pass 0.71 m from the sphere
+        let report = model.check(&robot, &[0.0]).unwrap();
+        assert!((report.min_distance - 0.7).abs() < EPSILON, "{:?}", report);
+    }
+
+    #[test]
+    fn test_invalid_models_are_rejected() {
+        let robot = RobotArm::planar(vec![1.0, 1.0]);
+        let mut model = CollisionModel::for_robot(&robot, 0.1);
+        assert!(model.validate(&robot).is_ok());
+
+        model.links[1] = LinkGeometry::Capsules { radius: -0.1 };
+        assert_eq!(model.validate(&robot).unwrap_err().code(), "INVALID_ARGUMENT");
+
+        model.links.pop();
+        assert_eq!(model.validate(&robot).unwrap_err().code(), "DIMENSION_MISMATCH");
+        assert_eq!(model.check(&robot, &[0.0, 0.0]).unwrap_err().code(), "DIMENSION_MISMATCH");
+
+        let model = CollisionModel {
+            links: vec![LinkGeometry::Shapes(Vec::new()); 2],
+            obstacles: vec![Obstacle {
+                name: String::new(),
+                shape: cube([0.0, 0.0, f64::NAN], 1.0),
+            }],
+        };
+        assert_eq!(model.validate(&robot).unwrap_err().code(), "INVALID_ARGUMENT");
+    }
+}
@@ -0,0 +1,523 @@
+use crate::error::{check_dimension, check_finite, RoboticsError};
+use crate::robot::RobotArm;
+use crate::simulation::MAX_SUBSTEPS;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Speed below which a joint with Coulomb friction counts as stopped
+const STICTION_VELOCITY: f64 = 1e-9;
+
+/// Gains and limits of one joint's PID controller
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PidGains {
+    /// Proportional gain (torque per unit of error)
+    pub kp: f64,
+    /// Integral gain (torque per unit of accumulated error·s)
+    pub ki: f64,
+    /// Derivative gain (torque per unit of velocity)
+    pub kd: f64,
+    /// Time constant of the first-order derivative filter in seconds (0 = unfiltered)
+    pub derivative_filter: f64,
+    /// Bound on the magnitude of the integral term's torque
+    pub integral_limit: f64,
+    /// Bound on the magnitude of the output torque (the joint's effort limit also applies)
+    pub max_torque: f64,
+}
+
+impl Default for PidGains {
+    fn default() -> Self {
+        PidGains {
+            kp: 40.0,
+            ki: 5.0,
+            kd: 4.0,
+            derivative_filter: 0.005,
+            integral_limit: f64::INFINITY,
+            max_torque: f64::INFINITY,
+        }
+    }
+}
+
+impl PidGains {
+    /// Reason the gains are unusable, if any
+    pub fn validate(&self) -> Result<(), String> {
+        let values = [self.kp, self.ki, self.kd, self.derivative_filter];
+        if values.iter().any(|value| !value.is_finite() || *value < 0.0)
+            || self.integral_limit.is_nan()
+            || self.integral_limit < 0.0
+            || self.max_torque.is_nan()
+            || self.max_torque <= 0.0
+        {
+            return Err("gains must be non-negative and max_torque positive".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// PID controller with conditional-integration anti-windup, a filtered
+/// derivative on the measurement and output saturation
+///
+/// The derivative acts on the measurement rather than the error, so setpoint
+/// steps do not kick the output.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PidController {
+    pub gains: PidGains,
+    /// Integral term (torque), kept within `integral_limit`
+    pub integral: f64,
+    /// Filtered rate of change of the measurement
+    pub derivative: f64,
+    previous_measurement: Option<f64>,
+}
+
+impl PidController {
+    pub fn new(gains: PidGains) -> Self {
+        PidController {
+            gains,
+            integral: 0.0,
+            derivative: 0.0,
+            previous_measurement: None,
+        }
+    }
+
+    /// Forget the accumulated state (integral, derivative filter, last measurement)
+    pub fn reset(&mut self) {
+        self.integral = 0.0;
+        self.derivative = 0.0;
+        self.previous_measurement = None;
+    }
+
+    /// Output torque for one control period of `dt` seconds, clamped to ±`limit`
+    /// (and ±`max_torque`)
+    ///
+    /// The integral only accumulates while the output is unsaturated or the
+    /// error drives it back out of saturation, so it cannot wind up.
+    pub fn update(&mut self, setpoint: f64, measurement: f64, dt: f64, limit: f64) -> f64 {
+        let gains = self.gains;
+        let limit = limit.min(gains.max_torque);
+        let error = setpoint - measurement;
+
+        if let Some(previous) = self.previous_measurement {
+            let rate = (measurement - previous) / dt;
+            let blend = dt / (gains.derivative_filter + dt);
+            self.derivative += blend * (rate - self.derivative);
+        }
+        self.previous_measurement = Some(measurement);
+
+        let base = gains.kp * error - gains.kd * self.derivative;
+        // max/min rather than clamp: a negative or NaN bound must not panic
+        let integral = (self.integral + gains.ki * error * dt)
+            .max(-gains.integral_limit)
+            .min(gains.integral_limit);
+        let output = base + integral;
+        let winding_up = output.abs() > limit && output.signum() == error.signum();
+        if !winding_up {
+            self.integral = integral;
+        }
+
+        (base + self.integral).max(-limit).min(limit)
+    }
+}
+
+/// Actuator and transmission of one joint
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JointModel {
+    /// Inertia seen by the motor (kg·m², or kg for prismatic joints)
+    pub inertia: f64,
+    /// Viscous damping (torque per unit of velocity)
+    pub damping: f64,
+    /// Coulomb friction torque (also the breakaway torque at rest)
+    pub friction: f64,
+}
+
+impl Default for JointModel {
+    fn default() -> Self {
+        JointModel {
+            inertia: 0.1,
+            damping: 0.2,
+            friction: 0.0,
+        }
+    }
+}
+
+impl JointModel {
+    /// Reason the model is not physical, if any
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.inertia.is_finite() && self.inertia > 0.0) {
+            return Err(format!("inertia must be positive, got {}", self.inertia));
+        }
+        if !(self.damping.is_finite() && self.damping >= 0.0) {
+            return Err(format!("damping must be non-negative, got {}", self.damping));
+        }
+        if !(self.friction.is_finite() && self.friction >= 0.0) {
+            return Err(format!("friction must be non-negative, got {}", self.friction));
+        }
+        Ok(())
+    }
+
+    /// Velocity after `dt` seconds of `torque`: J v̇ = τ - b v - f sign(v)
+    ///
+    /// Semi-implicit in the damping (stable for any step). A stopped joint stays
+    /// put until the torque overcomes friction, and friction never reverses
+    /// the motion on its own.
+    pub fn advance_velocity(&self, velocity: f64, torque: f64, dt: f64) -> f64 {
+        let direction = if velocity.abs() > STICTION_VELOCITY {
+            velocity.signum()
+        } else if torque.abs() > self.friction {
+            torque.signum()
+        } else {
+            return 0.0;
+        };
+
+        let next = (self.inertia * velocity + dt * (torque - self.friction * direction)) / (self.inertia + dt * self.damping);
+        // Friction brought the joint to rest within the step: stick unless the torque breaks away
+        if next * direction < 0.0 && (torque.abs() <= self.friction || torque.signum() != next.signum()) {
+            0.0
+        } else {
+            next
+        }
+    }
+}
+
+/// Options for the servo simulation
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServoOptions {
+    /// Control and integration period (seconds); longer `step` calls are subdivided
+    pub control_period: f64,
+    /// Number of tracking samples kept (oldest dropped first)
+    pub history_length: usize,
+}
+
+impl Default for ServoOptions {
+    fn default() -> Self {
+        ServoOptions {
+            control_period: 1e-3,
+            history_length: 2000,
+        }
+    }
+}
+
+impl ServoOptions {
+    /// Check that `control_period` is positive
+    pub fn validate(&self) -> Result<(), RoboticsError> {
+        if !self.control_period.is_finite() || self.control_period <= 0.0 {
+            return Err(RoboticsError::InvalidArgument(format!(
+                "control_period must be positive, got {}",
+                self.control_period
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Joint state and controller activity at the end of one `step`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrackingSample {
+    pub time: f64,
+    pub setpoints: Vec<f64>,
+    pub positions: Vec<f64>,
+    pub velocities: Vec<f64>,
+    /// Setpoint minus position, per joint
+    pub errors: Vec<f64>,
+    /// Torques applied during the last control period (after saturation)
+    pub torques: Vec<f64>,
+}
+
+/// Independent joint servos: every joint is driven by its own PID controller
+/// through its `JointModel`, ignoring the coupling between links
+///
+/// Positions live in the robot (`joint_angles`); joints stop at their position
+/// limits and torques are clamped to the effort limits.
+#[derive(Debug, Clone)]
+pub struct ServoSimulation {
+    pub options: ServoOptions,
+    pub controllers: Vec<PidController>,
+    pub models: Vec<JointModel>,
+    /// Target position of every joint
+    pub setpoints: Vec<f64>,
+    pub velocities: Vec<f64>,
+    pub time: f64,
+    history: VecDeque<TrackingSample>,
+}
+
+impl ServoSimulation {
+    /// Servos with default gains and joint models, holding the robot's current pose
+    pub fn new(robot: &RobotArm) -> Self {
+        let num_joints = robot.num_joints();
+        ServoSimulation {
+            options: ServoOptions::default(),
+            controllers: vec![PidController::new(PidGains::default()); num_joints],
+            models: vec![JointModel::default(); num_joints],
+            setpoints: robot.joint_angles.clone(),
+            velocities: vec![0.0; num_joints],
+            time: 0.0,
+            history: VecDeque::new(),
+        }
+    }
+
+    /// Set the gains of every joint
+    pub fn set_gains(&mut self, gains: Vec<PidGains>) -> Result<(), RoboticsError> {
+        check_dimension(self.controllers.len(), gains.len())?;
+        for (index, gains) in gains.iter().enumerate() {
+            gains
+                .validate()
+                .map_err(|reason| RoboticsError::InvalidArgument(format!("joint {}: {}", index, reason)))?;
+        }
+
+        for (controller, gains) in self.controllers.iter_mut().zip(gains) {
+            controller.gains = gains;
+        }
+        Ok(())
+    }
+
+    /// Set the actuator model of every joint
+    pub fn set_models(&mut self, models: Vec<JointModel>) -> Result<(), RoboticsError> {
+        check_dimension(self.models.len(), models.len())?;
+        for (index, model) in models.iter().enumerate() {
+            model
+                .validate()
+                .map_err(|reason| RoboticsError::InvalidArgument(format!("joint {}: {}", index, reason)))?;
+        }
+
+        self.models = models;
+        Ok(())
+    }
+
+    /// Set the target position of every joint (must be finite)
+    pub fn set_setpoints(&mut self, setpoints: Vec<f64>) -> Result<(), RoboticsError> {
+        check_dimension(self.setpoints.len(), setpoints.len())?;
+        check_finite(&setpoints)?;
+        self.setpoints = setpoints;
+        Ok(())
+    }
+
+    /// Stop the joints, clear the controllers and the history and rewind the clock
+    pub fn reset(&mut self) {
+        self.controllers.iter_mut().for_each(PidController::reset);
+        self.velocities.iter_mut().for_each(|velocity| *velocity = 0.0);
+        self.history.clear();
+        self.time = 0.0;
+    }
+
+    /// Recorded samples, oldest first
+    pub fn history(&self) -> impl Iterator<Item = &TrackingSample> {
+        self.history.iter()
+    }
+
+    /// Run the servos for `dt` seconds, moving the robot, and record a sample
+    ///
+    /// Intervals needing more than `MAX_SUBSTEPS` control periods are rejected.
+    pub fn step(&mut self, robot: &mut RobotArm, dt: f64) -> Result<TrackingSample, RoboticsError> {
+        check_dimension(robot.num_joints(), self.setpoints.len())?;
+        if !dt.is_finite() || dt <= 0.0 {
+            return Err(RoboticsError::InvalidArgument(format!("time step must be positive, got {}", dt)));
+        }
+        self.options.validate()?;
+        let period = self.options.control_period;
+        let substeps = (dt / period).ceil().max(1.0);
+        if substeps > MAX_SUBSTEPS as f64 {
+            return Err(RoboticsError::InvalidArgument(format!(
+                "time step {} needs more than {} control periods of {}",
+                dt, MAX_SUBSTEPS, period
+            )));
+        }
+        let substeps = substeps as usize;
+        let h = dt / substeps as f64;
+        let mut torques = vec![0.0; robot.num_joints()];
+
+        for _ in 0..substeps {
+            for (i, torque) in torques.iter_mut().enumerate() {
+                let limits = robot.limits[i];
+                let position = robot.joint_angles[i];
+                *torque = self.controllers[i].update(self.setpoints[i], position, h, limits.effort);
+
+                let mut velocity = self.models[i].advance_velocity(self.velocities[i], *torque, h);
+                let mut next = position + h * velocity;
+                // Hard stops at the position limits
+                if next < limits.lower || next > limits.upper {
+                    next = limits.clamp(next);
+                    velocity = 0.0;
+                }
+                robot.joint_angles[i] = next;
+                self.velocities[i] = velocity;
+            }
+        }
+        self.time += dt;
+
+        let sample = TrackingSample {
+            time: self.time,
+            setpoints: self.setpoints.clone(),
+            positions: robot.joint_angles.clone(),
+            velocities: self.velocities.clone(),
+            errors: self.setpoints.iter().zip(&robot.joint_angles).map(|(target, q)| target - q).collect(),
+            torques,
+        };
+        self.history.push_back(sample.clone());
+        while self.history.len() > self.options.history_length {
+            self.history.pop_front();
+        }
+
+        Ok(sample)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::limits::JointLimits;
+
+    #[test]
+    fn test_step_response_settles_through_friction() {
+        let mut robot = RobotArm::new(vec![1.0, 1.0]);
+        let mut servos = ServoSimulation::new(&robot);
+        servos
+            .set_models(vec![JointModel { friction: 0.5, ..JointModel::default() }; 2])
+            .unwrap();
+        servos.set_setpoints(vec![1.0, -0.5]).unwrap();
+
+        let mut peak: f64 = 0.0;
+        for _ in 0..300 {
+            let sample = servos.step(&mut robot, 0.01).unwrap();
+            peak = peak.max(sample.positions[0]);
+        }
+
+        // A PD servo sticks anywhere within friction / kp of the target; the integral pushes it closer
+        let deadband = 0.5 / PidGains::default().kp;
+        assert!((robot.joint_angles[0] - 1.0).abs() < deadband / 2.0, "{:?}", robot.joint_angles);
+        assert!((robot.joint_angles[1] + 0.5).abs() < deadband / 2.0, "{:?}", robot.joint_angles);
+        assert!(peak < 1.05, "overshoot to {}", peak);
+        assert!((servos.time - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_saturation_and_anti_windup() {
+        let mut robot = RobotArm::new(vec![1.0]);
+        robot.limits = vec![JointLimits {
+            effort: 2.0,
+            ..JointLimits::unbounded()
+        }];
+        let mut servos = ServoSimulation::new(&robot);
+        servos.set_setpoints(vec![3.0]).unwrap();
+
+        let mut peak: f64 = 0.0;
+        for _ in 0..400 {
+            let sample = servos.step(&mut robot, 0.01).unwrap();
+            assert!(sample.torques[0].abs() <= 2.0 + 1e-12);
+            peak = peak.max(sample.positions[0]);
+        }
+
+        // Without anti-windup the integral would grow through the whole saturated rise
+        assert!(servos.controllers[0].integral.abs() < 2.0, "integral wound up to {}", servos.controllers[0].integral);
+        assert!(peak < 3.3, "overshoot to {}", peak);
+        assert!((robot.joint_angles[0] - 3.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_derivative_filter_smooths_measurement_noise() {
+        let gains = PidGains {
+            kp: 0.0,
+            ki: 0.0,
+            kd: 1.0,
+            ..PidGains::default()
+        };
+        let mut filtered = PidController::new(gains);
+        let mut raw = PidController::new(PidGains {
+            derivative_filter: 0.0,
+            ..gains
+        });
+
+        // One-sample measurement spike of 1 mrad at 1 kHz
+        let measurements = [0.0, 0.0, 1e-3, 0.0, 0.0];
+        let peak = |controller: &mut PidController| {
+            measurements
+                .iter()
+                .map(|&measurement| controller.update(0.0, measurement, 1e-3, f64::INFINITY).abs())
+                .fold(0.0, f64::max)
+        };
+
+        assert!((peak(&mut raw) - 1.0).abs() < 1e-9);
+        assert!(peak(&mut filtered) < 0.2);
+    }
+
+    #[test]
+    fn test_stiction_and_limits() {
+        let model = JointModel {
+            inertia: 0.1,
+            damping: 0.0,
+            friction: 1.0,
+        };
+        assert_eq!(model.advance_velocity(0.0, 0.9, 0.01), 0.0);
+        assert!(model.advance_velocity(0.0, 1.5, 0.01) > 0.0);
+        // Friction stops a slow joint without pushing it backwards
+        assert_eq!(model.advance_velocity(0.05, 0.0, 0.01), 0.0);
+
+        let mut robot = RobotArm::new(vec![1.0]);
+        robot.limits = vec![JointLimits::position(-0.5, 0.5)];
+        let mut servos = ServoSimulation::new(&robot);
+        servos.set_setpoints(vec![2.0]).unwrap();
+        for _ in 0..200 {
+            servos.step(&mut robot, 0.01).unwrap();
+        }
+        assert_eq!(robot.joint_angles, vec![0.5]);
+    }
+
+    #[test]
+    fn test_history_is_bounded_and_reset() {
+        let mut robot = RobotArm::new(vec![1.0, 1.0]);
+        let mut servos = ServoSimulation::new(&robot);
+        servos.options.history_length = 5;
+
+        for _ in 0..8 {
+            servos.step(&mut robot, 0.02).unwrap();
+        }
+        let times: Vec<f64> = servos.history().map(|sample| sample.time).collect();
+        assert_eq!(times.len(), 5);
+        assert!((times[0] - 0.08).abs() < 1e-12);
+
+        servos.reset();
+        assert_eq!(servos.history().count(), 0);
+        assert_eq!(servos.time, 0.0);
+    }
+
+    #[test]
+    fn test_invalid_settings_are_rejected() {
+        let mut robot = RobotArm::new(vec![1.0, 1.0]);
+        let mut servos = ServoSimulation::new(&robot);
+
+        assert_eq!(servos.set_setpoints(vec![0.0]).unwrap_err().code(), "DIMENSION_MISMATCH");
+        let negative = PidGains {
+            kp: -1.0,
+            ..PidGains::default()
+        };
+        assert_eq!(servos.set_gains(vec![negative, PidGains::default()]).unwrap_err().code(), "INVALID_ARGUMENT");
+        let massless = JointModel {
+            inertia: 0.0,
+            ..JointModel::default()
+        };
+        assert_eq!(servos.set_models(vec![JointModel::default(), massless]).unwrap_err().code(), "INVALID_ARGUMENT");
+        assert_eq!(servos.step(&mut robot, 0.0).unwrap_err().code(), "INVALID_ARGUMENT");
+        assert_eq!(servos.step(&mut robot, 1e9).unwrap_err().code(), "INVALID_ARGUMENT");
+        servos.options.control_period = 0.0;
+        assert_eq!(servos.step(&mut robot, 0.01).unwrap_err().code(), "INVALID_ARGUMENT");
+        assert_eq!(servos.time, 0.0);
+    }
+
+    #[test]
+    fn test_bad_limits_do_not_panic() {
+        let mut controller = PidController::new(PidGains {
+            integral_limit: -1.0,
+            max_torque: -1.0,
+            ..PidGains::default()
+        });
+        for limit in [-1.0, f64::NAN] {
+            assert!(!controller.update(1.0, 0.0, 1e-3, limit).is_infinite());
+        }
+
+        let mut robot = RobotArm::new(vec![1.0, 1.0]);
+        robot.limits[0].effort = -1.0;
+        let mut servos = ServoSimulation::new(&robot);
+        servos.set_setpoints(vec![1.0, 1.0]).unwrap();
+        assert!(servos.step(&mut robot, 0.01).is_ok());
+    }
+}
@@ -0,0 +1,524 @@
+use crate::dh_parameters::{validate_dh_params, DHParameter};
+use crate::error::RoboticsError;
+use crate::geometry3d::Transform3D;
+use crate::limits::{JointLimits, LimitPolicy};
+use crate::robot::RobotArm;
+use crate::screw_model::ScrewModel;
+use nalgebra::{Matrix3, Vector3, Vector6};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Value of the `format` field identifying a robot description document
+pub const DESCRIPTION_FORMAT: &str = "robotics-wasm/robot";
+
+/// Schema version written by `robot_to_json` (and the newest one `robot_from_json` reads)
+pub const DESCRIPTION_VERSION: u32 = 1;
+
+/// Versioned JSON document describing a robot: model, joints and current state
+///
+/// ```json
+/// {
+///   "format": "robotics-wasm/robot",
+///   "version": 1,
+///   "name": "ur5",
+///   "kinematics": { "type": "dh", "links": [{ "a": 0, "alpha": 1.5708, "d": 0.089, ... }] },
+///   "joints": [{ "name": "joint_1", "limits": { "lower": -6.28, "upper": 6.28 }, "home": 0, "position": 0 }],
+///   "limit_policy": "Clamp",
+///   "base": [16 numbers, row-major],
+///   "tool": [16 numbers, row-major]
+/// }
+/// ```
+///
+/// Missing limit fields (or `null`) are unbounded; `limit_policy`, `base` and
+/// `tool` are optional.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RobotDescription {
+    pub format: String,
+    pub version: u32,
+    pub name: String,
+    pub kinematics: KinematicsDescription,
+    pub joints: Vec<JointDescription>,
+    #[serde(default)]
+    pub limit_policy: LimitPolicy,
+    /// Base pose in the world frame (identity when omitted)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<[f64; 16]>,
+    /// Tool center point in the last link frame (identity when omitted)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<[f64; 16]>,
+}
+
+/// Kinematic model of a robot description, tagged by `type`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum KinematicsDescription {
+    /// Planar chain of revolute joints, one link length per joint
+    Simple { link_lengths: Vec<f64> },
+    /// Denavit-Hartenberg links (Standard or Modified, per link)
+    Dh { links: Vec<DHParameter> },
+    /// Product of exponentials: space-frame screw axes (ω, v) and the home pose of every link frame
+    Screw {
+        screw_axes: Vec<[f64; 6]>,
+        link_homes: Vec<[f64; 16]>,
+    },
+}
+
+/// One joint of a robot description
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JointDescription {
+    pub name: String,
+    #[serde(default)]
+    pub limits: LimitsDescription,
+    /// Home (ready) value
+    #[serde(default)]
+    pub home: f64,
+    /// Current value
+    #[serde(default)]
+    pub position: f64,
+}
+
+/// Joint limits with unbounded values left out (JSON has no infinity)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LimitsDescription {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lower: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upper: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub velocity: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acceleration: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effort: Option<f64>,
+}
+
+impl LimitsDescription {
+    fn from_limits(limits: &JointLimits) -> Self {
+        let finite = |value: f64| value.is_finite().then_some(value);
+
+        LimitsDescription {
+            lower: finite(limits.lower),
+            upper: finite(limits.upper),
+            velocity: finite(limits.velocity),
+            acceleration: finite(limits.acceleration),
+            effort: finite(limits.effort),
+        }
+    }
+
+    fn to_limits(self) -> JointLimits {
+        JointLimits {
+            lower: self.lower.unwrap_or(f64::NEG_INFINITY),
+            upper: self.upper.unwrap_or(f64::INFINITY),
+            velocity: self.velocity.unwrap_or(f64::INFINITY),
+            acceleration: self.acceleration.unwrap_or(f64::INFINITY),
+            effort: self.effort.unwrap_or(f64::INFINITY),
+        }
+    }
+}
+
+impl RobotDescription {
+    /// Describe a robot, including its current joint values
+    pub fn from_robot(robot: &RobotArm) -> Self {
+        let kinematics = match (&robot.screw_model, &robot.dh_params) {
+            (Some(model), _) => KinematicsDescription::Screw {
+                screw_axes: model
+                    .screw_axes
+                    .iter()
+                    .map(|axis| [axis[0], axis[1], axis[2], axis[3], axis[4], axis[5]])
+                    .collect(),
+                link_homes: model.link_homes.iter().map(Transform3D::to_row_major).collect(),
+            },
+            (None, Some(dh_params)) => KinematicsDescription::Dh {
+                links: dh_params.clone(),
+            },
+            (None, None) => KinematicsDescription::Simple {
+                link_lengths: robot.link_lengths.clone(),
+            },
+        };
+
+        let joints = (0..robot.num_joints())
+            .map(|i| JointDescription {
+                name: robot.joint_names[i].clone(),
+                limits: LimitsDescription::from_limits(&robot.limits[i]),
+                home: robot.home[i],
+                position: robot.joint_angles[i],
+            })
+            .collect();
+
+        let non_identity = |frame: &Transform3D| {
+            (frame.matrix() != Transform3D::identity().matrix()).then(|| frame.to_row_major())
+        };
+
+        RobotDescription {
+            format: DESCRIPTION_FORMAT.to_string(),
+            version: DESCRIPTION_VERSION,
+            name: robot.name.clone(),
+            kinematics,
+            joints,
+            limit_policy: robot.limit_policy,
+            base: non_identity(&robot.base),
+            tool: non_identity(&robot.tool),
+        }
+    }
+
+    /// Check the document and build the robot it describes
+    ///
+    /// Every problem found is reported in a single `Parse` error, each prefixed
+    /// with the path of the offending field (e.g. `joints[2].limits`).
+    pub fn to_robot(&self) -> Result<RobotArm, RoboticsError> {
+        let mut issues = Vec::new();
+        self.validate(&mut issues);
+        if !issues.is_empty() {
+            return Err(description_error(issues.join("; ")));
+        }
+
+        let mut robot = match &self.kinematics {
+            KinematicsDescription::Simple { link_lengths } => RobotArm::new(link_lengths.clone()),
+            KinematicsDescription::Dh { links } => RobotArm::from_dh_params(links.clone()),
+            KinematicsDescription::Screw { screw_axes, link_homes } => RobotArm::from_screw_model(ScrewModel::new(
+                screw_axes.iter().map(|axis| Vector6::from_row_slice(axis)).collect(),
+                link_homes.iter().map(Transform3D::from_row_major).collect(),
+            )),
+        };
+
+        robot.name = self.name.clone();
+        robot.joint_names = self.joints.iter().map(|joint| joint.name.clone()).collect();
+        robot.limits = self.joints.iter().map(|joint| joint.limits.to_limits()).collect();
+        robot.home = self.joints.iter().map(|joint| joint.home).collect();
+        robot.base = self.base.as_ref().map_or_else(Transform3D::identity, Transform3D::from_row_major);
+        robot.tool = self.tool.as_ref().map_or_else(Transform3D::identity, Transform3D::from_row_major);
+        // The stored state passes through the policy like any other joint update
+        robot.limit_policy = self.limit_policy;
+        robot.set_joint_angles(self.joints.iter().map(|joint| joint.position).collect())?;
+
+        Ok(robot)
+    }
+
+    fn validate(&self, issues: &mut Vec<String>) {
+        if self.format != DESCRIPTION_FORMAT {
+            issues.push(format!("format: expected \"{}\", got \"{}\"", DESCRIPTION_FORMAT, self.format));
+        }
+        if self.version == 0 || self.version > DESCRIPTION_VERSION {
+            issues.push(format!(
+                "version: {} is not supported (this build reads versions 1 to {})",
+                self.version, DESCRIPTION_VERSION
+            ));
+        }
+
+        let num_joints = self.validate_kinematics(issues);
+        if self.joints.len() != num_joints {
+            issues.push(format!(
+                "joints: the kinematics describe {} joints, got {} entries",
+                num_joints,
+                self.joints.len()
+            ));
+        }
+
+        let mut names = HashSet::new();
+        for (i, joint) in self.joints.iter().enumerate() {
+            let path = format!("joints[{}]", i);
+            if joint.name.is_empty() {
+                issues.push(format!("{}.name: must not be empty", path));
+            } else if !names.insert(joint.name.as_str()) {
+                issues.push(format!("{}.name: \"{}\" is used by another joint", path, joint.name));
+            }
+
+            let limits = joint.limits.to_limits();
+            let values = [
+                ("limits.lower", joint.limits.lower),
+                ("limits.upper", joint.limits.upper),
+                ("limits.velocity", joint.limits.velocity),
+                ("limits.acceleration", joint.limits.acceleration),
+                ("limits.effort", joint.limits.effort),
+                ("home", Some(joint.home)),
+                ("position", Some(joint.position)),
+            ];
+            for (field, value) in values {
+                if let Some(value) = value.filter(|value| !value.is_finite()) {
+                    issues.push(format!("{}.{}: {} is not finite", path, field, value));
+                }
+            }
+            if limits.lower > limits.upper {
+                issues.push(format!(
+                    "{}.limits: lower {} is above upper {}",
+                    path, limits.lower, limits.upper
+                ));
+            }
+            for (field, value) in [
+                ("velocity", limits.velocity),
+                ("acceleration", limits.acceleration),
+                ("effort", limits.effort),
+            ] {
+                if value <= 0.0 {
+                    issues.push(format!("{}.limits.{}: must be positive, got {}", path, field, value));
+                }
+            }
+            if joint.home.is_finite() && limits.lower <= limits.upper && !limits.contains(joint.home) {
+                issues.push(format!(
+                    "{}.home: {} is outside the limits [{}, {}]",
+                    path, joint.home, limits.lower, limits.upper
+                ));
+            }
+        }
+
+        for (field, frame) in [("base", &self.base), ("tool", &self.tool)] {
+            if let Some(values) = frame {
+                validate_transform(field, values, issues);
+            }
+        }
+    }
+
+    /// Check the kinematic model, returning its number of joints
+    fn validate_kinematics(&self, issues: &mut Vec<String>) -> usize {
+        match &self.kinematics {
+            KinematicsDescription::Simple { link_lengths } => {
+                for (i, length) in link_lengths.iter().enumerate() {
+                    if !length.is_finite() {
+                        issues.push(format!("kinematics.link_lengths[{}]: {} is not finite", i, length));
+                    }
+                }
+                link_lengths.len()
+            }
+            KinematicsDescription::Dh { links } => {
+                if let Err(RoboticsError::MalformedDh { index, reason }) = validate_dh_params(links) {
+                    issues.push(format!("kinematics.links[{}]: {}", index, reason));
+                }
+                links.len()
+            }
+            KinematicsDescription::Screw { screw_axes, link_homes } => {
+                for (i, axis) in screw_axes.iter().enumerate() {
+                    let path = format!("kinematics.screw_axes[{}]", i);
+                    if axis.iter().any(|value| !value.is_finite()) {
+                        issues.push(format!("{}: values must be finite", path));
+                        continue;
+                    }
+
+                    let omega = Vector3::new(axis[0], axis[1], axis[2]).norm();
+                    let v = Vector3::new(axis[3], axis[4], axis[5]).norm();
+                    let unit = |norm: f64| (norm - 1.0).abs() < 1e-6;
+                    if !(unit(omega) || (omega < 1e-12 && unit(v))) {
+                        issues.push(format!(
+                            "{}: expected a unit rotation axis (revolute) or ω = 0 with a unit direction (prismatic)",
+                            path
+                        ));
+                    }
+                }
+                if link_homes.len() != screw_axes.len() {
+                    issues.push(format!(
+                        "kinematics.link_homes: expected one pose per screw axis ({}), got {}",
+                        screw_axes.len(),
+                        link_homes.len()
+                    ));
+                }
+                for (i, values) in link_homes.iter().enumerate() {
+                    validate_transform(&format!("kinematics.link_homes[{}]", i), values, issues);
+                }
+                screw_axes.len()
+            }
+        }
+    }
+}
+
+/// Serialize a robot to a pretty-printed JSON robot description
+pub fn robot_to_json(robot: &RobotArm) -> Result<String, RoboticsError> {
+    serde_json::to_string_pretty(&RobotDescription::from_robot(robot))
+        .map_err(|e| RoboticsError::Serialization(format!("robot description: {}", e)))
+}
+
+/// Load a robot from a JSON robot description
+///
+/// Malformed JSON, unknown or missing fields, unsupported versions and
+/// inconsistent values are reported as `Parse` errors naming the offending field.
+pub fn robot_from_json(json: &str) -> Result<RobotArm, RoboticsError> {
+    // Check the header first so a document from a newer version gets a clear message
+    let document: serde_json::Value = serde_json::from_str(json).map_err(|e| description_error(e.to_string()))?;
+    let version = document.get("version").and_then(serde_json::Value::as_u64);
+    if let Some(version) = version.filter(|&version| version > DESCRIPTION_VERSION as u64) {
+        return Err(description_error(format!(
+            "version: {} is newer than the supported version {}",
+            version, DESCRIPTION_VERSION
+        )));
+    }
+
+    let description: RobotDescription = serde_json::from_str(json).map_err(|e| description_error(e.to_string()))?;
+    description.to_robot()
+}
+
+fn description_error(message: String) -> RoboticsError {
+    RoboticsError::Parse(format!("robot description: {}", message))
+}
+
+/// Check that 16 row-major values form a finite rigid transform
+fn validate_transform(path: &str, values: &[f64; 16], issues: &mut Vec<String>) {
+    if values.iter().any(|value| !value.is_finite()) {
+        issues.push(format!("{}: values must be finite", path));
+        return;
+    }
+
+    let transform = Transform3D::from_row_major(values);
+    let rotation: Matrix3<f64> = transform.rotation_matrix();
+    let orthonormal = (rotation.transpose() * rotation - Matrix3::identity()).abs().max() < 1e-6;
+    if !orthonormal || rotation.determinant() < 0.0 {
+        issues.push(format!("{}: the upper-left 3x3 block is not a rotation", path));
+    }
+    if values[12..] != [0.0, 0.0, 0.0, 1.0] {
+        issues.push(format!("{}: the last row must be [0, 0, 0, 1]", path));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kinematics::end_effector_transform;
+    use crate::presets::Preset;
+    use crate::urdf::UrdfRobot;
+
+    fn round_trip(robot: &RobotArm) -> RobotArm {
+        robot_from_json(&robot_to_json(robot).unwrap()).unwrap()
+    }
+
+    fn assert_same_robot(a: &RobotArm, b: &RobotArm) {
+        assert_eq!(a.name, b.name);
+        assert_eq!(a.joint_names, b.joint_names);
+        assert_eq!(a.joint_angles, b.joint_angles);
+        assert_eq!(a.home, b.home);
+        assert_eq!(a.limits, b.limits);
+        assert_eq!(a.limit_policy, b.limit_policy);
+        assert_eq!(a.base.matrix(), b.base.matrix());
+        assert_eq!(a.tool.matrix(), b.tool.matrix());
+        assert_eq!(a.link_inertias(), b.link_inertias());
+        assert_eq!(end_effector_transform(a).matrix(), end_effector_transform(b).matrix());
+    }
+
+    fn parse_error(json: &str) -> String {
+        let error = robot_from_json(json).unwrap_err();
+        assert_eq!(error.code(), "PARSE_ERROR");
+        error.to_string()
+    }
+
+    const TWO_LINK: &str = r#"{
+        "format": "robotics-wasm/robot",
+        "version": 1,
+        "name": "two_link",
+        "kinematics": { "type": "simple", "link_lengths": [2.0, 1.5] },
+        "joints": [
+            { "name": "shoulder", "limits": { "lower": -1.0, "upper": 1.0 }, "position": 0.5 },
+            { "name": "elbow" }
+        ]
+    }"#;
+
+    #[test]
+    fn test_round_trip_of_every_model_type() {
+        let mut simple = RobotArm::new(vec![2.0, 1.5]);
+        simple.set_joint_angles(vec![0.3, -0.4]).unwrap();
+        assert_same_robot(&simple, &round_trip(&simple));
+
+        let mut preset = Preset::Panda.build();
+        preset.limit_policy = LimitPolicy::Reject;
+        preset
+            .set_base_frame(Transform3D::from_xyz_rpy([0.2, 0.0, 0.8], [0.0, 0.0, 1.0]))
+            .unwrap();
+        assert_same_robot(&preset, &round_trip(&preset));
+
+        let urdf = include_str!("../tests/fixtures/spatial_4dof.urdf");
+        let mut screw = UrdfRobot::parse(urdf).unwrap().to_robot_arm().unwrap();
+        screw.set_joint_angles(vec![0.2, -0.3, 0.1, 2.0]).unwrap();
+        let loaded = round_trip(&screw);
+        assert_same_robot(&screw, &loaded);
+        assert!(loaded.uses_screw_model());
+    }
+
+    #[test]
+    fn test_link_inertias_are_kept_and_checked() {
+        let puma = Preset::Puma560.build();
+        let json = robot_to_json(&puma).unwrap();
+        assert!(json.contains("\"center_of_mass\""));
+        assert_same_robot(&puma, &round_trip(&puma));
+
+        let negative = json.replacen("\"mass\": 17.4", "\"mass\": -17.4", 1);
+        let message = parse_error(&negative);
+        assert!(message.contains("kinematics.links[1]: mass -17.4 is negative"), "{}", message);
+    }
+
+    #[test]
+    fn test_unbounded_limits_are_omitted() {
+        let json = robot_to_json(&RobotArm::new(vec![1.0])).unwrap();
+
+        assert!(json.contains("\"format\": \"robotics-wasm/robot\""));
+        assert!(!json.contains("lower"));
+        assert!(!json.contains("base"));
+        assert!(round_trip(&RobotArm::new(vec![1.0])).limits[0] == JointLimits::unbounded());
+    }
+
+    #[test]
+    fn test_load_hand_written_description() {
+        let robot = robot_from_json(TWO_LINK).unwrap();
+
+        assert_eq!(robot.name, "two_link");
+        assert_eq!(robot.joint_names, vec!["shoulder", "elbow"]);
+        assert_eq!(robot.joint_angles, vec![0.5, 0.0]);
+        assert_eq!(robot.limits[0], JointLimits::position(-1.0, 1.0));
+        assert!(!robot.limits[1].is_bounded());
+        assert_eq!(robot.limit_policy, LimitPolicy::Clamp);
+    }
+
+    #[test]
+    fn test_structural_errors_name_the_field() {
+        let message = parse_error(&TWO_LINK.replace("\"link_lengths\"", "\"lengths\""));
+        assert!(message.contains("link_lengths"), "{}", message);
+
+        let message = parse_error(&TWO_LINK.replace("\"name\": \"elbow\"", "\"name\": \"elbow\", \"speed\": 1"));
+        assert!(message.contains("unknown field `speed`"), "{}", message);
+
+        let message = parse_error(&TWO_LINK.replace("\"simple\"", "\"spline\""));
+        assert!(message.contains("spline"), "{}", message);
+
+        assert!(parse_error("{ \"format\": ").contains("robot description"));
+    }
+
+    #[test]
+    fn test_version_and_format_are_checked() {
+        let message = parse_error(&TWO_LINK.replace("\"version\": 1", "\"version\": 7"));
+        assert!(message.contains("version: 7 is newer"), "{}", message);
+
+        let message = parse_error(&TWO_LINK.replace("robotics-wasm/robot", "urdf"));
+        assert!(message.contains("format: expected"), "{}", message);
+    }
+
+    #[test]
+    fn test_semantic_errors_are_all_reported() {
+        let json = TWO_LINK
+            .replace("\"lower\": -1.0, \"upper\": 1.0", "\"lower\": 1.0, \"upper\": -1.0")
+            .replace("\"name\": \"elbow\"", "\"name\": \"shoulder\", \"home\": 0.2, \"limits\": { \"velocity\": -2 }");
+        let message = parse_error(&json);
+
+        assert!(message.contains("joints[0].limits: lower 1 is above upper -1"), "{}", message);
+        assert!(message.contains("joints[1].name: \"shoulder\" is used by another joint"), "{}", message);
+        assert!(message.contains("joints[1].limits.velocity: must be positive, got -2"), "{}", message);
+
+        let message = parse_error(&TWO_LINK.replace("[2.0, 1.5]", "[2.0, 1.5, 1.0]"));
+        assert!(message.contains("joints: the kinematics describe 3 joints, got 2 entries"), "{}", message);
+
+        let message = parse_error(&TWO_LINK.replace("\"position\": 0.5", "\"home\": 3.0"));
+        assert!(message.contains("joints[0].home: 3 is outside the limits [-1, 1]"), "{}", message);
+    }
+
+    #[test]
+    fn test_invalid_frames_and_screw_axes_are_rejected() {
+        let scaled = "[2, 0, 0, 0, 0, 1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 1]";
+        let message = parse_error(&TWO_LINK.replace("\"joints\"", &format!("\"tool\": {}, \"joints\"", scaled)));
+        assert!(message.contains("tool: the upper-left 3x3 block is not a rotation"), "{}", message);
+
+        let json = r#"{
+            "format": "robotics-wasm/robot", "version": 1, "name": "screw",
+            "kinematics": { "type": "screw", "screw_axes": [[0, 0, 2, 0, 0, 0]],
+                            "link_homes": [[1, 0, 0, 1, 0, 1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 1]] },
+            "joints": [{ "name": "j" }]
+        }"#;
+        let message = parse_error(json);
+        assert!(message.contains("kinematics.screw_axes[0]: expected a unit rotation axis"), "{}", message);
+    }
+}
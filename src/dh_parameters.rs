@@ -1,3 +1,5 @@
+use crate::dynamics::LinkInertia;
+use crate::error::RoboticsError;
 use crate::geometry3d::Transform3D;
 use serde::{Deserialize, Serialize};
 
@@ -10,10 +12,23 @@ pub enum JointType {
     Prismatic,
 }
 
+/// Denavit-Hartenberg convention used to interpret a link's parameters
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum DHConvention {
+    /// Standard (Classic) DH: T(i-1,i) = Rot(Z, θ) * Trans(Z, d) * Trans(X, a) * Rot(X, α)
+    #[default]
+    Standard,
+    /// Modified (Craig) DH: T(i-1,i) = Rot(X, α) * Trans(X, a) * Rot(Z, θ) * Trans(Z, d)
+    Modified,
+}
+
 /// Denavit-Hartenberg parameters for a single link
 ///
-/// Using Standard (Classic) DH Convention:
+/// Using Standard (Classic) DH Convention by default:
 /// T(i-1,i) = Rot(Z, θ) * Trans(Z, d) * Trans(X, a) * Rot(X, α)
+///
+/// With `DHConvention::Modified`, a and α describe the previous link (Craig):
+/// T(i-1,i) = Rot(X, α) * Trans(X, a) * Rot(Z, θ) * Trans(Z, d)
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct DHParameter {
     /// a: Link length (distance along X axis from Z_i-1 to Z_i)
@@ -28,6 +43,27 @@ pub struct DHParameter {
     pub joint_type: JointType,
     /// Offset added to the joint variable (for homing or calibration)
     pub joint_offset: f64,
+    /// Convention the parameters are expressed in (Standard if omitted)
+    #[serde(default)]
+    pub convention: DHConvention,
+    /// Mass properties of the link, in frame i (massless if omitted)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inertia: Option<LinkInertia>,
+}
+
+/// A DH chain converted to another convention
+///
+/// The two conventions attach the a/α pair to different links, so converting
+/// leaves one fixed transform that no link can express: the end-effector pose is
+/// `base * FK(params) * tool`.
+#[derive(Debug, Clone)]
+pub struct ConvertedDH {
+    /// Fixed transform from the world to the first converted link
+    pub base: Transform3D,
+    /// Link parameters in the target convention
+    pub params: Vec<DHParameter>,
+    /// Fixed transform from the last converted link to the original end-effector frame
+    pub tool: Transform3D,
 }
 
 impl DHParameter {
@@ -46,6 +82,8 @@ impl DHParameter {
             theta: theta_offset,
             joint_type: JointType::Revolute,
             joint_offset: theta_offset,
+            convention: DHConvention::Standard,
+            inertia: None,
         }
     }
 
@@ -64,6 +102,8 @@ impl DHParameter {
             theta,
             joint_type: JointType::Prismatic,
             joint_offset: d_offset,
+            convention: DHConvention::Standard,
+            inertia: None,
         }
     }
 
@@ -77,6 +117,23 @@ impl DHParameter {
         DHParameter::revolute(link_length, 0.0, 0.0, 0.0)
     }
 
+    /// Return a copy of these parameters interpreted in another convention
+    ///
+    /// The values are reused as-is; use `standard_to_modified` or
+    /// `modified_to_standard` to convert an equivalent robot.
+    pub fn with_convention(&self, convention: DHConvention) -> Self {
+        let mut new_dh = *self;
+        new_dh.convention = convention;
+        new_dh
+    }
+
+    /// Return a copy of these parameters with the given link mass properties
+    pub fn with_inertia(&self, inertia: LinkInertia) -> Self {
+        let mut new_dh = *self;
+        new_dh.inertia = Some(inertia);
+        new_dh
+    }
+
     /// Update this DH parameter with a new joint value
     ///
     /// For revolute joints, updates theta
@@ -99,28 +156,117 @@ impl DHParameter {
     /// Standard DH Convention:
     /// T(i-1,i) = Rot(Z, θ) * Trans(Z, d) * Trans(X, a) * Rot(X, α)
     ///
+    /// Modified DH Convention:
+    /// T(i-1,i) = Rot(X, α) * Trans(X, a) * Rot(Z, θ) * Trans(Z, d)
+    ///
     /// This represents the transformation from frame i-1 to frame i
-    pub fn to_transform(&self) -> Transform3D {
-        // Step 1: Rotate around Z by theta
-        let rot_z = Transform3D::rotation_z(self.theta);
+    pub fn to_transform(self) -> Transform3D {
+        match self.convention {
+            DHConvention::Standard => self.joint_transform().compose(&self.link_transform()),
+            DHConvention::Modified => self.link_transform().compose(&self.joint_transform()),
+        }
+    }
 
-        // Step 2: Translate along Z by d
-        let trans_z = Transform3D::translation(0.0, 0.0, self.d);
+    /// Fixed part of the link transform that precedes the joint motion
+    ///
+    /// Identity for Standard DH (the joint acts about Z of frame i-1),
+    /// Rot(X, α) * Trans(X, a) for Modified DH (the joint acts about Z of frame i).
+    pub fn pre_joint_transform(self) -> Transform3D {
+        match self.convention {
+            DHConvention::Standard => Transform3D::identity(),
+            DHConvention::Modified => self.link_transform(),
+        }
+    }
+
+    /// Joint part of the transform: Rot(Z, θ) * Trans(Z, d)
+    fn joint_transform(self) -> Transform3D {
+        Transform3D::rotation_z(self.theta).compose(&Transform3D::translation(0.0, 0.0, self.d))
+    }
+
+    /// Link part of the transform: Trans(X, a) * Rot(X, α) (the two commute)
+    fn link_transform(self) -> Transform3D {
+        Transform3D::translation(self.a, 0.0, 0.0).compose(&Transform3D::rotation_x(self.alpha))
+    }
+}
+
+/// Convert a Standard DH chain to an equivalent Modified DH chain
+///
+/// Modified link i takes (a, α) from standard link i-1; the first link gets
+/// a = α = 0 and the last standard (a, α) becomes the `tool` transform.
+pub fn standard_to_modified(params: &[DHParameter]) -> ConvertedDH {
+    let mut converted = Vec::with_capacity(params.len());
+    let mut previous = (0.0, 0.0);
+
+    for dh in params {
+        let mut modified = dh.with_convention(DHConvention::Modified);
+        (modified.a, modified.alpha) = previous;
+        // Standard frame i sits at the end of the link, modified frame i at its joint
+        modified.inertia = dh.inertia.map(|inertia| inertia.expressed_in(&dh.link_transform()));
+        previous = (dh.a, dh.alpha);
+        converted.push(modified);
+    }
 
-        // Step 3: Translate along X by a
-        let trans_x = Transform3D::translation(self.a, 0.0, 0.0);
+    ConvertedDH {
+        base: Transform3D::identity(),
+        params: converted,
+        tool: Transform3D::translation(previous.0, 0.0, 0.0).compose(&Transform3D::rotation_x(previous.1)),
+    }
+}
 
-        // Step 4: Rotate around X by alpha
-        let rot_x = Transform3D::rotation_x(self.alpha);
+/// Convert a Modified DH chain to an equivalent Standard DH chain
+///
+/// Standard link i takes (a, α) from modified link i+1; the first modified
+/// (a, α) becomes the `base` transform and the last link gets a = α = 0.
+pub fn modified_to_standard(params: &[DHParameter]) -> ConvertedDH {
+    let mut converted = Vec::with_capacity(params.len());
+
+    for (i, dh) in params.iter().enumerate() {
+        let mut standard = dh.with_convention(DHConvention::Standard);
+        (standard.a, standard.alpha) = params.get(i + 1).map_or((0.0, 0.0), |next| (next.a, next.alpha));
+        standard.inertia = dh
+            .inertia
+            .map(|inertia| inertia.expressed_in(&standard.link_transform().inverse()));
+        converted.push(standard);
+    }
+
+    let base = params.first().map_or(Transform3D::identity(), |first| {
+        Transform3D::rotation_x(first.alpha).compose(&Transform3D::translation(first.a, 0.0, 0.0))
+    });
 
-        // Compose in order: Rot(Z,θ) * Trans(Z,d) * Trans(X,a) * Rot(X,α)
-        rot_z
-            .compose(&trans_z)
-            .compose(&trans_x)
-            .compose(&rot_x)
+    ConvertedDH {
+        base,
+        params: converted,
+        tool: Transform3D::identity(),
     }
 }
 
+/// Check that every link of a DH chain can be used to build a robot
+///
+/// All parameters must be finite (no NaN or infinite values) and link inertias
+/// physically valid (see `LinkInertia::validate`).
+pub fn validate_dh_params(params: &[DHParameter]) -> Result<(), RoboticsError> {
+    for (index, dh) in params.iter().enumerate() {
+        let fields = [
+            ("a", dh.a),
+            ("alpha", dh.alpha),
+            ("d", dh.d),
+            ("theta", dh.theta),
+            ("joint_offset", dh.joint_offset),
+        ];
+        if let Some((name, value)) = fields.iter().find(|(_, value)| !value.is_finite()) {
+            return Err(RoboticsError::MalformedDh {
+                index,
+                reason: format!("{} is {}", name, value),
+            });
+        }
+        if let Some(Err(reason)) = dh.inertia.map(|inertia| inertia.validate()) {
+            return Err(RoboticsError::MalformedDh { index, reason });
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -239,6 +385,8 @@ mod tests {
             theta: PI / 6.0,
             joint_type: JointType::Revolute,
             joint_offset: 0.0,
+            convention: DHConvention::Standard,
+            inertia: None,
         };
 
         let transform = dh.to_transform();
@@ -250,4 +398,121 @@ mod tests {
         assert!(result.y.is_finite());
         assert!(result.z.is_finite());
     }
+
+    #[test]
+    fn test_modified_dh_with_twist() {
+        // Modified: Rot(X,90°) * Trans(X,1) first, then Rot(Z,θ) * Trans(Z,d)
+        let dh = DHParameter::revolute(1.0, PI / 2.0, 0.5, 0.0).with_convention(DHConvention::Modified);
+        let result = dh.to_transform().transform_point(&Point3D::origin());
+
+        // After Rot(X,90°) the Z axis points along -Y, so d = 0.5 moves the origin to (1, -0.5, 0)
+        assert!(point_approx_eq(&result, &Point3D::new(1.0, -0.5, 0.0)));
+    }
+
+    #[test]
+    fn test_convention_defaults_to_standard_when_deserializing() {
+        let json = r#"{ "a": 1.0, "alpha": 0.0, "d": 0.0, "theta": 0.0, "joint_type": "Revolute", "joint_offset": 0.0 }"#;
+        let dh: DHParameter = serde_json::from_str(json).unwrap();
+        assert_eq!(dh.convention, DHConvention::Standard);
+        assert!(dh.inertia.is_none());
+        assert!(point_approx_eq(
+            &dh.to_transform().transform_point(&Point3D::origin()),
+            &Point3D::new(1.0, 0.0, 0.0)
+        ));
+    }
+
+    fn chain_transform(base: &Transform3D, params: &[DHParameter], values: &[f64], tool: &Transform3D) -> Transform3D {
+        params
+            .iter()
+            .zip(values)
+            .fold(*base, |frame, (dh, &value)| frame.compose(&dh.with_joint_value(value).to_transform()))
+            .compose(tool)
+    }
+
+    fn transforms_approx_eq(a: &Transform3D, b: &Transform3D) -> bool {
+        (a.matrix() - b.matrix()).abs().max() < EPSILON
+    }
+
+    fn six_dof_standard() -> Vec<DHParameter> {
+        vec![
+            DHParameter::revolute(0.0, PI / 2.0, 0.333, 0.0),
+            DHParameter::revolute(0.4, 0.0, 0.0, -PI / 2.0),
+            DHParameter::prismatic(0.05, -PI / 2.0, 0.1, 0.3),
+            DHParameter::revolute(0.0, PI / 2.0, 0.384, 0.0),
+            DHParameter::revolute(0.088, -PI / 2.0, 0.0, 0.0),
+            DHParameter::revolute(0.0, 0.0, 0.107, 0.0),
+        ]
+    }
+
+    #[test]
+    fn test_standard_to_modified_same_end_effector_pose() {
+        let standard = six_dof_standard();
+        let converted = standard_to_modified(&standard);
+        let identity = Transform3D::identity();
+
+        for values in [[0.0; 6], [0.3, -0.7, 0.12, 1.1, -0.4, 2.0], [-1.2, 0.5, -0.05, -0.3, 0.9, -2.5]] {
+            let expected = chain_transform(&identity, &standard, &values, &identity);
+            let actual = chain_transform(&converted.base, &converted.params, &values, &converted.tool);
+            assert!(transforms_approx_eq(&expected, &actual));
+        }
+
+        assert!(converted.params.iter().all(|dh| dh.convention == DHConvention::Modified));
+        assert_eq!(converted.params[0].a, 0.0);
+        assert_eq!(converted.params[1].alpha, PI / 2.0);
+    }
+
+    #[test]
+    fn test_modified_to_standard_same_end_effector_pose() {
+        // Panda-style Modified DH table
+        let modified: Vec<DHParameter> = [
+            (0.0, 0.0, 0.333),
+            (0.0, -PI / 2.0, 0.0),
+            (0.0, PI / 2.0, 0.316),
+            (0.0825, PI / 2.0, 0.0),
+            (-0.0825, -PI / 2.0, 0.384),
+            (0.0, PI / 2.0, 0.0),
+            (0.088, PI / 2.0, 0.0),
+        ]
+        .iter()
+        .map(|&(a, alpha, d)| DHParameter::revolute(a, alpha, d, 0.0).with_convention(DHConvention::Modified))
+        .collect();
+
+        let converted = modified_to_standard(&modified);
+        let identity = Transform3D::identity();
+        let values = [0.1, -0.5, 0.3, -2.0, 0.4, 1.6, 0.7];
+
+        let expected = chain_transform(&identity, &modified, &values, &identity);
+        let actual = chain_transform(&converted.base, &converted.params, &values, &converted.tool);
+        assert!(transforms_approx_eq(&expected, &actual));
+    }
+
+    #[test]
+    fn test_convention_round_trip() {
+        let standard = six_dof_standard();
+        let back = modified_to_standard(&standard_to_modified(&standard).params);
+
+        // Only the first link's (a, α) ends up in `base`; the rest maps back exactly
+        for (original, restored) in standard.iter().zip(back.params.iter()).take(standard.len() - 1) {
+            assert_eq!(original.a, restored.a);
+            assert_eq!(original.alpha, restored.alpha);
+            assert_eq!(original.d, restored.d);
+            assert_eq!(original.joint_offset, restored.joint_offset);
+            assert_eq!(restored.convention, DHConvention::Standard);
+        }
+    }
+
+    #[test]
+    fn test_validate_dh_params() {
+        let mut params = vec![DHParameter::planar(1.0), DHParameter::prismatic(0.0, 0.0, 0.2, 0.0)];
+        assert!(validate_dh_params(&params).is_ok());
+
+        params[1].alpha = f64::NAN;
+        match validate_dh_params(&params) {
+            Err(RoboticsError::MalformedDh { index, reason }) => {
+                assert_eq!(index, 1);
+                assert!(reason.starts_with("alpha"));
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
 }
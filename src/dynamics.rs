@@ -0,0 +1,524 @@
+use crate::dh_parameters::{DHParameter, JointType};
+use crate::error::{check_dimension, check_finite, RoboticsError};
+use crate::geometry3d::Transform3D;
+use crate::robot::RobotArm;
+use nalgebra::{DMatrix, DVector, Matrix3, Vector3};
+use serde::{Deserialize, Serialize};
+
+/// Standard gravity in the world frame (m/s², Z up)
+pub const STANDARD_GRAVITY: [f64; 3] = [0.0, 0.0, -9.80665];
+
+/// Relative tolerance on the eigenvalues of an inertia tensor
+const INERTIA_TOLERANCE: f64 = 1e-9;
+
+/// Inertial properties of a rigid link
+///
+/// Everything is expressed in the link frame, i.e. DH frame i for link i (the
+/// frame at the end of the link for Standard DH, at its joint for Modified DH).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LinkInertia {
+    /// Mass (kg)
+    pub mass: f64,
+    /// Center of mass in the link frame (m)
+    #[serde(default)]
+    pub center_of_mass: [f64; 3],
+    /// Inertia tensor about the center of mass, in link frame axes (kg·m²):
+    /// `[ixx, iyy, izz, ixy, ixz, iyz]`
+    #[serde(default)]
+    pub inertia: [f64; 6],
+}
+
+impl LinkInertia {
+    pub fn new(mass: f64, center_of_mass: [f64; 3], inertia: [f64; 6]) -> Self {
+        LinkInertia {
+            mass,
+            center_of_mass,
+            inertia,
+        }
+    }
+
+    /// Point mass (no rotational inertia about its center of mass)
+    pub fn point_mass(mass: f64, center_of_mass: [f64; 3]) -> Self {
+        LinkInertia::new(mass, center_of_mass, [0.0; 6])
+    }
+
+    /// Inertia tensor about the center of mass as a symmetric 3x3 matrix
+    pub fn tensor(&self) -> Matrix3<f64> {
+        let [ixx, iyy, izz, ixy, ixz, iyz] = self.inertia;
+        Matrix3::new(ixx, ixy, ixz, ixy, iyy, iyz, ixz, iyz, izz)
+    }
+
+    /// Center of mass as a vector in the link frame
+    pub fn center(&self) -> Vector3<f64> {
+        Vector3::from(self.center_of_mass)
+    }
+
+    /// Same body expressed in another frame, given the pose of the current link
+    /// frame in the new one
+    pub fn expressed_in(&self, frame: &Transform3D) -> Self {
+        let rotation = frame.rotation_matrix();
+        let center = rotation * self.center() + frame.translation_vector();
+        let tensor = rotation * self.tensor() * rotation.transpose();
+
+        LinkInertia {
+            mass: self.mass,
+            center_of_mass: center.into(),
+            inertia: [
+                tensor[(0, 0)],
+                tensor[(1, 1)],
+                tensor[(2, 2)],
+                tensor[(0, 1)],
+                tensor[(0, 2)],
+                tensor[(1, 2)],
+            ],
+        }
+    }
+
+    /// Reason the properties are not physical, if any
+    ///
+    /// Everything must be finite, the mass non-negative and the inertia tensor
+    /// positive semi-definite.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut values = vec![self.mass];
+        values.extend_from_slice(&self.center_of_mass);
+        values.extend_from_slice(&self.inertia);
+        if values.iter().any(|value| !value.is_finite()) {
+            return Err("inertia has a non-finite value".to_string());
+        }
+        if self.mass < 0.0 {
+            return Err(format!("mass {} is negative", self.mass));
+        }
+
+        let tensor = self.tensor();
+        let tolerance = INERTIA_TOLERANCE * tensor.abs().max().max(1.0);
+        if tensor.symmetric_eigenvalues().iter().any(|&eigenvalue| eigenvalue < -tolerance) {
+            return Err("inertia tensor is not positive semi-definite".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+/// Kinematic and dynamic state of one link during the forward RNEA pass,
+/// all in the base frame
+struct LinkState {
+    joint_type: JointType,
+    /// Joint axis (unit vector) and a point on it
+    axis: Vector3<f64>,
+    joint_origin: Vector3<f64>,
+    /// Origin and orientation of the link frame
+    origin: Vector3<f64>,
+    rotation: Matrix3<f64>,
+    angular_velocity: Vector3<f64>,
+    angular_acceleration: Vector3<f64>,
+    /// Linear acceleration of the link frame origin (gravity included)
+    acceleration: Vector3<f64>,
+}
+
+/// Joint torques (forces for prismatic joints) for a motion: τ = M(q)q̈ + C(q, q̇)q̇ + g(q)
+///
+/// Recursive Newton-Euler over the DH links: velocities and accelerations are
+/// propagated from the base outwards, then the link forces and moments are
+/// accumulated inwards and projected onto each joint axis. Gravity is a
+/// world-frame acceleration (e.g. `STANDARD_GRAVITY`), rotated into the base
+/// frame; the base itself is fixed and the tool frame is massless.
+///
+/// Links without `inertia` are massless. Joint values are used as given,
+/// without the limit policy. Only DH robots carry inertial properties; other
+/// models are reported as `Unsupported`.
+pub fn inverse_dynamics(
+    robot: &RobotArm,
+    positions: &[f64],
+    velocities: &[f64],
+    accelerations: &[f64],
+    gravity: [f64; 3],
+) -> Result<Vec<f64>, RoboticsError> {
+    let links = dynamic_links(robot)?;
+    for values in [positions, velocities, accelerations] {
+        check_dimension(links.len(), values.len())?;
+        check_finite(values)?;
+    }
+    check_finite(&gravity)?;
+
+    // Accelerating the base upwards is equivalent to pulling every link down
+    let gravity = robot.base.rotation_matrix().transpose() * Vector3::from(gravity);
+    let states = forward_pass(links, positions, velocities, accelerations, -gravity);
+
+    let mut torques = vec![0.0; links.len()];
+    let mut force = Vector3::zeros();
+    let mut moment = Vector3::zeros();
+    let mut outer_joint = Vector3::zeros();
+
+    for (i, (dh, state)) in links.iter().zip(states.iter()).enumerate().rev() {
+        // Move the moment of the outer links from their joint to this one
+        moment += (outer_joint - state.joint_origin).cross(&force);
+
+        if let Some(inertia) = &dh.inertia {
+            let offset = state.rotation * inertia.center();
+            let center = state.origin + offset;
+            let center_acceleration = state.acceleration
+                + state.angular_acceleration.cross(&offset)
+                + state.angular_velocity.cross(&state.angular_velocity.cross(&offset));
+            let tensor = state.rotation * inertia.tensor() * state.rotation.transpose();
+
+            let inertial_force = center_acceleration * inertia.mass;
+            let inertial_moment = tensor * state.angular_acceleration
+                + state.angular_velocity.cross(&(tensor * state.angular_velocity));
+
+            force += inertial_force;
+            moment += inertial_moment + (center - state.joint_origin).cross(&inertial_force);
+        }
+
+        torques[i] = match state.joint_type {
+            JointType::Revolute => state.axis.dot(&moment),
+            JointType::Prismatic => state.axis.dot(&force),
+        };
+        outer_joint = state.joint_origin;
+    }
+
+    Ok(torques)
+}
+
+/// Torques that hold the robot still against gravity: τ = g(q)
+pub fn gravity_torques(robot: &RobotArm, positions: &[f64], gravity: [f64; 3]) -> Result<Vec<f64>, RoboticsError> {
+    let zeros = vec![0.0; positions.len()];
+    inverse_dynamics(robot, positions, &zeros, &zeros, gravity)
+}
+
+/// Joint-space mass matrix M(q), by the composite rigid body algorithm
+///
+/// Walking from the tip inwards, the links beyond each joint are merged into
+/// one composite body; column i of M holds the joint torques needed to give
+/// that body a unit acceleration about (or along) joint i from rest. The
+/// matrix is symmetric and positive definite as long as every joint moves
+/// some mass or inertia.
+pub fn mass_matrix(robot: &RobotArm, positions: &[f64]) -> Result<DMatrix<f64>, RoboticsError> {
+    let links = dynamic_links(robot)?;
+    check_dimension(links.len(), positions.len())?;
+    check_finite(positions)?;
+
+    let zeros = vec![0.0; links.len()];
+    let states = forward_pass(links, positions, &zeros, &zeros, Vector3::zeros());
+    let mut matrix = DMatrix::zeros(links.len(), links.len());
+    let mut composite = CompositeBody::default();
+
+    for i in (0..links.len()).rev() {
+        let state = &states[i];
+        if let Some(inertia) = &links[i].inertia {
+            let center = state.origin + state.rotation * inertia.center();
+            composite.add(inertia.mass, center, state.rotation * inertia.tensor() * state.rotation.transpose());
+        }
+
+        // Force and moment about the composite center for a unit joint acceleration
+        let (force, moment) = match state.joint_type {
+            JointType::Revolute => (
+                state.axis.cross(&(composite.center - state.joint_origin)) * composite.mass,
+                composite.inertia * state.axis,
+            ),
+            JointType::Prismatic => (state.axis * composite.mass, Vector3::zeros()),
+        };
+
+        for (j, inner) in states.iter().enumerate().take(i + 1) {
+            let value = match inner.joint_type {
+                JointType::Revolute => inner.axis.dot(&(moment + (composite.center - inner.joint_origin).cross(&force))),
+                JointType::Prismatic => inner.axis.dot(&force),
+            };
+            matrix[(i, j)] = value;
+            matrix[(j, i)] = value;
+        }
+    }
+
+    Ok(matrix)
+}
+
+/// Joint accelerations produced by the given torques: q̈ = M(q)⁻¹ (τ - C(q, q̇)q̇ - g(q))
+///
+/// The bias torques come from `inverse_dynamics` with zero acceleration and
+/// the mass matrix from `mass_matrix`. Fails when the mass matrix is singular,
+/// i.e. some joint moves no mass or inertia.
+pub fn forward_dynamics(
+    robot: &RobotArm,
+    positions: &[f64],
+    velocities: &[f64],
+    torques: &[f64],
+    gravity: [f64; 3],
+) -> Result<Vec<f64>, RoboticsError> {
+    check_dimension(positions.len(), torques.len())?;
+    check_finite(torques)?;
+    let bias = inverse_dynamics(robot, positions, velocities, &vec![0.0; positions.len()], gravity)?;
+    let mass = mass_matrix(robot, positions)?;
+
+    let cholesky = mass.cholesky().ok_or_else(|| {
+        RoboticsError::Unsupported("mass matrix is singular: every joint must move some mass or inertia".to_string())
+    })?;
+    let net = DVector::from_iterator(torques.len(), torques.iter().zip(&bias).map(|(torque, bias)| torque - bias));
+
+    Ok(cholesky.solve(&net).iter().copied().collect())
+}
+
+/// Links beyond a joint lumped into one rigid body (base frame)
+#[derive(Default)]
+struct CompositeBody {
+    mass: f64,
+    center: Vector3<f64>,
+    /// Inertia tensor about `center`
+    inertia: Matrix3<f64>,
+}
+
+impl CompositeBody {
+    /// Merge a body into this one (parallel axis theorem)
+    fn add(&mut self, mass: f64, center: Vector3<f64>, inertia: Matrix3<f64>) {
+        let total = self.mass + mass;
+        let merged_center = if total > 0.0 {
+            (self.center * self.mass + center * mass) / total
+        } else {
+            center
+        };
+        let shift = |offset: Vector3<f64>| Matrix3::identity() * offset.norm_squared() - offset * offset.transpose();
+
+        self.inertia = self.inertia
+            + shift(self.center - merged_center) * self.mass
+            + inertia
+            + shift(center - merged_center) * mass;
+        self.mass = total;
+        self.center = merged_center;
+    }
+}
+
+/// The DH links of a robot, which carry the inertial properties
+fn dynamic_links(robot: &RobotArm) -> Result<&[DHParameter], RoboticsError> {
+    match (&robot.screw_model, &robot.dh_params) {
+        (None, Some(dh_params)) => Ok(dh_params),
+        _ => Err(RoboticsError::Unsupported(
+            "dynamics requires a DH robot (link inertias are attached to DH links)".to_string(),
+        )),
+    }
+}
+
+/// Outward RNEA pass: link frames, velocities and accelerations in the base frame
+fn forward_pass(
+    links: &[DHParameter],
+    positions: &[f64],
+    velocities: &[f64],
+    accelerations: &[f64],
+    base_acceleration: Vector3<f64>,
+) -> Vec<LinkState> {
+    let mut states: Vec<LinkState> = Vec::with_capacity(links.len());
+    let mut frame = Transform3D::identity();
+    let mut angular_velocity = Vector3::zeros();
+    let mut angular_acceleration = Vector3::zeros();
+    let mut acceleration = base_acceleration;
+
+    for (i, dh) in links.iter().enumerate() {
+        let joint = frame.compose(&dh.pre_joint_transform());
+        let axis = joint.rotation_matrix() * Vector3::z();
+        let joint_origin = joint.translation_vector();
+        let previous_origin = frame.translation_vector();
+        frame = frame.compose(&dh.with_joint_value(positions[i]).to_transform());
+        let origin = frame.translation_vector();
+        let (velocity, joint_acceleration) = (velocities[i], accelerations[i]);
+
+        match dh.joint_type {
+            JointType::Revolute => {
+                // Acceleration of the joint point, carried by the previous link
+                let lever = joint_origin - previous_origin;
+                let joint_point = acceleration
+                    + angular_acceleration.cross(&lever)
+                    + angular_velocity.cross(&angular_velocity.cross(&lever));
+
+                angular_acceleration += axis * joint_acceleration + angular_velocity.cross(&(axis * velocity));
+                angular_velocity += axis * velocity;
+
+                let lever = origin - joint_origin;
+                acceleration = joint_point
+                    + angular_acceleration.cross(&lever)
+                    + angular_velocity.cross(&angular_velocity.cross(&lever));
+            }
+            JointType::Prismatic => {
+                // The link slides along an axis fixed in the previous link (Coriolis term 2ω × v)
+                let lever = origin - previous_origin;
+                acceleration += angular_acceleration.cross(&lever)
+                    + angular_velocity.cross(&angular_velocity.cross(&lever))
+                    + angular_velocity.cross(&(axis * velocity)) * 2.0
+                    + axis * joint_acceleration;
+            }
+        }
+
+        states.push(LinkState {
+            joint_type: dh.joint_type,
+            axis,
+            joint_origin,
+            origin,
+            rotation: frame.rotation_matrix(),
+            angular_velocity,
+            angular_acceleration,
+            acceleration,
+        });
+    }
+
+    states
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dh_parameters::{modified_to_standard, standard_to_modified};
+    use crate::presets::Preset;
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    const EPSILON: f64 = 1e-9;
+    const G: f64 = 9.81;
+
+    /// Planar arm in the XY plane with a point mass at the end of every link
+    fn planar_arm(links: &[(f64, f64)]) -> RobotArm {
+        RobotArm::from_dh_params(
+            links
+                .iter()
+                .map(|&(length, mass)| DHParameter::planar(length).with_inertia(LinkInertia::point_mass(mass, [0.0; 3])))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_two_link_planar_arm_matches_closed_form() {
+        let (l1, m1, l2, m2) = (1.2, 2.0, 0.8, 1.5);
+        let robot = planar_arm(&[(l1, m1), (l2, m2)]);
+        let (q, qd, qdd) = ([0.4, -0.9], [0.7, -1.3], [1.1, 0.6]);
+
+        let torques = inverse_dynamics(&robot, &q, &qd, &qdd, [0.0, -G, 0.0]).unwrap();
+
+        // Craig, Introduction to Robotics, eq. 6.60 (gravity along -Y)
+        let (c1, c2, s2, c12) = (q[0].cos(), q[1].cos(), q[1].sin(), (q[0] + q[1]).cos());
+        let tau1 = m2 * l2 * l2 * (qdd[0] + qdd[1])
+            + m2 * l1 * l2 * c2 * (2.0 * qdd[0] + qdd[1])
+            + (m1 + m2) * l1 * l1 * qdd[0]
+            - m2 * l1 * l2 * s2 * qd[1] * qd[1]
+            - 2.0 * m2 * l1 * l2 * s2 * qd[0] * qd[1]
+            + m2 * l2 * G * c12
+            + (m1 + m2) * l1 * G * c1;
+        let tau2 = m2 * l1 * l2 * c2 * qdd[0] + m2 * l1 * l2 * s2 * qd[0] * qd[0] + m2 * l2 * G * c12 + m2 * l2 * l2 * (qdd[0] + qdd[1]);
+
+        assert!((torques[0] - tau1).abs() < EPSILON);
+        assert!((torques[1] - tau2).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_prismatic_lift_and_rotating_slider() {
+        // Vertical lift carrying 3 kg: f = m (q̈ + g)
+        let lift = RobotArm::from_dh_params(vec![
+            DHParameter::prismatic(0.0, 0.0, 0.0, 0.0).with_inertia(LinkInertia::point_mass(3.0, [0.0; 3])),
+        ]);
+        let forces = inverse_dynamics(&lift, &[0.4], &[0.2], &[1.5], [0.0, 0.0, -G]).unwrap();
+        assert!((forces[0] - 3.0 * (1.5 + G)).abs() < EPSILON);
+
+        // Horizontal slider on a turntable (polar RP arm), mass at the slider tip:
+        // τ = m r² θ̈ + 2 m r ṙ θ̇, f = m (r̈ - r θ̇²)
+        let mass = 2.0;
+        let robot = RobotArm::from_dh_params(vec![
+            DHParameter::revolute(0.0, -FRAC_PI_2, 0.0, 0.0),
+            DHParameter::prismatic(0.0, 0.0, 0.0, 0.0).with_inertia(LinkInertia::point_mass(mass, [0.0; 3])),
+        ]);
+        let (r, rd, rdd, thetad, thetadd) = (0.7, 0.3, -0.4, 1.2, 0.5);
+        let torques = inverse_dynamics(&robot, &[0.3, r], &[thetad, rd], &[thetadd, rdd], [0.0, 0.0, -G]).unwrap();
+
+        assert!((torques[0] - (mass * r * r * thetadd + 2.0 * mass * r * rd * thetad)).abs() < EPSILON);
+        assert!((torques[1] - mass * (rdd - r * thetad * thetad)).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_puma560_gravity_load() {
+        // Reference values from the Robotics Toolbox (p560.gravload) with g = 9.81
+        let robot = Preset::Puma560.build();
+        let nominal = [0.0, FRAC_PI_4, PI, 0.0, FRAC_PI_4, 0.0];
+        let expected = [0.0, 31.6399, 6.0351, 0.0, 0.0283, 0.0];
+
+        let torques = gravity_torques(&robot, &nominal, [0.0, 0.0, -G]).unwrap();
+
+        for (torque, expected) in torques.iter().zip(expected.iter()) {
+            assert!((torque - expected).abs() < 1e-4, "{:?}", torques);
+        }
+    }
+
+    #[test]
+    fn test_rotated_base_rotates_gravity() {
+        let mut robot = Preset::Ur5.build();
+        let q = [0.3, -1.1, 0.8, -0.5, 1.2, 0.2];
+        let qd = [0.5, -0.2, 0.3, 0.9, -0.4, 0.1];
+        let qdd = [0.2, 0.1, -0.6, 0.3, 0.5, -0.2];
+
+        // Mounted upside down: gravity points along +Z of the base
+        let upright = inverse_dynamics(&robot, &q, &qd, &qdd, [0.0, 0.0, G]).unwrap();
+        robot.base = Transform3D::from_xyz_rpy([1.0, 2.0, 3.0], [PI, 0.0, 0.0]);
+        let inverted = inverse_dynamics(&robot, &q, &qd, &qdd, [0.0, 0.0, -G]).unwrap();
+
+        assert!(upright.iter().zip(inverted.iter()).all(|(a, b)| (a - b).abs() < EPSILON));
+    }
+
+    #[test]
+    fn test_convention_conversion_keeps_dynamics() {
+        let robot = Preset::Puma560.build();
+        let q = [0.3, -0.6, 0.8, -0.5, 1.2, 0.2];
+        let qd = [0.5, -0.2, 0.3, 0.9, -0.4, 0.1];
+        let qdd = [0.2, 0.1, -0.6, 0.3, 0.5, -0.2];
+        let expected = inverse_dynamics(&robot, &q, &qd, &qdd, STANDARD_GRAVITY).unwrap();
+
+        let modified = standard_to_modified(robot.dh_params.as_ref().unwrap());
+        let modified_robot = RobotArm::from_dh_params(modified.params.clone());
+        let torques = inverse_dynamics(&modified_robot, &q, &qd, &qdd, STANDARD_GRAVITY).unwrap();
+        assert!(torques.iter().zip(expected.iter()).all(|(a, b)| (a - b).abs() < EPSILON));
+
+        let standard_robot = RobotArm::from_dh_params(modified_to_standard(&modified.params).params);
+        let torques = inverse_dynamics(&standard_robot, &q, &qd, &qdd, STANDARD_GRAVITY).unwrap();
+        assert!(torques.iter().zip(expected.iter()).all(|(a, b)| (a - b).abs() < EPSILON));
+    }
+
+    #[test]
+    fn test_mass_matrix_matches_unit_accelerations() {
+        let robot = Preset::Puma560.build();
+        let q = [0.3, -0.6, 0.8, -0.5, 1.2, 0.2];
+        let zeros = [0.0; 6];
+        let matrix = mass_matrix(&robot, &q).unwrap();
+
+        // Column j of M is the torque for a unit acceleration of joint j without gravity or velocity
+        for j in 0..6 {
+            let mut unit = [0.0; 6];
+            unit[j] = 1.0;
+            let column = inverse_dynamics(&robot, &q, &zeros, &unit, [0.0; 3]).unwrap();
+            for i in 0..6 {
+                assert!((matrix[(i, j)] - column[i]).abs() < EPSILON, "M[{}, {}]", i, j);
+            }
+        }
+        assert!(matrix.clone().cholesky().is_some());
+    }
+
+    #[test]
+    fn test_forward_dynamics_inverts_inverse_dynamics() {
+        let mut robot = Preset::Puma560.build();
+        robot.base = Transform3D::from_xyz_rpy([0.0, 0.0, 0.5], [0.2, -0.1, 0.4]);
+        let q = [0.3, -0.6, 0.8, -0.5, 1.2, 0.2];
+        let qd = [0.5, -0.2, 0.3, 0.9, -0.4, 0.1];
+        let qdd = [0.2, 0.1, -0.6, 0.3, 0.5, -0.2];
+
+        let torques = inverse_dynamics(&robot, &q, &qd, &qdd, STANDARD_GRAVITY).unwrap();
+        let accelerations = forward_dynamics(&robot, &q, &qd, &torques, STANDARD_GRAVITY).unwrap();
+
+        assert!(accelerations.iter().zip(qdd.iter()).all(|(a, b)| (a - b).abs() < 1e-8), "{:?}", accelerations);
+
+        // A wrist link without inertia leaves the mass matrix singular
+        let massless = planar_arm(&[(1.0, 1.0), (1.0, 0.0)]);
+        let error = forward_dynamics(&massless, &[0.0, 0.0], &[0.0, 0.0], &[0.0, 0.0], STANDARD_GRAVITY).unwrap_err();
+        assert_eq!(error.code(), "UNSUPPORTED");
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let robot = planar_arm(&[(1.0, 1.0), (1.0, 1.0)]);
+        assert_eq!(gravity_torques(&robot, &[0.0], STANDARD_GRAVITY).unwrap_err().code(), "DIMENSION_MISMATCH");
+        assert_eq!(gravity_torques(&robot, &[0.0, f64::NAN], STANDARD_GRAVITY).unwrap_err().code(), "NON_FINITE");
+        assert_eq!(gravity_torques(&RobotArm::new(vec![1.0]), &[0.0], STANDARD_GRAVITY).unwrap_err().code(), "UNSUPPORTED");
+
+        assert!(LinkInertia::new(-1.0, [0.0; 3], [0.0; 6]).validate().is_err());
+        assert!(LinkInertia::new(1.0, [0.0; 3], [1.0, 1.0, 1.0, 2.0, 0.0, 0.0]).validate().is_err());
+        assert!(LinkInertia::new(1.0, [0.0; 3], [1.0, 2.0, 2.5, 0.1, 0.0, 0.0]).validate().is_ok());
+    }
+}
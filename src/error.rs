@@ -0,0 +1,155 @@
+use crate::limits::LimitViolation;
+use std::fmt;
+use wasm_bindgen::JsValue;
+
+/// Errors reported by the robotics API
+///
+/// Each variant has a stable `code` so JavaScript callers can tell failure
+/// causes apart without parsing messages.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RoboticsError {
+    /// A vector has the wrong number of entries (e.g. joint values vs. joints)
+    DimensionMismatch { expected: usize, actual: usize },
+    /// An input value is NaN or infinite
+    NonFinite { index: usize, value: f64 },
+    /// A joint value is outside its limits (with `LimitPolicy::Reject`)
+    LimitViolation(LimitViolation),
+    /// A DH link cannot be used to build a robot
+    MalformedDh { index: usize, reason: String },
+    /// The target cannot be reached by the robot
+    Unreachable(String),
+    /// The operation is not available for this robot
+    Unsupported(String),
+    /// An option or argument is out of its valid range
+    InvalidArgument(String),
+    /// Input could not be parsed (JS values, URDF documents)
+    Parse(String),
+    /// A result could not be converted to a JS value
+    Serialization(String),
+}
+
+impl RoboticsError {
+    /// Stable machine-readable error code
+    pub fn code(&self) -> &'static str {
+        match self {
+            RoboticsError::DimensionMismatch { .. } => "DIMENSION_MISMATCH",
+            RoboticsError::NonFinite { .. } => "NON_FINITE",
+            RoboticsError::LimitViolation(_) => "LIMIT_VIOLATION",
+            RoboticsError::MalformedDh { .. } => "MALFORMED_DH",
+            RoboticsError::Unreachable(_) => "UNREACHABLE",
+            RoboticsError::Unsupported(_) => "UNSUPPORTED",
+            RoboticsError::InvalidArgument(_) => "INVALID_ARGUMENT",
+            RoboticsError::Parse(_) => "PARSE_ERROR",
+            RoboticsError::Serialization(_) => "SERIALIZATION_ERROR",
+        }
+    }
+}
+
+impl fmt::Display for RoboticsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RoboticsError::DimensionMismatch { expected, actual } => {
+                write!(f, "Expected {} values, got {}", expected, actual)
+            }
+            RoboticsError::NonFinite { index, value } => {
+                write!(f, "Value {} at index {} is not finite", value, index)
+            }
+            RoboticsError::LimitViolation(violation) => write!(f, "Limit violation: {}", violation),
+            RoboticsError::MalformedDh { index, reason } => {
+                write!(f, "Malformed DH parameters for link {}: {}", index, reason)
+            }
+            RoboticsError::Unreachable(message) => write!(f, "Unreachable target: {}", message),
+            RoboticsError::Unsupported(message) => write!(f, "Unsupported operation: {}", message),
+            RoboticsError::InvalidArgument(message) => write!(f, "Invalid argument: {}", message),
+            RoboticsError::Parse(message) => write!(f, "Failed to parse {}", message),
+            RoboticsError::Serialization(message) => write!(f, "Failed to serialize {}", message),
+        }
+    }
+}
+
+impl std::error::Error for RoboticsError {}
+
+impl From<LimitViolation> for RoboticsError {
+    fn from(violation: LimitViolation) -> Self {
+        RoboticsError::LimitViolation(violation)
+    }
+}
+
+/// Converts to a JS `Error` with `name = "RoboticsError"` and a `code` property
+impl From<RoboticsError> for JsValue {
+    fn from(error: RoboticsError) -> Self {
+        let js_error = js_sys::Error::new(&error.to_string());
+        js_error.set_name("RoboticsError");
+        // Setting a property on a fresh Error object cannot fail
+        let _ = js_sys::Reflect::set(&js_error, &JsValue::from_str("code"), &JsValue::from_str(error.code()));
+        js_error.into()
+    }
+}
+
+/// Check that every value is finite (not NaN or infinite)
+pub fn check_finite(values: &[f64]) -> Result<(), RoboticsError> {
+    match values.iter().position(|value| !value.is_finite()) {
+        Some(index) => Err(RoboticsError::NonFinite {
+            index,
+            value: values[index],
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Check that a vector has the expected number of entries
+pub fn check_dimension(expected: usize, actual: usize) -> Result<(), RoboticsError> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(RoboticsError::DimensionMismatch { expected, actual })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_finite() {
+        assert!(check_finite(&[0.0, -1.5, 1e300]).is_ok());
+        assert_eq!(
+            check_finite(&[0.0, f64::INFINITY]).unwrap_err().code(),
+            "NON_FINITE"
+        );
+
+        match check_finite(&[1.0, 2.0, f64::NAN]) {
+            Err(RoboticsError::NonFinite { index, .. }) => assert_eq!(index, 2),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_check_dimension() {
+        assert!(check_dimension(3, 3).is_ok());
+        assert_eq!(
+            check_dimension(3, 2),
+            Err(RoboticsError::DimensionMismatch { expected: 3, actual: 2 })
+        );
+    }
+
+    #[test]
+    fn test_codes_and_messages() {
+        let violation = LimitViolation {
+            joint: 1,
+            value: 2.0,
+            lower: -1.0,
+            upper: 1.0,
+        };
+        let error = RoboticsError::from(violation);
+        assert_eq!(error.code(), "LIMIT_VIOLATION");
+        assert!(error.to_string().contains("joint 1"));
+
+        let error = RoboticsError::MalformedDh {
+            index: 0,
+            reason: "a is NaN".to_string(),
+        };
+        assert_eq!(error.code(), "MALFORMED_DH");
+        assert_eq!(error.to_string(), "Malformed DH parameters for link 0: a is NaN");
+    }
+}
@@ -1,5 +1,7 @@
-use nalgebra::Matrix4;
+use crate::error::RoboticsError;
+use nalgebra::{Matrix3, Matrix4, Matrix6, Rotation3, UnitQuaternion, Vector3, Vector6};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Point3D {
@@ -18,6 +20,213 @@ impl Point3D {
     }
 }
 
+/// Unit quaternion (w + xi + yj + zk) describing a rotation
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Quaternion {
+    pub w: f64,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Quaternion {
+    pub fn new(w: f64, x: f64, y: f64, z: f64) -> Self {
+        Quaternion { w, x, y, z }
+    }
+
+    /// The identity rotation (1, 0, 0, 0)
+    pub fn identity() -> Self {
+        Quaternion::new(1.0, 0.0, 0.0, 0.0)
+    }
+
+    /// Euclidean norm of the four components
+    pub fn norm(&self) -> f64 {
+        (self.w * self.w + self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
+    }
+
+    /// Scaled to unit norm (the identity if all components are zero)
+    pub fn normalized(&self) -> Self {
+        let norm = self.norm();
+        if norm < 1e-300 {
+            return Quaternion::identity();
+        }
+        Quaternion::new(self.w / norm, self.x / norm, self.y / norm, self.z / norm)
+    }
+
+    /// Rotation matrix of the normalized quaternion
+    pub fn to_rotation_matrix(&self) -> Matrix3<f64> {
+        let q = self.normalized();
+        UnitQuaternion::new_unchecked(nalgebra::Quaternion::new(q.w, q.x, q.y, q.z))
+            .to_rotation_matrix()
+            .into_inner()
+    }
+}
+
+/// Coordinate axis of a frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+impl Axis {
+    /// Row/column index of the axis (X = 0, Y = 1, Z = 2)
+    pub fn index(self) -> usize {
+        match self {
+            Axis::X => 0,
+            Axis::Y => 1,
+            Axis::Z => 2,
+        }
+    }
+
+    /// Elementary rotation about this axis
+    pub fn rotation(self, angle: f64) -> Transform3D {
+        match self {
+            Axis::X => Transform3D::rotation_x(angle),
+            Axis::Y => Transform3D::rotation_y(angle),
+            Axis::Z => Transform3D::rotation_z(angle),
+        }
+    }
+}
+
+/// Whether Euler angle rotations are about the moving or the fixed axes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EulerFrame {
+    /// Each rotation is about an axis of the frame produced by the previous ones:
+    /// R = R_a(α) * R_b(β) * R_c(γ)
+    Intrinsic,
+    /// Every rotation is about an axis of the fixed base frame:
+    /// R = R_c(γ) * R_b(β) * R_a(α)
+    Extrinsic,
+}
+
+/// One of the 12 Euler / Tait-Bryan axis sequences, intrinsic or extrinsic
+///
+/// Tait-Bryan sequences use three different axes (e.g. ZYX), proper Euler
+/// sequences repeat the first axis (e.g. ZYZ). Consecutive axes must differ.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EulerSequence {
+    axes: [Axis; 3],
+    frame: EulerFrame,
+}
+
+impl EulerSequence {
+    /// Sequence of rotations about `axes` (first applied first in the chosen frame)
+    pub fn new(axes: [Axis; 3], frame: EulerFrame) -> Result<Self, RoboticsError> {
+        if axes[0] == axes[1] || axes[1] == axes[2] {
+            return Err(RoboticsError::InvalidArgument(format!(
+                "consecutive Euler axes must differ, got {:?}",
+                axes
+            )));
+        }
+        Ok(EulerSequence { axes, frame })
+    }
+
+    /// Parse a sequence such as "ZYX" or "zxz" (case-insensitive)
+    pub fn parse(sequence: &str, frame: EulerFrame) -> Result<Self, RoboticsError> {
+        let axes: Vec<Axis> = sequence
+            .chars()
+            .map(|c| match c.to_ascii_uppercase() {
+                'X' => Ok(Axis::X),
+                'Y' => Ok(Axis::Y),
+                'Z' => Ok(Axis::Z),
+                _ => Err(RoboticsError::Parse(format!("Euler sequence '{}': unknown axis '{}'", sequence, c))),
+            })
+            .collect::<Result<_, _>>()?;
+
+        match axes.as_slice() {
+            &[a, b, c] => EulerSequence::new([a, b, c], frame),
+            _ => Err(RoboticsError::Parse(format!("Euler sequence '{}': expected three axes", sequence))),
+        }
+    }
+
+    /// All 12 axis sequences in the given frame
+    pub fn all(frame: EulerFrame) -> Vec<Self> {
+        let axes = [Axis::X, Axis::Y, Axis::Z];
+        let mut sequences = Vec::with_capacity(12);
+        for a in axes {
+            for b in axes {
+                for c in axes {
+                    if let Ok(sequence) = EulerSequence::new([a, b, c], frame) {
+                        sequences.push(sequence);
+                    }
+                }
+            }
+        }
+        sequences
+    }
+
+    pub fn axes(&self) -> [Axis; 3] {
+        self.axes
+    }
+
+    pub fn frame(&self) -> EulerFrame {
+        self.frame
+    }
+
+    /// Whether the first axis is repeated (proper Euler angles such as ZYZ)
+    pub fn is_proper(&self) -> bool {
+        self.axes[0] == self.axes[2]
+    }
+
+    /// The same rotation as an intrinsic sequence (extrinsic abc = intrinsic cba, angles reversed)
+    fn to_intrinsic(self) -> (Self, bool) {
+        match self.frame {
+            EulerFrame::Intrinsic => (self, false),
+            EulerFrame::Extrinsic => (
+                EulerSequence {
+                    axes: [self.axes[2], self.axes[1], self.axes[0]],
+                    frame: EulerFrame::Intrinsic,
+                },
+                true,
+            ),
+        }
+    }
+}
+
+impl fmt::Display for EulerSequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frame = match self.frame {
+            EulerFrame::Intrinsic => "intrinsic",
+            EulerFrame::Extrinsic => "extrinsic",
+        };
+        write!(f, "{} {:?}{:?}{:?}", frame, self.axes[0], self.axes[1], self.axes[2])
+    }
+}
+
+/// Skew-symmetric matrix [v] such that [v] * w = v × w
+pub fn skew(v: &Vector3<f64>) -> Matrix3<f64> {
+    #[rustfmt::skip]
+    let matrix = Matrix3::new(
+          0.0, -v.z,  v.y,
+          v.z,  0.0, -v.x,
+         -v.y,  v.x,  0.0,
+    );
+    matrix
+}
+
+/// Logarithm of a rotation matrix as a scaled axis (axis * angle)
+///
+/// Uses atan2 on the skew-symmetric part for angles below 90°, where the
+/// acos of the trace loses precision.
+pub fn rotation_log(r: &Matrix3<f64>) -> Vector3<f64> {
+    let cos_angle = ((r.trace() - 1.0) / 2.0).clamp(-1.0, 1.0);
+
+    if cos_angle < 0.0 {
+        return Rotation3::from_matrix_unchecked(*r).scaled_axis();
+    }
+
+    // sin(angle) * axis
+    let skew_part = Vector3::new(r[(2, 1)] - r[(1, 2)], r[(0, 2)] - r[(2, 0)], r[(1, 0)] - r[(0, 1)]) * 0.5;
+    let sin_angle = skew_part.norm();
+    if sin_angle < 1e-15 {
+        return Vector3::zeros();
+    }
+
+    skew_part * (sin_angle.atan2(cos_angle) / sin_angle)
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct Transform3D {
     // 4x4 homogeneous transformation matrix
@@ -31,6 +240,347 @@ impl Transform3D {
         }
     }
 
+    /// Wrap an existing 4x4 homogeneous matrix
+    pub fn from_matrix(matrix: Matrix4<f64>) -> Self {
+        Transform3D { matrix }
+    }
+
+    /// Borrow the underlying 4x4 homogeneous matrix
+    pub fn matrix(&self) -> &Matrix4<f64> {
+        &self.matrix
+    }
+
+    /// Origin of this frame expressed in the parent frame
+    pub fn position(&self) -> Point3D {
+        Point3D {
+            x: self.matrix[(0, 3)],
+            y: self.matrix[(1, 3)],
+            z: self.matrix[(2, 3)],
+        }
+    }
+
+    /// Upper-left 3x3 rotation block
+    pub fn rotation_matrix(&self) -> Matrix3<f64> {
+        self.matrix.fixed_view::<3, 3>(0, 0).into_owned()
+    }
+
+    /// Build a transform from a rotation block and a translation
+    pub fn from_parts(rotation: &Matrix3<f64>, translation: &Vector3<f64>) -> Self {
+        let mut matrix = Matrix4::identity();
+        matrix.fixed_view_mut::<3, 3>(0, 0).copy_from(rotation);
+        matrix.fixed_view_mut::<3, 1>(0, 3).copy_from(translation);
+
+        Transform3D { matrix }
+    }
+
+    /// Build a transform from a row-major 4x4 array (inverse of `to_row_major`)
+    pub fn from_row_major(values: &[f64; 16]) -> Self {
+        Transform3D {
+            matrix: Matrix4::from_row_slice(values),
+        }
+    }
+
+    /// Pure rotation from a quaternion (normalized first; all zeros gives the identity)
+    pub fn from_quaternion(q: &Quaternion) -> Self {
+        Transform3D::from_parts(&q.to_rotation_matrix(), &Vector3::zeros())
+    }
+
+    /// Pure rotation from roll-pitch-yaw: R = Rot(Z, yaw) * Rot(Y, pitch) * Rot(X, roll)
+    ///
+    /// Inverse of `rpy`.
+    pub fn from_rpy(roll: f64, pitch: f64, yaw: f64) -> Self {
+        Transform3D::rotation_z(yaw)
+            .compose(&Transform3D::rotation_y(pitch))
+            .compose(&Transform3D::rotation_x(roll))
+    }
+
+    /// Translation followed by a roll-pitch-yaw rotation, as in URDF `<origin xyz rpy>`
+    pub fn from_xyz_rpy(xyz: [f64; 3], rpy: [f64; 3]) -> Self {
+        Transform3D::translation(xyz[0], xyz[1], xyz[2]).compose(&Transform3D::from_rpy(rpy[0], rpy[1], rpy[2]))
+    }
+
+    /// Pure rotation from Euler angles (α, β, γ) applied about the sequence's axes
+    ///
+    /// Intrinsic: R = R_a(α) * R_b(β) * R_c(γ), extrinsic: R = R_c(γ) * R_b(β) * R_a(α).
+    pub fn from_euler(angles: [f64; 3], sequence: EulerSequence) -> Self {
+        let [a, b, c] = sequence.axes;
+        let (first, second, third) = (a.rotation(angles[0]), b.rotation(angles[1]), c.rotation(angles[2]));
+
+        match sequence.frame {
+            EulerFrame::Intrinsic => first.compose(&second).compose(&third),
+            EulerFrame::Extrinsic => third.compose(&second).compose(&first),
+        }
+    }
+
+    /// Pure rotation by `angle` about `axis` through the origin (identity for a zero axis)
+    pub fn from_axis_angle(axis: &Vector3<f64>, angle: f64) -> Self {
+        match axis.try_normalize(1e-300) {
+            Some(axis) => Transform3D::exp(&Vector6::new(axis.x * angle, axis.y * angle, axis.z * angle, 0.0, 0.0, 0.0)),
+            None => Transform3D::identity(),
+        }
+    }
+
+    /// Rotation by `angle` about the line through `point` along `direction`
+    ///
+    /// Points on the line stay fixed (identity for a zero direction).
+    pub fn rotation_about_axis(point: &Point3D, direction: &Vector3<f64>, angle: f64) -> Self {
+        let q = Vector3::new(point.x, point.y, point.z);
+        Transform3D::translation(q.x, q.y, q.z)
+            .compose(&Transform3D::from_axis_angle(direction, angle))
+            .compose(&Transform3D::translation(-q.x, -q.y, -q.z))
+    }
+
+    /// Rotation block as a typed rotation
+    pub fn rotation(&self) -> Rotation3<f64> {
+        Rotation3::from_matrix_unchecked(self.rotation_matrix())
+    }
+
+    /// Translation column as a vector (same values as `position`)
+    pub fn translation_vector(&self) -> Vector3<f64> {
+        self.matrix.fixed_view::<3, 1>(0, 3).into_owned()
+    }
+
+    /// Inverse of a rigid transform: [Rᵀ, -Rᵀ p]
+    pub fn inverse(&self) -> Self {
+        let rotation_t = self.rotation_matrix().transpose();
+        let translation = self.matrix.fixed_view::<3, 1>(0, 3).into_owned();
+
+        Transform3D::from_parts(&rotation_t, &(-rotation_t * translation))
+    }
+
+    /// 6x6 adjoint [Ad_T] = [R 0; [p]R R], acting on twists ordered (ω, v)
+    pub fn adjoint(&self) -> Matrix6<f64> {
+        let rotation = self.rotation_matrix();
+        let translation = self.matrix.fixed_view::<3, 1>(0, 3).into_owned();
+
+        let mut adjoint = Matrix6::zeros();
+        adjoint.fixed_view_mut::<3, 3>(0, 0).copy_from(&rotation);
+        adjoint.fixed_view_mut::<3, 3>(3, 3).copy_from(&rotation);
+        adjoint
+            .fixed_view_mut::<3, 3>(3, 0)
+            .copy_from(&(skew(&translation) * rotation));
+        adjoint
+    }
+
+    /// SE(3) exponential of a twist ordered (ω, v), already scaled by the joint value
+    ///
+    /// For θ = |ω| > 0 with ω̂ = ω/θ, v̂ = v/θ:
+    /// R = I + sin θ [ω̂] + (1 - cos θ) [ω̂]², p = (I θ + (1 - cos θ) [ω̂] + (θ - sin θ) [ω̂]²) v̂.
+    /// A pure translation (ω = 0) gives R = I, p = v.
+    pub fn exp(twist: &Vector6<f64>) -> Self {
+        let omega = Vector3::new(twist[0], twist[1], twist[2]);
+        let v = Vector3::new(twist[3], twist[4], twist[5]);
+        let theta = omega.norm();
+
+        if theta < 1e-12 {
+            return Transform3D::from_parts(&Matrix3::identity(), &v);
+        }
+
+        let omega_hat = skew(&(omega / theta));
+        let omega_hat_sq = omega_hat * omega_hat;
+        let rotation = Matrix3::identity() + omega_hat * theta.sin() + omega_hat_sq * (1.0 - theta.cos());
+        let g = Matrix3::identity() * theta + omega_hat * (1.0 - theta.cos()) + omega_hat_sq * (theta - theta.sin());
+
+        Transform3D::from_parts(&rotation, &(g * (v / theta)))
+    }
+
+    /// SE(3) logarithm: the twist (ω, v) * θ with exp(log(T)) = T
+    pub fn log(&self) -> Vector6<f64> {
+        let omega = rotation_log(&self.rotation_matrix());
+        let p = self.matrix.fixed_view::<3, 1>(0, 3).into_owned();
+        let theta = omega.norm();
+
+        if theta < 1e-12 {
+            return Vector6::new(0.0, 0.0, 0.0, p.x, p.y, p.z);
+        }
+
+        let omega_hat = skew(&(omega / theta));
+        let g_inverse = Matrix3::identity() / theta - omega_hat * 0.5
+            + omega_hat * omega_hat * (1.0 / theta - 0.5 / (theta / 2.0).tan());
+        let v = g_inverse * p * theta;
+
+        Vector6::new(omega.x, omega.y, omega.z, v.x, v.y, v.z)
+    }
+
+    /// Rotation block as a unit quaternion (scalar part w ≥ 0)
+    pub fn quaternion(&self) -> Quaternion {
+        let rotation = Rotation3::from_matrix_unchecked(self.rotation_matrix());
+        let q = UnitQuaternion::from_rotation_matrix(&rotation);
+        let sign = if q.w < 0.0 { -1.0 } else { 1.0 };
+
+        Quaternion::new(sign * q.w, sign * q.i, sign * q.j, sign * q.k)
+    }
+
+    /// Rotation block as a unit axis and an angle in [0, π]
+    ///
+    /// The identity rotation returns the Z axis with angle 0.
+    pub fn axis_angle(&self) -> (Vector3<f64>, f64) {
+        let scaled_axis = rotation_log(&self.rotation_matrix());
+        let angle = scaled_axis.norm();
+
+        match scaled_axis.try_normalize(1e-15) {
+            Some(axis) => (axis, angle),
+            None => (Vector3::z(), 0.0),
+        }
+    }
+
+    /// Euler angles (α, β, γ) of the rotation block for any axis sequence
+    ///
+    /// Inverse of `from_euler`. The middle angle lies in [-π/2, π/2] for
+    /// Tait-Bryan sequences and in [0, π] for proper Euler sequences. At a gimbal
+    /// lock only α + γ (or α - γ) is defined; the first intrinsic angle is then set to 0.
+    pub fn euler_angles(&self, sequence: EulerSequence) -> [f64; 3] {
+        let (intrinsic, reversed) = sequence.to_intrinsic();
+        let r = self.rotation_matrix();
+
+        let [a, b, c] = intrinsic.axes;
+        let (i, j) = (a.index(), b.index());
+        let k = 3 - i - j;
+        // +1 when (i, j, k) is a cyclic permutation of (X, Y, Z)
+        let parity = if (j + 3 - i) % 3 == 1 { 1.0 } else { -1.0 };
+
+        let angles = if c != a {
+            // Tait-Bryan: R[i][k] = ±sin β
+            let sin_beta = (parity * r[(i, k)]).clamp(-1.0, 1.0);
+            let cos_beta = (r[(i, i)] * r[(i, i)] + r[(i, j)] * r[(i, j)]).sqrt();
+            let beta = sin_beta.atan2(cos_beta);
+
+            if cos_beta < 1e-10 {
+                [0.0, beta, (parity * r[(j, i)]).atan2(r[(j, j)])]
+            } else {
+                [
+                    (-parity * r[(j, k)]).atan2(r[(k, k)]),
+                    beta,
+                    (-parity * r[(i, j)]).atan2(r[(i, i)]),
+                ]
+            }
+        } else {
+            // Proper Euler: R[i][i] = cos β
+            let sin_beta = (r[(i, j)] * r[(i, j)] + r[(i, k)] * r[(i, k)]).sqrt();
+            let beta = sin_beta.atan2(r[(i, i)]);
+
+            if sin_beta < 1e-10 {
+                [0.0, beta, (-parity * r[(j, k)]).atan2(r[(j, j)])]
+            } else {
+                [
+                    r[(j, i)].atan2(-parity * r[(k, i)]),
+                    beta,
+                    r[(i, j)].atan2(parity * r[(i, k)]),
+                ]
+            }
+        };
+
+        if reversed {
+            [angles[2], angles[1], angles[0]]
+        } else {
+            angles
+        }
+    }
+
+    /// Screw-linear interpolation (ScLERP): T(t) = T0 * exp(t * log(T0⁻¹ T1))
+    ///
+    /// The pose moves along the constant screw joining the two frames, so a
+    /// point fixed to the frame follows a helix. `t` = 0 gives `self`, 1 gives `other`.
+    pub fn sclerp(&self, other: &Transform3D, t: f64) -> Self {
+        let twist = self.inverse().compose(other).log();
+        self.compose(&Transform3D::exp(&(twist * t)))
+    }
+
+    /// Decoupled interpolation: position LERP and rotation SLERP along the shortest arc
+    ///
+    /// The origin moves on the straight line between the two positions.
+    pub fn interpolate(&self, other: &Transform3D, t: f64) -> Self {
+        let start_rotation = self.rotation_matrix();
+        let relative = rotation_log(&(start_rotation.transpose() * other.rotation_matrix()));
+        let rotation = start_rotation * Transform3D::from_axis_angle(&relative, relative.norm() * t).rotation_matrix();
+        let position = self.translation_vector().lerp(&other.translation_vector(), t);
+
+        Transform3D::from_parts(&rotation, &position)
+    }
+
+    /// Geodesic distance between the two rotation blocks: the angle of R0ᵀ R1 in [0, π]
+    pub fn rotation_distance(&self, other: &Transform3D) -> f64 {
+        rotation_log(&(self.rotation_matrix().transpose() * other.rotation_matrix())).norm()
+    }
+
+    /// Euclidean distance between the two origins
+    pub fn translation_distance(&self, other: &Transform3D) -> f64 {
+        (other.translation_vector() - self.translation_vector()).norm()
+    }
+
+    /// Error twist taking this pose to `target`, ordered (linear, angular) like Jacobian rows
+    ///
+    /// Linear part: p_target - p, angular part: log(R_target Rᵀ) as a scaled axis,
+    /// both in the world frame. Zero exactly when the poses coincide.
+    pub fn pose_error(&self, target: &Transform3D) -> Vector6<f64> {
+        let linear = target.translation_vector() - self.translation_vector();
+        let angular = rotation_log(&(target.rotation_matrix() * self.rotation_matrix().transpose()));
+
+        Vector6::new(linear.x, linear.y, linear.z, angular.x, angular.y, angular.z)
+    }
+
+    /// `pose_error` with the linear part scaled by `linear_weight` and the angular part by `angular_weight`
+    ///
+    /// The norm of the result is a single pose distance; the angular weight acts
+    /// as a length converting radians into meters.
+    pub fn weighted_pose_error(&self, target: &Transform3D, linear_weight: f64, angular_weight: f64) -> Vector6<f64> {
+        let mut error = self.pose_error(target);
+        for i in 0..3 {
+            error[i] *= linear_weight;
+            error[i + 3] *= angular_weight;
+        }
+        error
+    }
+
+    /// Row-major copy of the 4x4 homogeneous matrix
+    pub fn to_row_major(&self) -> [f64; 16] {
+        let mut values = [0.0; 16];
+        for row in 0..4 {
+            for col in 0..4 {
+                values[row * 4 + col] = self.matrix[(row, col)];
+            }
+        }
+        values
+    }
+
+    /// Roll-pitch-yaw angles (roll, pitch, yaw) of the rotation block
+    ///
+    /// Convention: R = Rot(Z, yaw) * Rot(Y, pitch) * Rot(X, roll), pitch in [-π/2, π/2].
+    /// At the gimbal lock (pitch = ±π/2) yaw is set to 0.
+    pub fn rpy(&self) -> (f64, f64, f64) {
+        let r = self.rotation_matrix();
+        let cos_pitch = (r[(0, 0)] * r[(0, 0)] + r[(1, 0)] * r[(1, 0)]).sqrt();
+        let pitch = (-r[(2, 0)]).atan2(cos_pitch);
+
+        if cos_pitch < 1e-10 {
+            let roll = (-r[(2, 0)] * r[(0, 1)]).atan2(r[(1, 1)]);
+            (roll, pitch, 0.0)
+        } else {
+            let roll = r[(2, 1)].atan2(r[(2, 2)]);
+            let yaw = r[(1, 0)].atan2(r[(0, 0)]);
+            (roll, pitch, yaw)
+        }
+    }
+
+    /// ZYZ Euler angles (φ, θ, ψ) of the rotation block
+    ///
+    /// Convention: R = Rot(Z, φ) * Rot(Y, θ) * Rot(Z, ψ), θ in [0, π].
+    /// When θ = 0 or π, φ is set to 0.
+    pub fn euler_zyz(&self) -> (f64, f64, f64) {
+        let r = self.rotation_matrix();
+        let sin_theta = (r[(0, 2)] * r[(0, 2)] + r[(1, 2)] * r[(1, 2)]).sqrt();
+        let theta = sin_theta.atan2(r[(2, 2)]);
+
+        if sin_theta < 1e-10 {
+            let psi = r[(1, 0)].atan2(r[(1, 1)]);
+            (0.0, theta, psi)
+        } else {
+            let phi = r[(1, 2)].atan2(r[(0, 2)]);
+            let psi = r[(2, 1)].atan2(-r[(2, 0)]);
+            (phi, theta, psi)
+        }
+    }
+
     pub fn rotation_x(angle: f64) -> Self {
         let cos_a = angle.cos();
         let sin_a = angle.sin();
@@ -193,6 +743,345 @@ mod tests {
         assert!(point_approx_eq(&transformed, &Point3D::new(2.0, 1.0, 0.0)));
     }
 
+    #[test]
+    fn test_position_and_rotation_accessors() {
+        let transform = Transform3D::translation(1.0, 2.0, 3.0).compose(&Transform3D::rotation_z(PI / 2.0));
+
+        let position = transform.position();
+        assert!(point_approx_eq(&position, &Point3D::new(1.0, 2.0, 3.0)));
+
+        let rotation = transform.rotation_matrix();
+        assert!(approx_eq(rotation[(0, 0)], 0.0));
+        assert!(approx_eq(rotation[(1, 0)], 1.0));
+        assert!(approx_eq(rotation[(0, 1)], -1.0));
+        assert!(approx_eq(rotation[(2, 2)], 1.0));
+    }
+
+    #[test]
+    fn test_rpy_extraction() {
+        let (roll, pitch, yaw) = (0.3, -0.4, 1.2);
+        let transform = Transform3D::rotation_z(yaw)
+            .compose(&Transform3D::rotation_y(pitch))
+            .compose(&Transform3D::rotation_x(roll));
+
+        let (r, p, y) = transform.rpy();
+        assert!(approx_eq(r, roll));
+        assert!(approx_eq(p, pitch));
+        assert!(approx_eq(y, yaw));
+    }
+
+    #[test]
+    fn test_rpy_gimbal_lock() {
+        let transform = Transform3D::rotation_y(PI / 2.0).compose(&Transform3D::rotation_x(0.7));
+
+        let (r, p, y) = transform.rpy();
+        assert!(approx_eq(r, 0.7));
+        assert!(approx_eq(p, PI / 2.0));
+        assert!(approx_eq(y, 0.0));
+    }
+
+    #[test]
+    fn test_euler_zyz_extraction() {
+        let (phi, theta, psi) = (0.5, 1.1, -2.0);
+        let transform = Transform3D::rotation_z(phi)
+            .compose(&Transform3D::rotation_y(theta))
+            .compose(&Transform3D::rotation_z(psi));
+
+        let (a, b, c) = transform.euler_zyz();
+        assert!(approx_eq(a, phi));
+        assert!(approx_eq(b, theta));
+        assert!(approx_eq(c, psi));
+    }
+
+    #[test]
+    fn test_quaternion_extraction() {
+        let transform = Transform3D::rotation_z(PI / 2.0);
+        let q = transform.quaternion();
+
+        // 90° about Z: (cos 45°, 0, 0, sin 45°)
+        let half = (PI / 4.0).cos();
+        assert!(approx_eq(q.w, half));
+        assert!(approx_eq(q.x, 0.0));
+        assert!(approx_eq(q.y, 0.0));
+        assert!(approx_eq(q.z, half));
+
+        let identity = Transform3D::identity().quaternion();
+        assert!(approx_eq(identity.w, 1.0));
+    }
+
+    #[test]
+    fn test_row_major_layout() {
+        let values = Transform3D::translation(1.0, 2.0, 3.0).to_row_major();
+        assert_eq!(values[3], 1.0);
+        assert_eq!(values[7], 2.0);
+        assert_eq!(values[11], 3.0);
+        assert_eq!(values[15], 1.0);
+        assert_eq!(values[12], 0.0);
+    }
+
+    fn transform_approx_eq(a: &Transform3D, b: &Transform3D) -> bool {
+        (a.matrix() - b.matrix()).abs().max() < 1e-9
+    }
+
+    fn sample_transform() -> Transform3D {
+        Transform3D::translation(0.4, -1.2, 2.0)
+            .compose(&Transform3D::rotation_z(0.7))
+            .compose(&Transform3D::rotation_y(-1.1))
+            .compose(&Transform3D::rotation_x(2.4))
+    }
+
+    #[test]
+    fn test_inverse() {
+        let transform = sample_transform();
+        let product = transform.compose(&transform.inverse());
+
+        assert!(transform_approx_eq(&product, &Transform3D::identity()));
+    }
+
+    #[test]
+    fn test_exp_pure_rotation_and_translation() {
+        let rotation = Transform3D::exp(&Vector6::new(0.0, 0.0, PI / 2.0, 0.0, 0.0, 0.0));
+        assert!(transform_approx_eq(&rotation, &Transform3D::rotation_z(PI / 2.0)));
+
+        let translation = Transform3D::exp(&Vector6::new(0.0, 0.0, 0.0, 1.0, 2.0, 3.0));
+        assert!(transform_approx_eq(&translation, &Transform3D::translation(1.0, 2.0, 3.0)));
+    }
+
+    #[test]
+    fn test_exp_rotation_about_offset_axis() {
+        // Rotation of 90° about the Z axis through (1, 0, 0): v = -ω × q
+        let q = Vector3::new(1.0, 0.0, 0.0);
+        let omega = Vector3::new(0.0, 0.0, 1.0);
+        let v = -omega.cross(&q);
+        let twist = Vector6::new(omega.x, omega.y, omega.z, v.x, v.y, v.z) * (PI / 2.0);
+
+        let transform = Transform3D::exp(&twist);
+        let moved = transform.transform_point(&Point3D::origin());
+        assert!(point_approx_eq(&moved, &Point3D::new(1.0, -1.0, 0.0)));
+    }
+
+    #[test]
+    fn test_log_exp_round_trip() {
+        let transform = sample_transform();
+        let round_trip = Transform3D::exp(&transform.log());
+
+        assert!(transform_approx_eq(&round_trip, &transform));
+    }
+
+    #[test]
+    fn test_adjoint_maps_twists() {
+        // exp(Ad_T V) = T exp(V) T⁻¹
+        let transform = sample_transform();
+        let twist = Vector6::new(0.1, -0.3, 0.2, 0.5, 0.4, -0.6);
+
+        let lhs = Transform3D::exp(&(transform.adjoint() * twist));
+        let rhs = transform.compose(&Transform3D::exp(&twist)).compose(&transform.inverse());
+        assert!(transform_approx_eq(&lhs, &rhs));
+    }
+
+    #[test]
+    fn test_rotation_log_small_angle_precision() {
+        let angle = 1e-7;
+        let log = rotation_log(&Transform3D::rotation_x(angle).rotation_matrix());
+        assert!(((log.x - angle) / angle).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_quaternion_round_trip() {
+        let transform = sample_transform();
+        let rotation_only = Transform3D::from_quaternion(&transform.quaternion());
+
+        assert!((rotation_only.rotation_matrix() - transform.rotation_matrix()).abs().max() < 1e-12);
+        assert!(point_approx_eq(&rotation_only.position(), &Point3D::origin()));
+
+        // Non-unit input is normalized, all zeros is the identity
+        let scaled = Transform3D::from_quaternion(&Quaternion::new(0.0, 0.0, 0.0, 2.0));
+        assert!(transform_approx_eq(&scaled, &Transform3D::rotation_z(PI)));
+        assert!(transform_approx_eq(&Transform3D::from_quaternion(&Quaternion::new(0.0, 0.0, 0.0, 0.0)), &Transform3D::identity()));
+    }
+
+    #[test]
+    fn test_euler_round_trip_all_sequences() {
+        let angles = [0.7, -0.4, 2.1];
+        let proper_angles = [0.7, 1.1, -2.1];
+
+        for frame in [EulerFrame::Intrinsic, EulerFrame::Extrinsic] {
+            let sequences = EulerSequence::all(frame);
+            assert_eq!(sequences.len(), 12);
+
+            for sequence in sequences {
+                let input = if sequence.is_proper() { proper_angles } else { angles };
+                let transform = Transform3D::from_euler(input, sequence);
+                let output = transform.euler_angles(sequence);
+
+                for (a, b) in input.iter().zip(output.iter()) {
+                    assert!(approx_eq(*a, *b), "{}: {:?} vs {:?}", sequence, input, output);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_euler_gimbal_lock_reconstructs_rotation() {
+        for frame in [EulerFrame::Intrinsic, EulerFrame::Extrinsic] {
+            for sequence in EulerSequence::all(frame) {
+                let middle = if sequence.is_proper() { PI } else { -PI / 2.0 };
+                let transform = Transform3D::from_euler([0.4, middle, -0.9], sequence);
+
+                let angles = transform.euler_angles(sequence);
+                assert!(approx_eq(angles[1], middle) || approx_eq(angles[1].abs(), PI), "{}", sequence);
+                let rebuilt = Transform3D::from_euler(angles, sequence);
+                assert!(transform_approx_eq(&rebuilt, &transform), "{}", sequence);
+            }
+        }
+    }
+
+    #[test]
+    fn test_euler_matches_existing_conventions() {
+        let transform = sample_transform();
+
+        // RPY is intrinsic ZYX (yaw, pitch, roll)
+        let zyx = EulerSequence::parse("ZYX", EulerFrame::Intrinsic).unwrap();
+        let (roll, pitch, yaw) = transform.rpy();
+        let [a, b, c] = transform.euler_angles(zyx);
+        assert!(approx_eq(a, yaw) && approx_eq(b, pitch) && approx_eq(c, roll));
+        assert!(transform_approx_eq(
+            &Transform3D::from_rpy(roll, pitch, yaw),
+            &Transform3D::from_parts(&transform.rotation_matrix(), &Vector3::zeros())
+        ));
+
+        // ... which is the same rotation as extrinsic XYZ with the angles reversed
+        let xyz = EulerSequence::parse("xyz", EulerFrame::Extrinsic).unwrap();
+        let [r, p, y] = transform.euler_angles(xyz);
+        assert!(approx_eq(r, roll) && approx_eq(p, pitch) && approx_eq(y, yaw));
+
+        let zyz = EulerSequence::new([Axis::Z, Axis::Y, Axis::Z], EulerFrame::Intrinsic).unwrap();
+        let (phi, theta, psi) = transform.euler_zyz();
+        let [a, b, c] = transform.euler_angles(zyz);
+        assert!(approx_eq(a, phi) && approx_eq(b, theta) && approx_eq(c, psi));
+    }
+
+    #[test]
+    fn test_invalid_euler_sequences() {
+        assert!(EulerSequence::new([Axis::X, Axis::X, Axis::Y], EulerFrame::Intrinsic).is_err());
+        assert_eq!(EulerSequence::parse("XYW", EulerFrame::Intrinsic).unwrap_err().code(), "PARSE_ERROR");
+        assert_eq!(EulerSequence::parse("XY", EulerFrame::Intrinsic).unwrap_err().code(), "PARSE_ERROR");
+        assert_eq!(EulerSequence::parse("ZZY", EulerFrame::Extrinsic).unwrap_err().code(), "INVALID_ARGUMENT");
+    }
+
+    #[test]
+    fn test_axis_angle_round_trip() {
+        let axis = Vector3::new(1.0, -2.0, 0.5).normalize();
+        let transform = Transform3D::from_axis_angle(&(axis * 3.0), 2.5);
+
+        let (extracted_axis, angle) = transform.axis_angle();
+        assert!(approx_eq(angle, 2.5));
+        assert!((extracted_axis - axis).norm() < EPSILON);
+
+        let (_, zero) = Transform3D::identity().axis_angle();
+        assert_eq!(zero, 0.0);
+        assert!(transform_approx_eq(&Transform3D::from_axis_angle(&Vector3::zeros(), 1.0), &Transform3D::identity()));
+    }
+
+    #[test]
+    fn test_rotation_about_arbitrary_axis() {
+        let point = Point3D::new(1.0, 2.0, 0.0);
+        let transform = Transform3D::rotation_about_axis(&point, &Vector3::new(0.0, 0.0, 2.0), PI / 2.0);
+
+        // Points on the axis stay fixed, others rotate about it
+        assert!(point_approx_eq(&transform.transform_point(&Point3D::new(1.0, 2.0, 5.0)), &Point3D::new(1.0, 2.0, 5.0)));
+        assert!(point_approx_eq(&transform.transform_point(&Point3D::new(2.0, 2.0, 0.0)), &Point3D::new(1.0, 3.0, 0.0)));
+    }
+
+    #[test]
+    fn test_rotation_and_translation_accessors() {
+        let transform = sample_transform();
+
+        assert!((transform.rotation().matrix() - transform.rotation_matrix()).abs().max() < 1e-15);
+        assert_eq!(transform.translation_vector(), Vector3::new(0.4, -1.2, 2.0));
+        let rebuilt = Transform3D::from_parts(transform.rotation().matrix(), &transform.translation_vector());
+        assert!(transform_approx_eq(&rebuilt, &transform));
+        assert!(transform_approx_eq(&transform.inverse().inverse(), &transform));
+    }
+
+    #[test]
+    fn test_interpolation_endpoints() {
+        let start = sample_transform();
+        let end = Transform3D::translation(-1.0, 0.5, 0.3).compose(&Transform3D::from_rpy(0.2, 0.9, -2.5));
+
+        for blend in [Transform3D::sclerp, Transform3D::interpolate] {
+            assert!(transform_approx_eq(&blend(&start, &end, 0.0), &start));
+            assert!(transform_approx_eq(&blend(&start, &end, 1.0), &end));
+        }
+    }
+
+    #[test]
+    fn test_sclerp_follows_screw_motion() {
+        // Quarter turn about Z combined with a rise along Z: a helix around the Z axis
+        let start = Transform3D::translation(1.0, 0.0, 0.0);
+        let end = Transform3D::translation(0.0, 1.0, 2.0).compose(&Transform3D::rotation_z(PI / 2.0));
+        let middle = start.sclerp(&end, 0.5);
+
+        let expected = Transform3D::translation((PI / 4.0).cos(), (PI / 4.0).sin(), 1.0)
+            .compose(&Transform3D::rotation_z(PI / 4.0));
+        assert!(transform_approx_eq(&middle, &expected));
+    }
+
+    #[test]
+    fn test_decoupled_interpolation_moves_in_a_straight_line() {
+        let start = Transform3D::translation(1.0, 0.0, 0.0);
+        let end = Transform3D::translation(0.0, 1.0, 2.0).compose(&Transform3D::rotation_z(PI / 2.0));
+
+        for t in [0.25, 0.5, 0.8] {
+            let pose = start.interpolate(&end, t);
+            assert!(point_approx_eq(&pose.position(), &Point3D::new(1.0 - t, t, 2.0 * t)));
+            assert!(transform_approx_eq(
+                &Transform3D::from_parts(&pose.rotation_matrix(), &Vector3::zeros()),
+                &Transform3D::rotation_z(t * PI / 2.0),
+            ));
+        }
+    }
+
+    #[test]
+    fn test_rotation_and_translation_distances() {
+        let start = sample_transform();
+        let offset = Transform3D::translation(3.0, 0.0, 4.0).compose(&Transform3D::from_axis_angle(&Vector3::new(1.0, 1.0, 0.0), 2.5));
+        let end = start.compose(&offset);
+
+        assert!(approx_eq(start.rotation_distance(&end), 2.5));
+        assert!(approx_eq(end.rotation_distance(&start), 2.5));
+        assert!(approx_eq(start.translation_distance(&end), 5.0));
+        assert!(approx_eq(start.rotation_distance(&start), 0.0));
+
+        // The geodesic distance never exceeds π
+        let flipped = start.compose(&Transform3D::rotation_x(1.5 * PI));
+        assert!(approx_eq(start.rotation_distance(&flipped), 0.5 * PI));
+    }
+
+    #[test]
+    fn test_pose_error_twist() {
+        let current = sample_transform();
+        assert!(current.pose_error(&current).norm() < 1e-12);
+
+        // A world-frame rotation about Y plus a shift shows up in the matching components
+        let target = Transform3D::translation(0.1, 0.2, -0.3)
+            .compose(&Transform3D::rotation_y(0.4))
+            .compose(&Transform3D::translation(-0.4, 1.2, -2.0))
+            .compose(&current);
+        let error = current.pose_error(&target);
+        let expected_linear = target.translation_vector() - current.translation_vector();
+        for i in 0..3 {
+            assert!(approx_eq(error[i], expected_linear[i]));
+        }
+        assert!(approx_eq(error[3], 0.0) && approx_eq(error[4], 0.4) && approx_eq(error[5], 0.0));
+
+        let weighted = current.weighted_pose_error(&target, 2.0, 0.5);
+        for i in 0..3 {
+            assert!(approx_eq(weighted[i], 2.0 * error[i]));
+            assert!(approx_eq(weighted[i + 3], 0.5 * error[i + 3]));
+        }
+    }
+
     #[test]
     fn test_identity_composition() {
         let identity = Transform3D::identity();
@@ -0,0 +1,279 @@
+use crate::geometry3d::{Point3D, Transform3D};
+use crate::jacobian::jacobian;
+use crate::kinematics::end_effector_transform;
+use crate::robot::RobotArm;
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+
+/// Target for the inverse kinematics solver
+#[derive(Debug, Clone, Copy)]
+pub enum IkTarget {
+    /// Reach a point with the end-effector, orientation is left free
+    Position(Point3D),
+    /// Reach a full end-effector pose (position + orientation)
+    Pose(Transform3D),
+}
+
+/// Tuning parameters for the damped least squares solver
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct IkOptions {
+    /// Maximum number of solver iterations
+    pub max_iterations: usize,
+    /// Residual error norm below which the solver reports convergence
+    pub tolerance: f64,
+    /// Damping factor λ (larger = more robust near singularities, slower convergence)
+    pub damping: f64,
+    /// Maximum norm of a single joint update (limits jumps far from the solution)
+    pub max_step: f64,
+}
+
+impl Default for IkOptions {
+    fn default() -> Self {
+        IkOptions {
+            max_iterations: 200,
+            tolerance: 1e-6,
+            damping: 0.05,
+            max_step: 0.5,
+        }
+    }
+}
+
+/// Result of an inverse kinematics solve
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IkSolution {
+    /// Best joint values found (angles for revolute, distances for prismatic)
+    pub joint_values: Vec<f64>,
+    /// Whether the residual error dropped below the tolerance
+    pub converged: bool,
+    /// Number of iterations performed
+    pub iterations: usize,
+    /// Residual error norm of `joint_values`
+    pub error: f64,
+}
+
+/// Solves inverse kinematics with the damped least squares (Levenberg-Marquardt) method
+///
+/// Starting from the robot's current joint values, each iteration computes
+/// Δq = Jᵀ (J Jᵀ + λ² I)⁻¹ e, where e is the pose error and J the geometric
+/// Jacobian (only its linear rows for position targets). Every iterate is
+/// clamped into the robot's joint limits. The robot itself is not modified; the
+/// best configuration found is returned even if the target could not be reached.
+pub fn solve_ik(robot: &RobotArm, target: &IkTarget, options: &IkOptions) -> IkSolution {
+    let mut arm = robot.clone();
+    let mut joint_values = robot.joint_angles.clone();
+
+    let mut best = IkSolution {
+        joint_values: joint_values.clone(),
+        converged: false,
+        iterations: 0,
+        error: f64::INFINITY,
+    };
+
+    for iteration in 0..=options.max_iterations {
+        arm.joint_angles.clone_from(&joint_values);
+        let error = pose_error(&end_effector_transform(&arm), target);
+        let error_norm = error.norm();
+
+        if error_norm < best.error {
+            best.joint_values = joint_values.clone();
+            best.error = error_norm;
+        }
+        best.iterations = iteration;
+
+        if error_norm < options.tolerance {
+            best.converged = true;
+            break;
+        }
+        if iteration == options.max_iterations {
+            break;
+        }
+
+        let jacobian = match target {
+            IkTarget::Position(_) => jacobian(&arm).rows(0, 3).into_owned(),
+            IkTarget::Pose(_) => jacobian(&arm),
+        };
+        let mut step = damped_least_squares(&jacobian, &error, options.damping);
+
+        // Keep each update small so the linearization stays valid
+        let step_norm = step.norm();
+        if step_norm > options.max_step {
+            step *= options.max_step / step_norm;
+        }
+
+        // Project the update back into the joint limits
+        for ((value, delta), limits) in joint_values.iter_mut().zip(step.iter()).zip(robot.limits.iter()) {
+            *value = limits.clamp(*value + delta);
+        }
+    }
+
+    best
+}
+
+/// Error between the current end-effector frame and the target
+///
+/// Position targets give a 3-vector, pose targets a 6-vector whose last three
+/// components are the rotation error as a scaled axis (world frame).
+fn pose_error(current: &Transform3D, target: &IkTarget) -> DVector<f64> {
+    let position = current.position();
+
+    match target {
+        IkTarget::Position(goal) => DVector::from_vec(vec![
+            goal.x - position.x,
+            goal.y - position.y,
+            goal.z - position.z,
+        ]),
+        IkTarget::Pose(goal) => DVector::from_column_slice(current.pose_error(goal).as_slice()),
+    }
+}
+
+/// Damped least squares step: Jᵀ (J Jᵀ + λ² I)⁻¹ e
+fn damped_least_squares(jacobian: &DMatrix<f64>, error: &DVector<f64>, damping: f64) -> DVector<f64> {
+    let rows = jacobian.nrows();
+    let damped = jacobian * jacobian.transpose() + DMatrix::identity(rows, rows) * (damping * damping);
+
+    match damped.lu().solve(error) {
+        Some(weights) => jacobian.transpose() * weights,
+        None => DVector::zeros(jacobian.ncols()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dh_parameters::DHParameter;
+    use crate::limits::JointLimits;
+    use std::f64::consts::PI;
+
+    const EPSILON: f64 = 1e-5;
+
+    #[test]
+    fn test_ik_planar_position_target() {
+        let mut robot = RobotArm::planar(vec![2.0, 1.5]);
+        robot.set_joint_angles(vec![0.2, 0.3]).unwrap();
+
+        let target = IkTarget::Position(Point3D::new(1.0, 2.0, 0.0));
+        let solution = solve_ik(&robot, &target, &IkOptions::default());
+
+        assert!(solution.converged);
+        assert!(solution.error < 1e-6);
+
+        robot.set_joint_angles(solution.joint_values).unwrap();
+        let reached = end_effector_transform(&robot).position();
+        assert!((reached.x - 1.0).abs() < EPSILON);
+        assert!((reached.y - 2.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_ik_simple_robot_position_target() {
+        let mut robot = RobotArm::new(vec![1.0, 1.0, 1.0]);
+        robot.set_joint_angles(vec![0.1, 0.1, 0.1]).unwrap();
+
+        let target = IkTarget::Position(Point3D::new(0.5, 1.5, 0.0));
+        let solution = solve_ik(&robot, &target, &IkOptions::default());
+
+        assert!(solution.converged);
+    }
+
+    #[test]
+    fn test_ik_3d_robot_reaches_known_pose() {
+        let dh_params = vec![
+            DHParameter::revolute(0.0, PI / 2.0, 0.5, 0.0),
+            DHParameter::revolute(1.0, 0.0, 0.0, 0.0),
+            DHParameter::revolute(0.8, 0.0, 0.0, 0.0),
+        ];
+
+        let mut reference = RobotArm::from_dh_params(dh_params.clone());
+        reference.set_joint_angles(vec![0.4, 0.6, -0.9]).unwrap();
+        let goal = end_effector_transform(&reference).position();
+
+        let mut robot = RobotArm::from_dh_params(dh_params);
+        robot.set_joint_angles(vec![0.0, 0.3, -0.3]).unwrap();
+
+        let solution = solve_ik(&robot, &IkTarget::Position(goal), &IkOptions::default());
+
+        assert!(solution.converged);
+        robot.set_joint_angles(solution.joint_values).unwrap();
+        let reached = end_effector_transform(&robot).position();
+        assert!((reached.x - goal.x).abs() < EPSILON);
+        assert!((reached.y - goal.y).abs() < EPSILON);
+        assert!((reached.z - goal.z).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_ik_full_pose_target() {
+        let mut reference = RobotArm::planar(vec![1.0, 1.0, 0.5]);
+        reference.set_joint_angles(vec![0.5, 0.8, -0.4]).unwrap();
+        let goal = end_effector_transform(&reference);
+
+        let robot = RobotArm::planar(vec![1.0, 1.0, 0.5]);
+        let solution = solve_ik(&robot, &IkTarget::Pose(goal), &IkOptions::default());
+
+        assert!(solution.converged);
+        // Planar 3R pose IK has a unique elbow branch near the start; the sum of angles is fixed
+        let total: f64 = solution.joint_values.iter().sum();
+        assert!((total - 0.9).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_ik_with_base_and_tool_frames() {
+        let mut robot = RobotArm::from_dh_params(vec![
+            DHParameter::revolute(0.0, PI / 2.0, 0.5, 0.0),
+            DHParameter::revolute(1.0, 0.0, 0.0, 0.0),
+            DHParameter::revolute(0.8, 0.0, 0.0, 0.0),
+        ]);
+        robot
+            .set_base_frame(Transform3D::from_xyz_rpy([0.5, -1.0, 0.8], [0.0, 0.0, 0.6]))
+            .unwrap();
+        robot.set_tool_frame(Transform3D::translation(0.2, 0.0, 0.1)).unwrap();
+
+        let mut reference = robot.clone();
+        reference.set_joint_angles(vec![0.7, 0.4, -0.9]).unwrap();
+        let goal = end_effector_transform(&reference).position();
+
+        robot.set_joint_angles(vec![0.5, 0.2, -0.5]).unwrap();
+        let solution = solve_ik(&robot, &IkTarget::Position(goal), &IkOptions::default());
+        assert!(solution.converged);
+
+        robot.set_joint_angles(solution.joint_values).unwrap();
+        let reached = end_effector_transform(&robot).position();
+        assert!((reached.x - goal.x).abs() < EPSILON);
+        assert!((reached.y - goal.y).abs() < EPSILON);
+        assert!((reached.z - goal.z).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_ik_unreachable_target() {
+        let robot = RobotArm::planar(vec![2.0, 1.5]);
+
+        let target = IkTarget::Position(Point3D::new(5.0, 0.0, 0.0));
+        let solution = solve_ik(&robot, &target, &IkOptions::default());
+
+        assert!(!solution.converged);
+        assert_eq!(solution.iterations, IkOptions::default().max_iterations);
+        // Best effort: fully stretched towards the target, 1.5 short of it
+        assert!((solution.error - 1.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_ik_respects_joint_limits() {
+        let mut robot = RobotArm::planar(vec![2.0, 1.5]);
+        robot.set_joint_limits(vec![JointLimits::position(-0.2, 0.2), JointLimits::position(0.0, 2.0)]).unwrap();
+
+        // Straight up needs θ1 ≈ π/2: the best reachable point keeps θ1 at its limit
+        let target = IkTarget::Position(Point3D::new(0.0, 3.5, 0.0));
+        let solution = solve_ik(&robot, &target, &IkOptions::default());
+
+        assert!(!solution.converged);
+        assert!(solution.joint_values[0] <= 0.2 + 1e-12);
+        assert!((0.0..=2.0).contains(&solution.joint_values[1]));
+    }
+
+    #[test]
+    fn test_ik_does_not_modify_robot() {
+        let robot = RobotArm::planar(vec![2.0, 1.5]);
+        let target = IkTarget::Position(Point3D::new(1.0, 1.0, 0.0));
+        let _ = solve_ik(&robot, &target, &IkOptions::default());
+
+        assert_eq!(robot.joint_angles, vec![0.0, 0.0]);
+    }
+}
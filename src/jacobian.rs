@@ -0,0 +1,285 @@
+use crate::dh_parameters::JointType;
+use crate::kinematics::{end_effector_transform, joint_frames};
+use crate::robot::RobotArm;
+use nalgebra::{DMatrix, Matrix3, Vector3};
+use serde::{Deserialize, Serialize};
+
+/// Orientation representation used by the analytic Jacobian
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum OrientationRepresentation {
+    /// Roll-pitch-yaw: R = Rot(Z, yaw) * Rot(Y, pitch) * Rot(X, roll), rates ordered (roll, pitch, yaw)
+    RollPitchYaw,
+    /// ZYZ Euler angles: R = Rot(Z, φ) * Rot(Y, θ) * Rot(Z, ψ), rates ordered (φ, θ, ψ)
+    EulerZYZ,
+}
+
+/// Computes the geometric Jacobian of the end-effector
+///
+/// Returns a 6×N matrix mapping joint velocities to the end-effector's linear
+/// velocity (rows 0-2) and angular velocity (rows 3-5), both in the base frame.
+/// With z_i, p_i the axis and a point of joint i (see `joint_frames`) and p_e
+/// the end-effector:
+/// - Revolute column:  [z_i × (p_e - p_i); z_i]
+/// - Prismatic column: [z_i; 0]
+pub fn jacobian(robot: &RobotArm) -> DMatrix<f64> {
+    let end_effector = end_effector_transform(robot).position();
+    let p_e = Vector3::new(end_effector.x, end_effector.y, end_effector.z);
+
+    let mut jacobian = DMatrix::zeros(6, robot.num_joints());
+
+    for (i, frame) in joint_frames(robot).iter().enumerate() {
+        let rotation = frame.rotation_matrix();
+        let z_axis: Vector3<f64> = rotation.column(2).into_owned();
+        let origin = frame.position();
+        let p_joint = Vector3::new(origin.x, origin.y, origin.z);
+
+        match robot.joint_type(i) {
+            JointType::Revolute => {
+                let linear = z_axis.cross(&(p_e - p_joint));
+                jacobian.fixed_view_mut::<3, 1>(0, i).copy_from(&linear);
+                jacobian.fixed_view_mut::<3, 1>(3, i).copy_from(&z_axis);
+            }
+            JointType::Prismatic => {
+                jacobian.fixed_view_mut::<3, 1>(0, i).copy_from(&z_axis);
+            }
+        }
+    }
+
+    jacobian
+}
+
+/// Computes the analytic Jacobian for a given orientation representation
+///
+/// The angular rows are replaced by the rates of the orientation angles:
+/// J_A = [I 0; 0 B(φ)⁻¹] J, where ω = B(φ) φ̇.
+///
+/// Returns None at a representation singularity (pitch = ±π/2 for RPY,
+/// θ = 0 or π for ZYZ), where the angle rates are undefined.
+pub fn analytic_jacobian(robot: &RobotArm, representation: OrientationRepresentation) -> Option<DMatrix<f64>> {
+    let end_effector = end_effector_transform(robot);
+
+    let rate_map = match representation {
+        OrientationRepresentation::RollPitchYaw => {
+            let (_, pitch, yaw) = end_effector.rpy();
+            #[rustfmt::skip]
+            let b = Matrix3::new(
+                yaw.cos() * pitch.cos(), -yaw.sin(), 0.0,
+                yaw.sin() * pitch.cos(),  yaw.cos(), 0.0,
+                           -pitch.sin(),        0.0, 1.0,
+            );
+            b
+        }
+        OrientationRepresentation::EulerZYZ => {
+            let (phi, theta, _) = end_effector.euler_zyz();
+            #[rustfmt::skip]
+            let b = Matrix3::new(
+                0.0, -phi.sin(), phi.cos() * theta.sin(),
+                0.0,  phi.cos(), phi.sin() * theta.sin(),
+                1.0,        0.0,             theta.cos(),
+            );
+            b
+        }
+    };
+
+    if rate_map.determinant().abs() < 1e-10 {
+        return None;
+    }
+    let rate_map_inverse = rate_map.try_inverse()?;
+
+    let mut analytic = jacobian(robot);
+    let angular = rate_map_inverse * analytic.rows(3, 3);
+    analytic.rows_mut(3, 3).copy_from(&angular);
+
+    Some(analytic)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dh_parameters::{DHConvention, DHParameter};
+    use crate::geometry3d::Transform3D;
+    use crate::screw_model::ScrewModel;
+    use std::f64::consts::PI;
+
+    const EPSILON: f64 = 1e-6;
+    const STEP: f64 = 1e-7;
+
+    fn test_robot() -> RobotArm {
+        let mut robot = RobotArm::from_dh_params(vec![
+            DHParameter::revolute(0.0, PI / 2.0, 0.4, 0.0),
+            DHParameter::revolute(0.8, 0.0, 0.0, 0.0),
+            DHParameter::prismatic(0.0, -PI / 2.0, 0.2, 0.0),
+            DHParameter::revolute(0.3, PI / 2.0, 0.1, 0.0),
+        ]);
+        robot.set_joint_angles(vec![0.3, -0.5, 0.25, 0.8]).unwrap();
+        robot
+    }
+
+    /// Finite-difference Jacobian: linear velocity + angular velocity (skew part of R' Rᵀ)
+    fn numerical_jacobian(robot: &RobotArm) -> DMatrix<f64> {
+        let base = end_effector_transform(robot);
+        let mut result = DMatrix::zeros(6, robot.num_joints());
+
+        for joint in 0..robot.num_joints() {
+            let mut moved = robot.clone();
+            let mut values = robot.joint_angles.clone();
+            values[joint] += STEP;
+            moved.set_joint_angles(values).unwrap();
+            let transform = end_effector_transform(&moved);
+
+            let (p0, p1) = (base.position(), transform.position());
+            result[(0, joint)] = (p1.x - p0.x) / STEP;
+            result[(1, joint)] = (p1.y - p0.y) / STEP;
+            result[(2, joint)] = (p1.z - p0.z) / STEP;
+
+            let delta = transform.rotation_matrix() * base.rotation_matrix().transpose();
+            result[(3, joint)] = (delta[(2, 1)] - delta[(1, 2)]) / (2.0 * STEP);
+            result[(4, joint)] = (delta[(0, 2)] - delta[(2, 0)]) / (2.0 * STEP);
+            result[(5, joint)] = (delta[(1, 0)] - delta[(0, 1)]) / (2.0 * STEP);
+        }
+
+        result
+    }
+
+    #[test]
+    fn test_planar_2r_jacobian_closed_form() {
+        let (l1, l2) = (2.0, 1.5);
+        let (q1, q2) = (0.4, 0.7);
+        let mut robot = RobotArm::new(vec![l1, l2]);
+        robot.set_joint_angles(vec![q1, q2]).unwrap();
+
+        let j = jacobian(&robot);
+        assert_eq!(j.shape(), (6, 2));
+
+        let (s1, c1) = q1.sin_cos();
+        let (s12, c12) = (q1 + q2).sin_cos();
+        assert!((j[(0, 0)] - (-l1 * s1 - l2 * s12)).abs() < EPSILON);
+        assert!((j[(1, 0)] - (l1 * c1 + l2 * c12)).abs() < EPSILON);
+        assert!((j[(0, 1)] - (-l2 * s12)).abs() < EPSILON);
+        assert!((j[(1, 1)] - (l2 * c12)).abs() < EPSILON);
+
+        // Pure rotation about Z, no out-of-plane motion
+        for col in 0..2 {
+            assert!(j[(2, col)].abs() < EPSILON);
+            assert!(j[(3, col)].abs() < EPSILON);
+            assert!(j[(4, col)].abs() < EPSILON);
+            assert!((j[(5, col)] - 1.0).abs() < EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_jacobian_matches_finite_differences() {
+        let robot = test_robot();
+        let analytic = jacobian(&robot);
+        let numerical = numerical_jacobian(&robot);
+
+        assert!((analytic - numerical).abs().max() < EPSILON);
+    }
+
+    #[test]
+    fn test_modified_dh_jacobian_matches_finite_differences() {
+        let mut robot = RobotArm::from_dh_params(
+            test_robot()
+                .dh_params
+                .unwrap()
+                .iter()
+                .map(|dh| dh.with_convention(DHConvention::Modified))
+                .collect(),
+        );
+        robot.set_joint_angles(vec![0.3, -0.5, 0.25, 0.8]).unwrap();
+
+        let analytic = jacobian(&robot);
+        let numerical = numerical_jacobian(&robot);
+
+        assert!((analytic - numerical).abs().max() < EPSILON);
+    }
+
+    #[test]
+    fn test_screw_model_jacobian_matches_dh_jacobian() {
+        let dh_robot = test_robot();
+        let mut screw_robot = RobotArm::from_screw_model(ScrewModel::from_dh_params(
+            dh_robot.dh_params.as_ref().unwrap(),
+        ));
+        screw_robot.set_joint_angles(dh_robot.joint_angles.clone()).unwrap();
+
+        assert!((jacobian(&screw_robot) - jacobian(&dh_robot)).abs().max() < 1e-9);
+    }
+
+    #[test]
+    fn test_jacobian_with_base_and_tool_frames() {
+        let mut dh_robot = test_robot();
+        dh_robot
+            .set_base_frame(Transform3D::from_xyz_rpy([0.5, -0.2, 0.9], [0.3, -0.2, 1.1]))
+            .unwrap();
+        dh_robot
+            .set_tool_frame(Transform3D::from_xyz_rpy([0.05, 0.0, 0.15], [0.0, 0.4, 0.0]))
+            .unwrap();
+        assert!((jacobian(&dh_robot) - numerical_jacobian(&dh_robot)).abs().max() < EPSILON);
+
+        let mut screw_robot = RobotArm::from_screw_model(ScrewModel::from_dh_params(
+            dh_robot.dh_params.as_ref().unwrap(),
+        ));
+        screw_robot.base = dh_robot.base;
+        screw_robot.tool = dh_robot.tool;
+        screw_robot.set_joint_angles(dh_robot.joint_angles.clone()).unwrap();
+        assert!((jacobian(&screw_robot) - jacobian(&dh_robot)).abs().max() < 1e-9);
+    }
+
+    #[test]
+    fn test_prismatic_column_has_no_angular_part() {
+        let robot = test_robot();
+        let j = jacobian(&robot);
+
+        let linear = Vector3::new(j[(0, 2)], j[(1, 2)], j[(2, 2)]);
+        assert!((linear.norm() - 1.0).abs() < EPSILON);
+        assert!(j[(3, 2)].abs() < EPSILON);
+        assert!(j[(4, 2)].abs() < EPSILON);
+        assert!(j[(5, 2)].abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_analytic_jacobian_rpy_matches_finite_differences() {
+        let robot = test_robot();
+        let analytic = analytic_jacobian(&robot, OrientationRepresentation::RollPitchYaw).unwrap();
+        let (r0, p0, y0) = end_effector_transform(&robot).rpy();
+
+        for joint in 0..robot.num_joints() {
+            let mut moved = robot.clone();
+            let mut values = robot.joint_angles.clone();
+            values[joint] += STEP;
+            moved.set_joint_angles(values).unwrap();
+            let (r1, p1, y1) = end_effector_transform(&moved).rpy();
+
+            assert!((analytic[(3, joint)] - (r1 - r0) / STEP).abs() < 1e-5);
+            assert!((analytic[(4, joint)] - (p1 - p0) / STEP).abs() < 1e-5);
+            assert!((analytic[(5, joint)] - (y1 - y0) / STEP).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_analytic_jacobian_zyz_matches_finite_differences() {
+        let robot = test_robot();
+        let analytic = analytic_jacobian(&robot, OrientationRepresentation::EulerZYZ).unwrap();
+        let (a0, b0, c0) = end_effector_transform(&robot).euler_zyz();
+
+        for joint in 0..robot.num_joints() {
+            let mut moved = robot.clone();
+            let mut values = robot.joint_angles.clone();
+            values[joint] += STEP;
+            moved.set_joint_angles(values).unwrap();
+            let (a1, b1, c1) = end_effector_transform(&moved).euler_zyz();
+
+            assert!((analytic[(3, joint)] - (a1 - a0) / STEP).abs() < 1e-5);
+            assert!((analytic[(4, joint)] - (b1 - b0) / STEP).abs() < 1e-5);
+            assert!((analytic[(5, joint)] - (c1 - c0) / STEP).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_analytic_jacobian_representation_singularity() {
+        // Planar arm: the tool Z axis stays vertical, so ZYZ has θ = 0
+        let robot = RobotArm::planar(vec![1.0, 1.0]);
+        assert!(analytic_jacobian(&robot, OrientationRepresentation::EulerZYZ).is_none());
+        assert!(analytic_jacobian(&robot, OrientationRepresentation::RollPitchYaw).is_some());
+    }
+}
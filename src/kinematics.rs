@@ -1,5 +1,6 @@
-use crate::geometry3d::{Point3D, Transform3D};
+use crate::geometry3d::{Point3D, Quaternion, Transform3D};
 use crate::robot::RobotArm;
+use nalgebra::{Matrix3, Vector3};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -23,82 +24,198 @@ impl JointPosition3D {
     }
 }
 
+/// Full pose of a link frame (or the end-effector) in the base frame
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct LinkPose {
+    /// Origin of the frame
+    pub position: JointPosition3D,
+    /// Orientation as a unit quaternion
+    pub quaternion: Quaternion,
+    /// Orientation as roll-pitch-yaw: R = Rot(Z, yaw) * Rot(Y, pitch) * Rot(X, roll)
+    pub rpy: [f64; 3],
+    /// Homogeneous 4x4 transform, row-major
+    pub matrix: [f64; 16],
+}
+
+impl LinkPose {
+    pub fn from_transform(transform: &Transform3D) -> Self {
+        let (roll, pitch, yaw) = transform.rpy();
+
+        LinkPose {
+            position: JointPosition3D::from_point(&transform.position()),
+            quaternion: transform.quaternion(),
+            rpy: [roll, pitch, yaw],
+            matrix: transform.to_row_major(),
+        }
+    }
+}
+
 /// Computes forward kinematics for a robot arm
 ///
-/// Supports two modes:
+/// Supports three modes:
 /// 1. Simple planar robots (link_lengths + joint_angles)
 /// 2. DH parameter-based robots (full 3D specification)
+/// 3. Screw-axis robots (product of exponentials)
 ///
-/// Returns positions of all joints including base and end-effector
+/// Returns positions of all joints including base and end-effector (the origins
+/// of `link_frames`, so the base and tool frames are taken into account)
 pub fn forward_kinematics(robot: &RobotArm) -> Vec<JointPosition3D> {
-    if robot.uses_dh_params() {
-        forward_kinematics_dh(robot)
-    } else {
-        forward_kinematics_planar(robot)
-    }
+    link_frames(robot)
+        .iter()
+        .map(|frame| JointPosition3D::from_point(&frame.position()))
+        .collect()
+}
+
+/// Computes the full end-effector frame (position + orientation)
+///
+/// Chains the same link transforms as `forward_kinematics` but keeps the
+/// complete homogeneous transform of the last frame instead of its origin only:
+/// T = base * T_chain * tool.
+pub fn end_effector_transform(robot: &RobotArm) -> Transform3D {
+    let chain = match &robot.screw_model {
+        Some(model) => model.forward_kinematics_space(&robot.joint_angles),
+        None => link_transforms(robot)
+            .iter()
+            .fold(Transform3D::identity(), |frame, link| frame.compose(link)),
+    };
+
+    robot.base.compose(&chain).compose(&robot.tool)
+}
+
+/// Computes the world frame of the base and of every link
+///
+/// Returns N+1 frames for an N-joint robot: frame 0 is the base and frame i is
+/// the frame attached to the end of link i. The Z axis of frame i-1 is the
+/// axis of joint i, and the last frame is the end-effector (the tool center
+/// point when a tool frame is set).
+pub fn link_frames(robot: &RobotArm) -> Vec<Transform3D> {
+    let mut frames = Vec::with_capacity(robot.num_joints() + 1);
+    visit_link_frames(robot, &robot.joint_angles, |_, frame| frames.push(*frame));
+    frames
 }
 
-/// Forward kinematics using DH parameters
+/// Calls `visit(i, frame)` with the world frame of the base and of every link, in order
 ///
-/// Chains DH transformations to compute joint positions in 3D space
-fn forward_kinematics_dh(robot: &RobotArm) -> Vec<JointPosition3D> {
-    let mut positions = Vec::new();
-    let mut current_transform = Transform3D::identity();
-
-    // Add base position at origin
-    positions.push(JointPosition3D::from_point(&Point3D::origin()));
-
-    // Get DH parameters with current joint values
-    if let Some(dh_params) = robot.get_dh_with_current_values() {
-        for dh in dh_params {
-            // Apply DH transformation for this link
-            let link_transform = dh.to_transform();
-            current_transform = current_transform.compose(&link_transform);
-
-            // Record joint position
-            let joint_pos = current_transform.transform_point(&Point3D::origin());
-            positions.push(JointPosition3D::from_point(&joint_pos));
+/// Same frames as `link_frames`, but for the given `joint_values` (one per joint,
+/// used as-is without the limit policy) and without allocating, so it can be
+/// used in hot loops over many configurations.
+pub fn visit_link_frames(robot: &RobotArm, joint_values: &[f64], mut visit: impl FnMut(usize, &Transform3D)) {
+    let num_joints = robot.num_joints();
+    // The tool frame is part of the last link
+    let mut emit = |index: usize, frame: Transform3D| {
+        if index == num_joints && num_joints > 0 {
+            visit(index, &frame.compose(&robot.tool));
+        } else {
+            visit(index, &frame);
+        }
+    };
+    emit(0, robot.base);
+
+    if let Some(model) = &robot.screw_model {
+        let mut product = Transform3D::identity();
+        let links = model.screw_axes.iter().zip(joint_values).zip(model.link_homes.iter());
+        for (i, ((axis, &value), home)) in links.enumerate() {
+            product = product.compose(&Transform3D::exp(&(axis * value)));
+            emit(i + 1, robot.base.compose(&product).compose(home));
         }
+        return;
     }
 
-    positions
+    let mut current_transform = robot.base;
+    match &robot.dh_params {
+        Some(dh_params) => {
+            for (i, (dh, &value)) in dh_params.iter().zip(joint_values).enumerate() {
+                current_transform = current_transform.compose(&dh.with_joint_value(value).to_transform());
+                emit(i + 1, current_transform);
+            }
+        }
+        None => {
+            for (i, (&length, &angle)) in robot.link_lengths.iter().zip(joint_values).enumerate() {
+                current_transform = current_transform
+                    .compose(&Transform3D::rotation_z(angle))
+                    .compose(&Transform3D::translation(length, 0.0, 0.0));
+                emit(i + 1, current_transform);
+            }
+        }
+    }
 }
 
-/// Forward kinematics for simple planar robots
+/// Computes the world frame of every joint axis
 ///
-/// Uses 3D transforms but constrains motion to XY plane (Z=0)
-/// This is the legacy method from Phase 1/2
-fn forward_kinematics_planar(robot: &RobotArm) -> Vec<JointPosition3D> {
-    let mut positions = Vec::new();
-    let mut current_transform = Transform3D::identity();
-
-    // Add base position at origin
-    let base = current_transform.transform_point(&Point3D::origin());
-    positions.push(JointPosition3D::from_point(&base));
-
-    // For each link, compute the joint position using 3D transforms
-    // Planar robots rotate around Z-axis and translate along X-axis (in the XY plane)
-    for i in 0..robot.num_joints() {
-        // Rotate around Z-axis by joint angle
-        let rotation = Transform3D::rotation_z(robot.joint_angles[i]);
-        current_transform = current_transform.compose(&rotation);
-
-        // Translate along X-axis by link length (Z component is 0)
-        let translation = Transform3D::translation(robot.link_lengths[i], 0.0, 0.0);
-        current_transform = current_transform.compose(&translation);
-
-        // Record position after this link
-        let joint_pos = current_transform.transform_point(&Point3D::origin());
-        positions.push(JointPosition3D::from_point(&joint_pos));
+/// Joint i moves about (revolute) or along (prismatic) the Z axis of frame i.
+/// For Standard DH this is link frame i-1, for Modified DH it is link frame i-1
+/// followed by the link's fixed Rot(X, α) * Trans(X, a). For screw-axis robots
+/// the frame is placed on the current joint screw axis.
+pub fn joint_frames(robot: &RobotArm) -> Vec<Transform3D> {
+    if let Some(model) = &robot.screw_model {
+        let jacobian = model.space_jacobian(&robot.joint_angles);
+        return jacobian
+            .column_iter()
+            .map(|screw| {
+                let omega = Vector3::new(screw[0], screw[1], screw[2]);
+                let v = Vector3::new(screw[3], screw[4], screw[5]);
+
+                let frame = if omega.norm() > 1e-12 {
+                    // Revolute: point on the axis closest to the origin, q = ω × v / |ω|²
+                    frame_along_axis(&(omega.cross(&v) / omega.norm_squared()), &omega)
+                } else {
+                    frame_along_axis(&Vector3::zeros(), &v)
+                };
+                robot.base.compose(&frame)
+            })
+            .collect();
+    }
+
+    let frames = link_frames(robot);
+
+    match robot.get_dh_with_current_values() {
+        Some(dh_params) => frames
+            .iter()
+            .zip(dh_params.iter())
+            .map(|(frame, dh)| frame.compose(&dh.pre_joint_transform()))
+            .collect(),
+        None => frames[..robot.num_joints()].to_vec(),
     }
+}
+
+/// Computes the full pose of the base, every link and the end-effector
+///
+/// Same frames as `link_frames`; the last entry is the end-effector (tool flange).
+pub fn link_poses(robot: &RobotArm) -> Vec<LinkPose> {
+    link_frames(robot).iter().map(LinkPose::from_transform).collect()
+}
+
+/// Frame at `origin` whose Z axis points along `axis`
+fn frame_along_axis(origin: &Vector3<f64>, axis: &Vector3<f64>) -> Transform3D {
+    let z = axis.normalize();
+    // Any vector not parallel to Z completes the basis
+    let helper = if z.x.abs() < 0.9 { Vector3::x() } else { Vector3::y() };
+    let x = helper.cross(&z).normalize();
+    let y = z.cross(&x);
 
-    positions
+    Transform3D::from_parts(&Matrix3::from_columns(&[x, y, z]), origin)
+}
+
+/// Local transform of every link for the robot's current joint values
+fn link_transforms(robot: &RobotArm) -> Vec<Transform3D> {
+    match robot.get_dh_with_current_values() {
+        Some(dh_params) => dh_params.iter().map(|dh| dh.to_transform()).collect(),
+        None => robot
+            .joint_angles
+            .iter()
+            .zip(robot.link_lengths.iter())
+            .map(|(&angle, &length)| {
+                Transform3D::rotation_z(angle).compose(&Transform3D::translation(length, 0.0, 0.0))
+            })
+            .collect(),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::dh_parameters::DHParameter;
+    use crate::dh_parameters::{DHConvention, DHParameter};
+    use crate::screw_model::ScrewModel;
     use std::f64::consts::PI;
 
     const EPSILON: f64 = 1e-10;
@@ -114,7 +231,7 @@ mod tests {
         // Robot with two links of length 2.0 and 1.5
         // Both angles at 0 - should extend horizontally along X-axis
         let mut robot = RobotArm::new(vec![2.0, 1.5]);
-        robot.set_joint_angles(vec![0.0, 0.0]);
+        robot.set_joint_angles(vec![0.0, 0.0]).unwrap();
 
         let positions = forward_kinematics(&robot);
 
@@ -140,7 +257,7 @@ mod tests {
     fn test_fk_90_degree_first_joint() {
         // First joint rotated 90°, second at 0°
         let mut robot = RobotArm::new(vec![2.0, 1.5]);
-        robot.set_joint_angles(vec![PI / 2.0, 0.0]);
+        robot.set_joint_angles(vec![PI / 2.0, 0.0]).unwrap();
 
         let positions = forward_kinematics(&robot);
 
@@ -164,7 +281,7 @@ mod tests {
     fn test_fk_both_joints_90_degrees() {
         // Both joints at 90°
         let mut robot = RobotArm::new(vec![2.0, 1.5]);
-        robot.set_joint_angles(vec![PI / 2.0, PI / 2.0]);
+        robot.set_joint_angles(vec![PI / 2.0, PI / 2.0]).unwrap();
 
         let positions = forward_kinematics(&robot);
 
@@ -188,7 +305,7 @@ mod tests {
     fn test_fk_folded_configuration() {
         // First joint at 0°, second at -180° (folded back)
         let mut robot = RobotArm::new(vec![2.0, 1.5]);
-        robot.set_joint_angles(vec![0.0, PI]);
+        robot.set_joint_angles(vec![0.0, PI]).unwrap();
 
         let positions = forward_kinematics(&robot);
 
@@ -214,7 +331,7 @@ mod tests {
     fn test_fk_dh_planar_robot() {
         // Create planar robot using DH parameters
         let mut robot = RobotArm::planar(vec![2.0, 1.5]);
-        robot.set_joint_angles(vec![0.0, 0.0]);
+        robot.set_joint_angles(vec![0.0, 0.0]).unwrap();
 
         let positions = forward_kinematics(&robot);
 
@@ -229,7 +346,7 @@ mod tests {
     fn test_fk_dh_planar_90_degrees() {
         // Planar DH robot with first joint at 90°
         let mut robot = RobotArm::planar(vec![2.0, 1.5]);
-        robot.set_joint_angles(vec![PI / 2.0, 0.0]);
+        robot.set_joint_angles(vec![PI / 2.0, 0.0]).unwrap();
 
         let positions = forward_kinematics(&robot);
 
@@ -248,7 +365,7 @@ mod tests {
         ];
 
         let mut robot = RobotArm::from_dh_params(dh_params);
-        robot.set_joint_angles(vec![0.0, 0.0]);
+        robot.set_joint_angles(vec![0.0, 0.0]).unwrap();
 
         let positions = forward_kinematics(&robot);
 
@@ -274,7 +391,7 @@ mod tests {
         ];
 
         let mut robot = RobotArm::from_dh_params(dh_params);
-        robot.set_joint_angles(vec![0.0, 0.0]);
+        robot.set_joint_angles(vec![0.0, 0.0]).unwrap();
 
         let positions = forward_kinematics(&robot);
 
@@ -288,4 +405,179 @@ mod tests {
         assert!(approx_eq(positions[2].y, 0.0));
         assert!(approx_eq(positions[2].z, 0.5));
     }
+
+    #[test]
+    fn test_end_effector_transform_matches_positions() {
+        let dh_params = vec![
+            DHParameter::revolute(0.0, PI / 2.0, 0.5, 0.0),
+            DHParameter::revolute(1.0, 0.0, 0.0, 0.0),
+            DHParameter::revolute(0.8, 0.0, 0.0, 0.0),
+        ];
+
+        let mut robot = RobotArm::from_dh_params(dh_params);
+        robot.set_joint_angles(vec![0.3, -0.7, 1.1]).unwrap();
+
+        let positions = forward_kinematics(&robot);
+        let end_effector = end_effector_transform(&robot).position();
+
+        assert!(approx_eq(end_effector.x, positions[3].x));
+        assert!(approx_eq(end_effector.y, positions[3].y));
+        assert!(approx_eq(end_effector.z, positions[3].z));
+    }
+
+    #[test]
+    fn test_link_frames_count_and_base() {
+        let robot = RobotArm::planar(vec![2.0, 1.5, 1.0]);
+        let frames = link_frames(&robot);
+
+        assert_eq!(frames.len(), 4);
+        assert!(approx_eq(frames[0].position().x, 0.0));
+        assert!(approx_eq(frames[3].position().x, 4.5));
+    }
+
+    #[test]
+    fn test_fk_modified_dh_robot() {
+        // Modified DH: a/α of a link describe the previous link
+        let dh_params = vec![
+            DHParameter::revolute(0.0, 0.0, 0.5, 0.0).with_convention(DHConvention::Modified),
+            DHParameter::revolute(1.0, 0.0, 0.0, 0.0).with_convention(DHConvention::Modified),
+        ];
+
+        let mut robot = RobotArm::from_dh_params(dh_params);
+        robot.set_joint_angles(vec![PI / 2.0, 0.0]).unwrap();
+
+        let positions = forward_kinematics(&robot);
+
+        // Joint 2 sits 1.0 along X of frame 1, which the first joint turned to +Y
+        assert!(approx_eq(positions[2].x, 0.0));
+        assert!(approx_eq(positions[2].y, 1.0));
+        assert!(approx_eq(positions[2].z, 0.5));
+    }
+
+    #[test]
+    fn test_joint_frames_follow_convention() {
+        let standard = RobotArm::from_dh_params(vec![DHParameter::revolute(1.0, PI / 2.0, 0.0, 0.0)]);
+        let modified = RobotArm::from_dh_params(vec![
+            DHParameter::revolute(1.0, PI / 2.0, 0.0, 0.0).with_convention(DHConvention::Modified),
+        ]);
+
+        // Standard: joint 1 axis is the base Z axis at the origin
+        let frame = joint_frames(&standard)[0];
+        assert!(approx_eq(frame.position().x, 0.0));
+        assert!(approx_eq(frame.rotation_matrix()[(2, 2)], 1.0));
+
+        // Modified: joint 1 axis is Z after Rot(X, 90°) * Trans(X, 1), i.e. -Y through (1, 0, 0)
+        let frame = joint_frames(&modified)[0];
+        assert!(approx_eq(frame.position().x, 1.0));
+        assert!(approx_eq(frame.rotation_matrix()[(1, 2)], -1.0));
+    }
+
+    #[test]
+    fn test_fk_screw_model_matches_dh() {
+        let dh_params = vec![
+            DHParameter::revolute(0.0, PI / 2.0, 0.5, 0.0),
+            DHParameter::revolute(1.0, 0.0, 0.0, 0.0),
+            DHParameter::prismatic(0.0, 0.0, 0.3, 0.0),
+        ];
+        let mut dh_robot = RobotArm::from_dh_params(dh_params.clone());
+        let mut screw_robot = RobotArm::from_screw_model(ScrewModel::from_dh_params(&dh_params));
+        dh_robot.set_joint_angles(vec![0.4, -0.2, 0.1]).unwrap();
+        screw_robot.set_joint_angles(vec![0.4, -0.2, 0.1]).unwrap();
+
+        let expected = forward_kinematics(&dh_robot);
+        let actual = forward_kinematics(&screw_robot);
+        assert_eq!(actual.len(), expected.len());
+        for (e, a) in expected.iter().zip(actual.iter()) {
+            assert!(approx_eq(e.x, a.x));
+            assert!(approx_eq(e.y, a.y));
+            assert!(approx_eq(e.z, a.z));
+        }
+    }
+
+    #[test]
+    fn test_base_and_tool_frames() {
+        let dh_params = vec![
+            DHParameter::revolute(0.0, PI / 2.0, 0.5, 0.0),
+            DHParameter::revolute(1.0, 0.0, 0.0, 0.0),
+        ];
+        let mut robot = RobotArm::from_dh_params(dh_params.clone());
+        robot.set_joint_angles(vec![0.4, -0.2]).unwrap();
+        let flange = end_effector_transform(&robot);
+
+        let base = Transform3D::from_xyz_rpy([1.0, 2.0, 0.5], [0.0, 0.0, PI / 2.0]);
+        let tool = Transform3D::translation(0.0, 0.0, 0.2);
+        robot.set_base_frame(base).unwrap();
+        robot.set_tool_frame(tool).unwrap();
+
+        let expected = base.compose(&flange).compose(&tool);
+        let actual = end_effector_transform(&robot);
+        assert!((actual.matrix() - expected.matrix()).abs().max() < EPSILON);
+
+        // Frame 0 is the base, the last frame is the TCP and the positions follow the frames
+        let frames = link_frames(&robot);
+        let positions = forward_kinematics(&robot);
+        assert_eq!(frames.len(), 3);
+        assert!((frames[0].matrix() - base.matrix()).abs().max() < EPSILON);
+        assert!((frames[2].matrix() - expected.matrix()).abs().max() < EPSILON);
+        assert!(approx_eq(positions[0].x, 1.0) && approx_eq(positions[0].y, 2.0) && approx_eq(positions[0].z, 0.5));
+        assert!(approx_eq(positions[2].z, expected.position().z));
+
+        // Screw-axis robots use the same frames
+        let mut screw_robot = RobotArm::from_screw_model(ScrewModel::from_dh_params(&dh_params));
+        screw_robot.set_joint_angles(vec![0.4, -0.2]).unwrap();
+        screw_robot.set_base_frame(base).unwrap();
+        screw_robot.set_tool_frame(tool).unwrap();
+        for (a, b) in link_frames(&screw_robot).iter().zip(frames.iter()) {
+            assert!((a.matrix() - b.matrix()).abs().max() < 1e-9);
+        }
+        for (a, b) in joint_frames(&screw_robot).iter().zip(joint_frames(&robot).iter()) {
+            assert!((a.position().z - b.position().z).abs() < 1e-9);
+            assert!((a.rotation_matrix().column(2) - b.rotation_matrix().column(2)).norm() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_link_poses_orientation() {
+        // Second link twisted by 90° about X: the tool Z axis points along -Y
+        let dh_params = vec![
+            DHParameter::revolute(1.0, 0.0, 0.0, 0.0),
+            DHParameter::revolute(1.0, PI / 2.0, 0.0, 0.0),
+        ];
+        let robot = RobotArm::from_dh_params(dh_params);
+
+        let poses = link_poses(&robot);
+        assert_eq!(poses.len(), 3);
+
+        let tool = poses[2];
+        assert!(approx_eq(tool.position.x, 2.0));
+        assert!(approx_eq(tool.rpy[0], PI / 2.0));
+        assert!(approx_eq(tool.rpy[1], 0.0));
+        assert!(approx_eq(tool.rpy[2], 0.0));
+
+        // Quaternion for 90° about X
+        let half = (PI / 4.0).cos();
+        assert!(approx_eq(tool.quaternion.w, half));
+        assert!(approx_eq(tool.quaternion.x, half));
+
+        // Row-major matrix: Z axis column is (0, -1, 0)
+        assert!(approx_eq(tool.matrix[2], 0.0));
+        assert!(approx_eq(tool.matrix[6], -1.0));
+        assert!(approx_eq(tool.matrix[10], 0.0));
+        assert!(approx_eq(tool.matrix[3], 2.0));
+    }
+
+    #[test]
+    fn test_end_effector_transform_simple_robot_orientation() {
+        let mut robot = RobotArm::new(vec![2.0, 1.5]);
+        robot.set_joint_angles(vec![PI / 2.0, PI / 2.0]).unwrap();
+
+        let transform = end_effector_transform(&robot);
+        let rotation = transform.rotation_matrix();
+
+        // Total rotation of 180° around Z: X axis of the tool points along -X
+        assert!(approx_eq(rotation[(0, 0)], -1.0));
+        assert!(approx_eq(rotation[(1, 0)], 0.0));
+        assert!(approx_eq(transform.position().x, -1.5));
+        assert!(approx_eq(transform.position().y, 2.0));
+    }
 }
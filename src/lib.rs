@@ -1,13 +1,61 @@
 use wasm_bindgen::prelude::*;
 
-mod geometry3d;
-mod robot;
-mod kinematics;
-mod dh_parameters;
+pub mod geometry3d;
+pub mod robot;
+pub mod kinematics;
+pub mod dh_parameters;
+pub mod error;
+pub mod inverse_kinematics;
+pub mod planar_ik;
+pub mod rng;
+pub mod jacobian;
+pub mod limits;
+pub mod screw_model;
+pub mod urdf;
+pub mod workspace;
+pub mod manipulability;
+pub mod trajectory;
+pub mod cartesian;
+pub mod presets;
+pub mod description;
+pub mod batch;
+pub mod dynamics;
+pub mod simulation;
+pub mod controller;
+pub mod model_control;
+pub mod collision;
+pub mod planner;
 
 use robot::RobotArm;
-use kinematics::{forward_kinematics, JointPosition3D};
-use dh_parameters::DHParameter;
+use kinematics::{forward_kinematics, link_poses, LinkPose};
+use dh_parameters::{validate_dh_params, DHParameter};
+use error::{check_dimension, check_finite, RoboticsError};
+use geometry3d::Point3D;
+use inverse_kinematics::{solve_ik, IkOptions, IkTarget};
+use planar_ik::solve_planar_ik;
+use jacobian::jacobian;
+use limits::{JointLimits, LimitPolicy};
+use screw_model::ScrewModel;
+use geometry3d::Transform3D;
+use nalgebra::Vector6;
+use urdf::UrdfRobot;
+use workspace::{analyze_workspace, WorkspaceOptions};
+use cartesian::{arc_path, linear_path, plan_cartesian_path, CartesianOptions, CartesianPath, CartesianTarget};
+use trajectory::{plan_trajectory, JointTrajectory, TrajectoryOptions};
+use manipulability::{
+    classify_singularity, manipulability, manipulability_ellipsoid, TaskSpace, DEFAULT_SINGULARITY_TOLERANCE,
+};
+use presets::{list_presets, Preset};
+use description::{robot_from_json, robot_to_json};
+use batch::{batch_forward_kinematics, BatchOutput};
+use dynamics::{forward_dynamics, gravity_torques, inverse_dynamics, mass_matrix, LinkInertia, STANDARD_GRAVITY};
+use simulation::{Simulation, SimulationOptions};
+use controller::{JointModel, PidGains, ServoOptions, ServoSimulation, TrackingSample};
+use model_control::{ControlLaw, JointReference, ModelController};
+use collision::{CollisionModel, LinkGeometry, Obstacle, DEFAULT_LINK_RADIUS};
+use planner::{plan_motion, PlannerOptions};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 
 // Browser console logging
 #[wasm_bindgen]
@@ -19,6 +67,21 @@ extern "C" {
 #[wasm_bindgen]
 pub struct RobotSimulator {
     robot: RobotArm,
+    /// Last planned joint trajectory (see `plan_trajectory`)
+    trajectory: Option<JointTrajectory>,
+    /// Reused buffers for the batch forward kinematics calls
+    batch_input: Vec<f64>,
+    batch_output: Vec<f64>,
+    /// Gravity acceleration in the world frame, used by the dynamics queries
+    gravity: [f64; 3],
+    /// Joint velocities and clock of the physics simulation (see `step`)
+    simulation: Simulation,
+    /// Model-based controller driving the physics simulation (see `set_controller`)
+    controller: Option<ModelController>,
+    /// Per-joint PID servos (see `servo_step`)
+    servos: ServoSimulation,
+    /// Link shapes and world obstacles (see `check_collisions`)
+    collision: CollisionModel,
 }
 
 #[wasm_bindgen]
@@ -31,15 +94,13 @@ impl RobotSimulator {
             link1_length, link2_length
         ));
 
-        RobotSimulator {
-            robot: RobotArm::new(vec![link1_length, link2_length]),
-        }
+        RobotSimulator::from_robot(RobotArm::new(vec![link1_length, link2_length]))
     }
 
     /// Create a robot from an array of link lengths (arbitrary-DOF, simple mode)
     pub fn new_simple(link_lengths: JsValue) -> Result<RobotSimulator, JsValue> {
-        let lengths: Vec<f64> = serde_wasm_bindgen::from_value(link_lengths)
-            .map_err(|e| JsValue::from_str(&format!("Failed to parse link lengths: {}", e)))?;
+        let lengths: Vec<f64> = from_js(link_lengths, "link lengths")?;
+        check_finite(&lengths)?;
 
         log(&format!(
             "Creating {}-DOF robot with link lengths: {:?}",
@@ -47,30 +108,26 @@ impl RobotSimulator {
             lengths
         ));
 
-        Ok(RobotSimulator {
-            robot: RobotArm::new(lengths),
-        })
+        Ok(RobotSimulator::from_robot(RobotArm::new(lengths)))
     }
 
     /// Create a robot from DH parameters (arbitrary-DOF, DH mode)
     pub fn new_dh(dh_params: JsValue) -> Result<RobotSimulator, JsValue> {
-        let params: Vec<DHParameter> = serde_wasm_bindgen::from_value(dh_params)
-            .map_err(|e| JsValue::from_str(&format!("Failed to parse DH parameters: {}", e)))?;
+        let params: Vec<DHParameter> = from_js(dh_params, "DH parameters")?;
+        validate_dh_params(&params)?;
 
         log(&format!(
             "Creating {}-DOF robot with DH parameters",
             params.len()
         ));
 
-        Ok(RobotSimulator {
-            robot: RobotArm::from_dh_params(params),
-        })
+        Ok(RobotSimulator::from_robot(RobotArm::from_dh_params(params)))
     }
 
     /// Create a planar robot using DH representation (arbitrary-DOF)
     pub fn new_planar(link_lengths: JsValue) -> Result<RobotSimulator, JsValue> {
-        let lengths: Vec<f64> = serde_wasm_bindgen::from_value(link_lengths)
-            .map_err(|e| JsValue::from_str(&format!("Failed to parse link lengths: {}", e)))?;
+        let lengths: Vec<f64> = from_js(link_lengths, "link lengths")?;
+        check_finite(&lengths)?;
 
         log(&format!(
             "Creating {}-DOF planar robot (DH mode) with link lengths: {:?}",
@@ -78,46 +135,828 @@ impl RobotSimulator {
             lengths
         ));
 
-        Ok(RobotSimulator {
-            robot: RobotArm::planar(lengths),
-        })
+        Ok(RobotSimulator::from_robot(RobotArm::planar(lengths)))
+    }
+
+    /// Create a robot from joint screw axes (product of exponentials, arbitrary-DOF)
+    ///
+    /// `screw_axes` is an array of `[ωx, ωy, ωz, vx, vy, vz]` in the base frame at the
+    /// home configuration, `home` the end-effector home pose M as a row-major 4x4 array.
+    pub fn new_screw(screw_axes: JsValue, home: JsValue) -> Result<RobotSimulator, JsValue> {
+        let axes: Vec<[f64; 6]> = from_js(screw_axes, "screw axes")?;
+        let home: [f64; 16] = from_js(home, "home configuration")?;
+        check_finite(&axes.concat())?;
+        check_finite(&home)?;
+
+        log(&format!("Creating {}-DOF robot from screw axes", axes.len()));
+
+        let screw_axes = axes.iter().map(|axis| Vector6::from_row_slice(axis)).collect();
+        let model = ScrewModel::from_home(screw_axes, Transform3D::from_row_major(&home));
+
+        Ok(RobotSimulator::from_robot(RobotArm::from_screw_model(model)))
+    }
+
+    /// Create a robot from a URDF description (serial chain, screw-axis mode)
+    ///
+    /// Supports revolute, continuous, prismatic and fixed joints; the end-effector
+    /// is the tip link of the chain.
+    pub fn from_urdf(xml: &str) -> Result<RobotSimulator, JsValue> {
+        let urdf = UrdfRobot::parse(xml)?;
+
+        log(&format!(
+            "Creating {}-DOF robot '{}' from URDF",
+            urdf.num_joints(),
+            urdf.name
+        ));
+
+        Ok(RobotSimulator::from_robot(urdf.to_robot_arm()?))
+    }
+
+    /// Create a robot from the built-in model library (e.g. "puma560", "ur5", "panda")
+    ///
+    /// The robot starts at the preset's home pose with its joint limits applied;
+    /// `list_presets` returns the available names.
+    pub fn from_preset(name: &str) -> Result<RobotSimulator, JsValue> {
+        let preset = Preset::from_name(name)?;
+
+        log(&format!("Creating robot from preset '{}'", preset.name()));
+
+        Ok(RobotSimulator::from_robot(preset.build()))
+    }
+
+    /// List the built-in robot models
+    ///
+    /// Returns `[{ name, description, num_joints, joint_types, home }]`.
+    pub fn list_presets() -> Result<JsValue, JsValue> {
+        Ok(to_js(&list_presets(), "robot presets")?)
+    }
+
+    /// Load a robot (model, joint names, limits, home pose and current state) from a
+    /// JSON robot description as written by `to_json`
+    ///
+    /// Throws with code `PARSE_ERROR` naming every invalid field.
+    pub fn from_json(json: &str) -> Result<RobotSimulator, JsValue> {
+        let robot = robot_from_json(json)?;
+
+        log(&format!("Creating {}-DOF robot '{}' from JSON", robot.num_joints(), robot.name));
+
+        Ok(RobotSimulator::from_robot(robot))
+    }
+
+    /// Save the robot as a versioned JSON robot description (see `from_json`)
+    pub fn to_json(&self) -> Result<String, JsValue> {
+        Ok(robot_to_json(&self.robot)?)
     }
 
     /// Set joint angles (2-DOF, backwards compatibility)
-    pub fn set_angles(&mut self, theta1: f64, theta2: f64) {
-        self.robot.set_joint_angles(vec![theta1, theta2]);
+    pub fn set_angles(&mut self, theta1: f64, theta2: f64) -> Result<(), JsValue> {
+        Ok(self.robot.set_joint_angles(vec![theta1, theta2])?)
     }
 
     /// Set joint angles from array (arbitrary-DOF)
+    ///
+    /// Out-of-range values are handled by the limit policy (see `set_limit_policy`).
     pub fn set_angles_array(&mut self, angles: JsValue) -> Result<(), JsValue> {
-        let angle_vec: Vec<f64> = serde_wasm_bindgen::from_value(angles)
-            .map_err(|e| JsValue::from_str(&format!("Failed to parse angles: {}", e)))?;
+        let angle_vec: Vec<f64> = from_js(angles, "angles")?;
+
+        Ok(self.robot.set_joint_angles(angle_vec)?)
+    }
+
+    /// Set per-joint limits from an array of `{ lower, upper, velocity, acceleration, effort }`
+    ///
+    /// Missing fields are unbounded. Throws `INVALID_ARGUMENT` if a lower bound is above
+    /// its upper bound or a velocity, acceleration or effort limit is not positive.
+    pub fn set_joint_limits(&mut self, limits: JsValue) -> Result<(), JsValue> {
+        let limits: Vec<JointLimits> = from_js(limits, "joint limits")?;
+
+        Ok(self.robot.set_joint_limits(limits)?)
+    }
+
+    /// Get per-joint limits (unbounded values are `Infinity` / `-Infinity`)
+    pub fn get_joint_limits(&self) -> Result<JsValue, JsValue> {
+        Ok(to_js(&self.robot.limits, "joint limits")?)
+    }
+
+    /// Set the mass properties of every link from an array of
+    /// `{ mass, center_of_mass: [x, y, z], inertia: [ixx, iyy, izz, ixy, ixz, iyz] }`
+    /// (or `null` for a massless link), expressed in the DH link frames
+    ///
+    /// Only DH robots carry link inertias.
+    pub fn set_link_inertias(&mut self, inertias: JsValue) -> Result<(), JsValue> {
+        let inertias: Vec<Option<LinkInertia>> = from_js(inertias, "link inertias")?;
+
+        Ok(self.robot.set_link_inertias(inertias)?)
+    }
+
+    /// Get the mass properties of every link (`null` for massless links)
+    pub fn get_link_inertias(&self) -> Result<JsValue, JsValue> {
+        Ok(to_js(&self.robot.link_inertias(), "link inertias")?)
+    }
+
+    /// Set the gravity acceleration in the world frame (m/s², default `[0, 0, -9.80665]`)
+    pub fn set_gravity(&mut self, x: f64, y: f64, z: f64) -> Result<(), JsValue> {
+        check_finite(&[x, y, z])?;
+        self.gravity = [x, y, z];
+        Ok(())
+    }
+
+    /// Get the gravity acceleration in the world frame as `[x, y, z]`
+    pub fn get_gravity(&self) -> Result<JsValue, JsValue> {
+        Ok(to_js(&self.gravity, "gravity")?)
+    }
+
+    /// Joint torques (forces for prismatic joints) that hold the current configuration against gravity
+    pub fn get_gravity_torques(&self) -> Result<JsValue, JsValue> {
+        let torques = gravity_torques(&self.robot, &self.robot.joint_angles, self.gravity)?;
+
+        Ok(to_js(&torques, "gravity torques")?)
+    }
+
+    /// Joint torques needed to move through the current configuration with the
+    /// given joint velocities and accelerations (arrays, one value per joint)
+    ///
+    /// Includes inertial, Coriolis/centrifugal and gravity terms; compare with the
+    /// `effort` limits to size the motors.
+    pub fn inverse_dynamics(&self, velocities: JsValue, accelerations: JsValue) -> Result<JsValue, JsValue> {
+        let velocities: Vec<f64> = from_js(velocities, "joint velocities")?;
+        let accelerations: Vec<f64> = from_js(accelerations, "joint accelerations")?;
+        let torques = inverse_dynamics(&self.robot, &self.robot.joint_angles, &velocities, &accelerations, self.gravity)?;
+
+        Ok(to_js(&torques, "joint torques")?)
+    }
+
+    /// Joint accelerations the given torques produce at the current positions and
+    /// simulation velocities (an array, one value per joint)
+    pub fn forward_dynamics(&self, torques: JsValue) -> Result<JsValue, JsValue> {
+        let torques: Vec<f64> = from_js(torques, "joint torques")?;
+        let accelerations = forward_dynamics(
+            &self.robot,
+            &self.robot.joint_angles,
+            &self.simulation.velocities,
+            &torques,
+            self.gravity,
+        )?;
+
+        Ok(to_js(&accelerations, "joint accelerations")?)
+    }
+
+    /// Joint-space mass matrix at the current configuration, as an array of rows
+    pub fn get_mass_matrix(&self) -> Result<JsValue, JsValue> {
+        let matrix = mass_matrix(&self.robot, &self.robot.joint_angles)?;
+        let rows: Vec<Vec<f64>> = matrix.row_iter().map(|row| row.iter().copied().collect()).collect();
+
+        Ok(to_js(&rows, "mass matrix")?)
+    }
+
+    /// Advance the physics simulation by `dt` seconds with constant joint torques
+    ///
+    /// `torques` is an array with one value per joint (zero torques when omitted, so
+    /// the arm falls and swings under gravity). When a controller is selected (see
+    /// `set_controller`) it runs at every integration step and `torques` are added to
+    /// its output as a disturbance. Torques are clamped to the effort limits and
+    /// joints stop at their position limits. The robot moves to the new positions;
+    /// returns `{ time, positions, velocities, accelerations }`.
+    pub fn step(&mut self, dt: f64, torques: JsValue) -> Result<JsValue, JsValue> {
+        let torques: Vec<f64> = if torques.is_undefined() || torques.is_null() {
+            vec![0.0; self.robot.num_joints()]
+        } else {
+            from_js(torques, "joint torques")?
+        };
+        let gravity = self.gravity;
+        let state = match &mut self.controller {
+            Some(controller) => {
+                check_dimension(self.robot.num_joints(), torques.len())?;
+                check_finite(&torques)?;
+                self.simulation.step_with(&mut self.robot, dt, gravity, |robot, velocities, h| {
+                    let mut output = controller.torques(robot, velocities, h, gravity)?;
+                    output.iter_mut().zip(&torques).for_each(|(torque, disturbance)| *torque += disturbance);
+                    Ok(output)
+                })?
+            }
+            None => self.simulation.step(&mut self.robot, &torques, dt, gravity)?,
+        };
+
+        Ok(to_js(&state, "simulation state")?)
+    }
+
+    /// Set the simulation options: `{ integrator: "SemiImplicitEuler" | "Rk4", max_step }`
+    /// (both optional, `max_step` in seconds and positive)
+    pub fn set_simulation_options(&mut self, options: JsValue) -> Result<(), JsValue> {
+        let options: SimulationOptions = from_js(options, "simulation options")?;
+        options.validate()?;
+
+        self.simulation.options = options;
+        Ok(())
+    }
+
+    /// Get the joint velocities of the simulation
+    pub fn get_joint_velocities(&self) -> Result<JsValue, JsValue> {
+        Ok(to_js(&self.simulation.velocities, "joint velocities")?)
+    }
+
+    /// Set the joint velocities of the simulation (an array, one value per joint)
+    pub fn set_joint_velocities(&mut self, velocities: JsValue) -> Result<(), JsValue> {
+        let velocities: Vec<f64> = from_js(velocities, "joint velocities")?;
+        check_dimension(self.robot.num_joints(), velocities.len())?;
+        check_finite(&velocities)?;
+
+        self.simulation.velocities = velocities;
+        Ok(())
+    }
+
+    /// Stop every joint and reset the simulation clock and the controller's PID state
+    /// (the pose is kept)
+    pub fn reset_simulation(&mut self) {
+        self.simulation.reset();
+        if let Some(controller) = &mut self.controller {
+            controller.reset();
+        }
+    }
+
+    /// Select the controller that drives `step`, or remove it with `null`
+    ///
+    /// One of `{ Pid: { kp, ki, kd, ... } }`, `{ GravityCompensation: { kp, kd } }`,
+    /// `{ ComputedTorque: { kp, kd } }` or `{ OperationalSpace: { kp, kd, posture_kp,
+    /// posture_kd, damping } }` (missing gains take defaults; gains must be non-negative,
+    /// `max_torque` and `damping` positive). A new controller holds
+    /// the current joint values and end-effector pose; switching laws keeps the
+    /// reference, target and posture.
+    pub fn set_controller(&mut self, law: JsValue) -> Result<(), JsValue> {
+        if law.is_undefined() || law.is_null() {
+            self.controller = None;
+            return Ok(());
+        }
+
+        let law: ControlLaw = from_js(law, "control law")?;
+        match &mut self.controller {
+            Some(controller) => controller.set_law(law)?,
+            None => self.controller = Some(ModelController::new(&self.robot, law)?),
+        }
+        Ok(())
+    }
+
+    /// Get the selected control law (same layout as `set_controller`), or null
+    pub fn get_controller(&self) -> Result<JsValue, JsValue> {
+        match &self.controller {
+            Some(controller) => Ok(to_js(&controller.law(), "control law")?),
+            None => Ok(JsValue::NULL),
+        }
+    }
+
+    /// Set the joint-space reference of the PID, gravity compensation and computed
+    /// torque laws: positions, with optional feed-forward velocities and accelerations
+    /// (arrays, one value per joint, zero when omitted)
+    pub fn set_control_reference(&mut self, positions: JsValue, velocities: JsValue, accelerations: JsValue) -> Result<(), JsValue> {
+        let positions: Vec<f64> = from_js(positions, "reference positions")?;
+        let mut reference = JointReference::hold(positions);
+        if !(velocities.is_undefined() || velocities.is_null()) {
+            reference.velocities = from_js(velocities, "reference velocities")?;
+        }
+        if !(accelerations.is_undefined() || accelerations.is_null()) {
+            reference.accelerations = from_js(accelerations, "reference accelerations")?;
+        }
+
+        Ok(self.model_controller()?.set_reference(reference)?)
+    }
+
+    /// Set the end-effector target of the operational-space law:
+    /// `{ position: { x, y, z }, quaternion: { w, x, y, z } }` (orientation is left
+    /// free when the quaternion is omitted)
+    pub fn set_control_target(&mut self, target: JsValue) -> Result<(), JsValue> {
+        let target: CartesianTarget = from_js(target, "control target")?;
+
+        Ok(self.model_controller()?.set_target(target)?)
+    }
+
+    /// Set the null-space posture of the operational-space law (an array, one value per
+    /// joint), or clear it with `null` so redundant joints are only damped
+    pub fn set_control_posture(&mut self, posture: JsValue) -> Result<(), JsValue> {
+        let posture: Option<Vec<f64>> = if posture.is_undefined() || posture.is_null() {
+            None
+        } else {
+            Some(from_js(posture, "control posture")?)
+        };
+
+        Ok(self.model_controller()?.set_posture(posture)?)
+    }
+
+    /// Set the PID gains: one `{ kp, ki, kd, derivative_filter, integral_limit, max_torque }`
+    /// object for every joint, or an array with one per joint (missing fields take defaults)
+    pub fn set_pid_gains(&mut self, gains: JsValue) -> Result<(), JsValue> {
+        let gains: Vec<PidGains> = per_joint(gains, self.robot.num_joints(), "PID gains")?;
+
+        Ok(self.servos.set_gains(gains)?)
+    }
+
+    /// Get the PID gains of every joint
+    pub fn get_pid_gains(&self) -> Result<JsValue, JsValue> {
+        let gains: Vec<PidGains> = self.servos.controllers.iter().map(|controller| controller.gains).collect();
+
+        Ok(to_js(&gains, "PID gains")?)
+    }
+
+    /// Set the joint actuator models: one `{ inertia, damping, friction }` object for
+    /// every joint, or an array with one per joint
+    pub fn set_joint_models(&mut self, models: JsValue) -> Result<(), JsValue> {
+        let models: Vec<JointModel> = per_joint(models, self.robot.num_joints(), "joint models")?;
+
+        Ok(self.servos.set_models(models)?)
+    }
+
+    /// Set the servo options: `{ control_period, history_length }` (both optional,
+    /// `control_period` in seconds and positive)
+    pub fn set_servo_options(&mut self, options: JsValue) -> Result<(), JsValue> {
+        let options: ServoOptions = from_js(options, "servo options")?;
+        options.validate()?;
+
+        self.servos.options = options;
+        Ok(())
+    }
+
+    /// Set the position every joint servo tracks (an array, one value per joint)
+    pub fn set_servo_setpoints(&mut self, setpoints: JsValue) -> Result<(), JsValue> {
+        let setpoints: Vec<f64> = from_js(setpoints, "servo setpoints")?;
+
+        Ok(self.servos.set_setpoints(setpoints)?)
+    }
+
+    /// Run the joint servos for `dt` seconds and move the robot
+    ///
+    /// Every joint is driven by its PID controller through its joint model,
+    /// independently of the others. Returns the recorded sample
+    /// `{ time, setpoints, positions, velocities, errors, torques }`.
+    pub fn servo_step(&mut self, dt: f64) -> Result<JsValue, JsValue> {
+        let sample = self.servos.step(&mut self.robot, dt)?;
+
+        Ok(to_js(&sample, "tracking sample")?)
+    }
+
+    /// Recorded servo samples, oldest first (same layout as `servo_step`), for plotting
+    pub fn get_tracking_history(&self) -> Result<JsValue, JsValue> {
+        let history: Vec<&TrackingSample> = self.servos.history().collect();
+
+        Ok(to_js(&history, "tracking history")?)
+    }
+
+    /// Stop the servos, clear their integrators and history, and hold the current pose
+    pub fn reset_servos(&mut self) -> Result<(), JsValue> {
+        self.servos.reset();
+        Ok(self.servos.set_setpoints(self.robot.joint_angles.clone())?)
+    }
+
+    /// Set the collision geometry of the links: one object for every link, or an array
+    /// with one per link
+    ///
+    /// Either `{ Capsules: { radius } }` (capsules following the links, the default with
+    /// a 5 cm radius) or `{ Shapes: [shape, ...] }` with shapes in the link frame:
+    /// `{ Sphere: { center, radius } }`, `{ Capsule: { start, end, radius } }` or
+    /// `{ Box: { center, rpy, half_extents } }` (points as `[x, y, z]`).
+    pub fn set_link_geometry(&mut self, geometry: JsValue) -> Result<(), JsValue> {
+        let links: Vec<LinkGeometry> = per_joint(geometry, self.robot.num_joints(), "link geometry")?;
+        let model = CollisionModel {
+            links,
+            obstacles: self.collision.obstacles.clone(),
+        };
+        model.validate(&self.robot)?;
 
-        self.robot.set_joint_angles(angle_vec);
+        self.collision = model;
         Ok(())
     }
 
+    /// Get the collision geometry of every link
+    pub fn get_link_geometry(&self) -> Result<JsValue, JsValue> {
+        Ok(to_js(&self.collision.links, "link geometry")?)
+    }
+
+    /// Set the static obstacles: an array of `{ name, shape }` with shapes (same layout
+    /// as `set_link_geometry`) in the world frame
+    pub fn set_obstacles(&mut self, obstacles: JsValue) -> Result<(), JsValue> {
+        let obstacles: Vec<Obstacle> = from_js(obstacles, "obstacles")?;
+        let model = CollisionModel {
+            links: self.collision.links.clone(),
+            obstacles,
+        };
+        model.validate(&self.robot)?;
+
+        self.collision = model;
+        Ok(())
+    }
+
+    /// Get the static obstacles
+    pub fn get_obstacles(&self) -> Result<JsValue, JsValue> {
+        Ok(to_js(&self.collision.obstacles, "obstacles")?)
+    }
+
+    /// Check the current configuration for self-collisions and obstacle collisions
+    ///
+    /// Returns `{ in_collision, pairs: [{ first, second, distance }], colliding_links,
+    /// min_distance, closest }` where bodies are `{ kind: "Link" | "Obstacle", index }`,
+    /// `distance` is the gap between two bodies (negative when they overlap, by the
+    /// penetration depth) and `min_distance` the smallest over all checked pairs.
+    pub fn check_collisions(&self) -> Result<JsValue, JsValue> {
+        let report = self.collision.check(&self.robot, &self.robot.joint_angles)?;
+
+        Ok(to_js(&report, "collision report")?)
+    }
+
+    /// Plan a collision-free joint-space motion from the current configuration to `goal`
+    ///
+    /// Uses RRT-Connect with the link geometry and obstacles of `check_collisions`, then
+    /// shortcuts and smooths the path. `options` is `{ seed, step_size, resolution,
+    /// max_iterations, shortcut_attempts, smoothing_passes, prismatic_range }` (all
+    /// optional; the same seed gives the same path). Returns `{ path, length, raw_length,
+    /// iterations, tree_size }`; `path` entries can be passed to `set_angles_array`.
+    pub fn plan_motion(&self, goal: JsValue, options: JsValue) -> Result<JsValue, JsValue> {
+        let goal: Vec<f64> = from_js(goal, "goal")?;
+        let options: PlannerOptions = if options.is_undefined() || options.is_null() {
+            PlannerOptions::default()
+        } else {
+            from_js(options, "planner options")?
+        };
+
+        let plan = plan_motion(&self.robot, &self.robot.joint_angles, &goal, &options, |q| {
+            self.collision.check(&self.robot, q).is_ok_and(|report| !report.in_collision)
+        })?;
+        Ok(to_js(&plan, "motion plan")?)
+    }
+
+    /// Select how out-of-range joint values are handled: "Clamp", "Reject" or "Wrap"
+    pub fn set_limit_policy(&mut self, policy: JsValue) -> Result<(), JsValue> {
+        self.robot.limit_policy = from_js::<LimitPolicy>(policy, "limit policy")?;
+        Ok(())
+    }
+
+    /// Check if every joint is within its position limits
+    pub fn is_within_limits(&self) -> bool {
+        self.robot.is_within_limits()
+    }
+
+    /// Signed distance of every joint to its nearest position limit (negative when outside)
+    pub fn distance_to_limits(&self) -> Result<JsValue, JsValue> {
+        Ok(to_js(&self.robot.distance_to_limits(), "limit distances")?)
+    }
+
+    /// Mount the robot on a pedestal: base pose as `xyz` `[x, y, z]` and `rpy` `[roll, pitch, yaw]`
+    ///
+    /// `rpy` is optional. Joint positions, frames, Jacobians and IK targets are in
+    /// the world frame afterwards.
+    pub fn set_base_frame(&mut self, xyz: JsValue, rpy: JsValue) -> Result<(), JsValue> {
+        let base = frame_from_js(xyz, rpy, "base frame")?;
+
+        Ok(self.robot.set_base_frame(base)?)
+    }
+
+    /// Attach a tool: tool center point in the last link frame as `xyz` and optional `rpy`
+    ///
+    /// The end-effector position, pose, Jacobian and IK all refer to the TCP afterwards.
+    pub fn set_tool_frame(&mut self, xyz: JsValue, rpy: JsValue) -> Result<(), JsValue> {
+        let tool = frame_from_js(xyz, rpy, "tool frame")?;
+
+        Ok(self.robot.set_tool_frame(tool)?)
+    }
+
+    /// Get the base pose in the world frame (same layout as `get_link_frames` entries)
+    pub fn get_base_frame(&self) -> Result<JsValue, JsValue> {
+        Ok(to_js(&LinkPose::from_transform(&self.robot.base), "base frame")?)
+    }
+
+    /// Get the tool pose in the last link frame (same layout as `get_link_frames` entries)
+    pub fn get_tool_frame(&self) -> Result<JsValue, JsValue> {
+        Ok(to_js(&LinkPose::from_transform(&self.robot.tool), "tool frame")?)
+    }
+
+    /// Get the current joint values (radians for revolute, meters for prismatic)
+    pub fn get_angles(&self) -> Result<JsValue, JsValue> {
+        Ok(to_js(&self.robot.joint_angles, "joint angles")?)
+    }
+
     /// Get the number of joints in the robot
     pub fn num_joints(&self) -> usize {
         self.robot.num_joints()
     }
 
-    pub fn get_joint_positions(&self) -> JsValue {
+    /// Get the 6×N geometric Jacobian as an array of 6 rows
+    ///
+    /// Rows 0-2 map joint velocities to linear velocity, rows 3-5 to angular velocity.
+    pub fn get_jacobian(&self) -> Result<JsValue, JsValue> {
+        let jacobian = jacobian(&self.robot);
+        let rows: Vec<Vec<f64>> = jacobian
+            .row_iter()
+            .map(|row| row.iter().copied().collect())
+            .collect();
+
+        Ok(to_js(&rows, "Jacobian")?)
+    }
+
+    /// Solve inverse kinematics for an end-effector target position `{ x, y, z }`
+    ///
+    /// Starts from the current joint values and applies the best solution found.
+    /// Returns `{ joint_values, converged, iterations, error }`.
+    pub fn solve_ik(&mut self, target: JsValue) -> Result<JsValue, JsValue> {
+        let point: Point3D = from_js(target, "IK target")?;
+        check_finite(&[point.x, point.y, point.z])?;
+
+        let solution = solve_ik(&self.robot, &IkTarget::Position(point), &IkOptions::default());
+        self.robot.set_joint_angles(solution.joint_values.clone())?;
+
+        Ok(to_js(&solution, "IK solution")?)
+    }
+
+    /// Closed-form IK for planar 2R/3R arms (does not change the robot state)
+    ///
+    /// `phi` is the end-effector orientation in radians, required for 3R arms.
+    /// Returns `{ reachable, solutions: [{ joint_values, elbow }] }` with every branch;
+    /// throws with code `UNREACHABLE` if the target is outside the workspace.
+    pub fn solve_planar_ik(&self, x: f64, y: f64, phi: Option<f64>) -> Result<JsValue, JsValue> {
+        let solutions = solve_planar_ik(&self.robot, x, y, phi)?;
+
+        Ok(to_js(&solutions, "IK solutions")?)
+    }
+
+    /// Sample the reachable workspace and compute its boundaries and reach statistics
+    ///
+    /// `options` is `{ sampling: { Grid: { steps } } | { Random: { samples, seed } },
+    /// alpha, prismatic_range }` (all optional; at most 2,000,000 samples, or 10,000
+    /// with `alpha`). Returns `{ points, hull_2d, alpha_shape: { points, triangles,
+    /// boundary, area }, hull_3d, statistics }`.
+    pub fn analyze_workspace(&self, options: JsValue) -> Result<JsValue, JsValue> {
+        let options: WorkspaceOptions = if options.is_undefined() || options.is_null() {
+            WorkspaceOptions::default()
+        } else {
+            from_js(options, "workspace options")?
+        };
+
+        let analysis = analyze_workspace(&self.robot, &options)?;
+        Ok(to_js(&analysis, "workspace analysis")?)
+    }
+
+    /// Manipulability measures at the current configuration
+    ///
+    /// `task_space` is "PlanarPosition", "Planar", "Position" or "Full" (optional, chosen
+    /// from the robot's mobility when omitted). Returns `{ task_space, singular_values,
+    /// manipulability, condition_number, min_singular_value }`.
+    pub fn get_manipulability(&self, task_space: JsValue) -> Result<JsValue, JsValue> {
+        let task_space = self.task_space(task_space)?;
+        let metrics = manipulability(&self.robot, task_space)?;
+
+        Ok(to_js(&metrics, "manipulability")?)
+    }
+
+    /// Linear velocity manipulability ellipsoid at the end-effector
+    ///
+    /// Returns `{ center, radii, axes, quaternion: { w, x, y, z } }` with the semi-axes
+    /// sorted from longest to shortest.
+    pub fn get_manipulability_ellipsoid(&self) -> Result<JsValue, JsValue> {
+        let ellipsoid = manipulability_ellipsoid(&self.robot)?;
+
+        Ok(to_js(&ellipsoid, "manipulability ellipsoid")?)
+    }
+
+    /// Check whether the current configuration is singular
+    ///
+    /// A direction counts as lost when its singular value is below `tolerance` (default
+    /// 1e-3) times the largest one. Returns `{ task_space, singular, singularities:
+    /// [{ kind: "Boundary" | "Interior", singular_value, direction, joints }] }`.
+    pub fn check_singularity(&self, tolerance: Option<f64>, task_space: JsValue) -> Result<JsValue, JsValue> {
+        let task_space = self.task_space(task_space)?;
+        let tolerance = tolerance.unwrap_or(DEFAULT_SINGULARITY_TOLERANCE);
+        let analysis = classify_singularity(&self.robot, task_space, tolerance)?;
+
+        Ok(to_js(&analysis, "singularity analysis")?)
+    }
+
+    /// Plan a joint trajectory from the current joint values through `waypoints`
+    ///
+    /// `waypoints` is an array of joint vectors, `options` is `{ profile: "Cubic" |
+    /// "Quintic" | "Trapezoidal" | "SCurve", default_velocity, default_acceleration,
+    /// min_segment_duration }` (all optional). The trajectory is kept for
+    /// `sample_trajectory` / `follow_trajectory`; returns `{ duration, waypoint_times }`.
+    pub fn plan_trajectory(&mut self, waypoints: JsValue, options: JsValue) -> Result<JsValue, JsValue> {
+        let targets: Vec<Vec<f64>> = from_js(waypoints, "waypoints")?;
+        let options: TrajectoryOptions = if options.is_undefined() || options.is_null() {
+            TrajectoryOptions::default()
+        } else {
+            from_js(options, "trajectory options")?
+        };
+
+        let mut waypoints = vec![self.robot.joint_angles.clone()];
+        waypoints.extend(targets);
+        let trajectory = plan_trajectory(&self.robot, &waypoints, &options)?;
+
+        let summary = TrajectorySummary {
+            duration: trajectory.duration,
+            waypoint_times: trajectory.waypoint_times(),
+        };
+        self.trajectory = Some(trajectory);
+
+        Ok(to_js(&summary, "trajectory summary")?)
+    }
+
+    /// Joint state of the planned trajectory at time `t` (seconds, clamped to its duration)
+    ///
+    /// Returns `{ time, positions, velocities, accelerations }` without moving the robot.
+    pub fn sample_trajectory(&self, t: f64) -> Result<JsValue, JsValue> {
+        check_finite(&[t])?;
+        let point = self.planned_trajectory()?.sample(t);
+
+        Ok(to_js(&point, "trajectory point")?)
+    }
+
+    /// Move the robot to the planned trajectory's state at time `t` (for animation)
+    ///
+    /// Returns the same `{ time, positions, velocities, accelerations }` as `sample_trajectory`.
+    pub fn follow_trajectory(&mut self, t: f64) -> Result<JsValue, JsValue> {
+        check_finite(&[t])?;
+        let point = self.planned_trajectory()?.sample(t);
+        self.robot.set_joint_angles(point.positions.clone())?;
+
+        Ok(to_js(&point, "trajectory point")?)
+    }
+
+    /// Plan a straight-line tool motion from the current pose to `target`
+    ///
+    /// `target` is `{ position: { x, y, z }, quaternion?: { w, x, y, z } }` (the current
+    /// orientation is kept when omitted), `options` is `{ resolution, angular_resolution,
+    /// track_orientation, singularity_tolerance, max_joint_step, ik }` (all optional).
+    /// Returns `{ length, positions, joint_path, issues: [{ kind, index, fraction, error }],
+    /// reachable }`; `joint_path` entries can be passed to `set_angles_array`.
+    pub fn plan_linear_path(&self, target: JsValue, options: JsValue) -> Result<JsValue, JsValue> {
+        let target: CartesianTarget = from_js(target, "path target")?;
+        let path = linear_path(&self.robot, &target);
+
+        self.plan_cartesian(&path, options)
+    }
+
+    /// Plan a circular-arc tool motion from the current pose through `via` to `target`
+    ///
+    /// `via` is a point `{ x, y, z }` on the arc; `target`, `options` and the result are
+    /// the same as for `plan_linear_path`.
+    pub fn plan_arc_path(&self, via: JsValue, target: JsValue, options: JsValue) -> Result<JsValue, JsValue> {
+        let via: Point3D = from_js(via, "arc via point")?;
+        let target: CartesianTarget = from_js(target, "path target")?;
+        check_finite(&[via.x, via.y, via.z])?;
+        let path = arc_path(&self.robot, via, &target);
+
+        self.plan_cartesian(&path, options)
+    }
+
+    pub fn get_joint_positions(&self) -> Result<JsValue, JsValue> {
         let positions = forward_kinematics(&self.robot);
 
         // Convert Vec<JointPosition3D> to JavaScript array
-        serde_wasm_bindgen::to_value(&positions)
-            .unwrap_or_else(|_| JsValue::NULL)
+        Ok(to_js(&positions, "joint positions")?)
+    }
+
+    /// Get the full pose of the base, every link frame and the end-effector
+    ///
+    /// Each entry is `{ position, quaternion: { w, x, y, z }, rpy: [roll, pitch, yaw], matrix }`
+    /// where `matrix` is the 4x4 homogeneous transform in row-major order.
+    pub fn get_link_frames(&self) -> Result<JsValue, JsValue> {
+        let poses = link_poses(&self.robot);
+
+        Ok(to_js(&poses, "link frames")?)
+    }
+
+    /// Get the full end-effector pose (same layout as `get_link_frames` entries)
+    pub fn get_end_effector_pose(&self) -> Result<JsValue, JsValue> {
+        match link_poses(&self.robot).last() {
+            Some(pose) => Ok(to_js(pose, "end-effector pose")?),
+            None => Ok(JsValue::NULL),
+        }
     }
 
-    pub fn get_end_effector_position(&self) -> JsValue {
+    pub fn get_end_effector_position(&self) -> Result<JsValue, JsValue> {
         let positions = forward_kinematics(&self.robot);
 
-        if let Some(end_effector) = positions.last() {
-            serde_wasm_bindgen::to_value(&end_effector)
-                .unwrap_or_else(|_| JsValue::NULL)
+        match positions.last() {
+            Some(end_effector) => Ok(to_js(end_effector, "end-effector position")?),
+            None => Ok(JsValue::NULL),
+        }
+    }
+
+    /// Forward kinematics for a batch of configurations, as a flat `Float64Array`
+    ///
+    /// `joint_values` holds N x DOF values (configuration after configuration).
+    /// The result holds N x (DOF+1) x 3 values: x, y, z of the base, every link
+    /// frame and the end-effector, in the same order as `get_joint_positions`.
+    /// Joint values are used as given (no limit policy) and the robot's current
+    /// angles are left untouched.
+    ///
+    /// The returned array is a view into WASM memory, reused by the next batch
+    /// call: read it (or `slice()` it to keep a copy) before calling into the
+    /// simulator again.
+    pub fn batch_joint_positions(&mut self, joint_values: &js_sys::Float64Array) -> Result<js_sys::Float64Array, JsValue> {
+        Ok(self.batch(joint_values, BatchOutput::Positions)?)
+    }
+
+    /// Link frames for a batch of configurations, as a flat `Float64Array`
+    ///
+    /// Same as `batch_joint_positions`, but every frame is written as its 4x4
+    /// homogeneous transform in row-major order (N x (DOF+1) x 16 values, the
+    /// `matrix` of `get_link_frames`). The same view caveat applies.
+    pub fn batch_link_frames(&mut self, joint_values: &js_sys::Float64Array) -> Result<js_sys::Float64Array, JsValue> {
+        Ok(self.batch(joint_values, BatchOutput::Frames)?)
+    }
+}
+
+impl RobotSimulator {
+    fn from_robot(robot: RobotArm) -> Self {
+        RobotSimulator {
+            trajectory: None,
+            batch_input: Vec::new(),
+            batch_output: Vec::new(),
+            gravity: STANDARD_GRAVITY,
+            simulation: Simulation::new(robot.num_joints()),
+            controller: None,
+            servos: ServoSimulation::new(&robot),
+            collision: CollisionModel::for_robot(&robot, DEFAULT_LINK_RADIUS),
+            robot,
+        }
+    }
+
+    /// Runs batch forward kinematics through the reused input and output buffers
+    fn batch(&mut self, joint_values: &js_sys::Float64Array, output: BatchOutput) -> Result<js_sys::Float64Array, RoboticsError> {
+        self.batch_input.resize(joint_values.length() as usize, 0.0);
+        joint_values.copy_to(&mut self.batch_input);
+        batch_forward_kinematics(&self.robot, &self.batch_input, output, &mut self.batch_output)?;
+
+        // SAFETY: the view is created last, nothing allocates before it is
+        // handed to JS, and it is documented as invalidated by the next call
+        Ok(unsafe { js_sys::Float64Array::view(&self.batch_output) })
+    }
+
+    /// The selected controller, or an error if none was selected
+    fn model_controller(&mut self) -> Result<&mut ModelController, RoboticsError> {
+        self.controller
+            .as_mut()
+            .ok_or_else(|| RoboticsError::Unsupported("no controller selected (see set_controller)".to_string()))
+    }
+
+    /// The last planned trajectory, or an error if none was planned
+    fn planned_trajectory(&self) -> Result<&JointTrajectory, RoboticsError> {
+        self.trajectory
+            .as_ref()
+            .ok_or_else(|| RoboticsError::Unsupported("no trajectory has been planned".to_string()))
+    }
+
+    /// Convert a Cartesian path to joint values (options from JS, defaults when omitted)
+    fn plan_cartesian(&self, path: &CartesianPath, options: JsValue) -> Result<JsValue, JsValue> {
+        let options: CartesianOptions = if options.is_undefined() || options.is_null() {
+            CartesianOptions::default()
         } else {
-            JsValue::NULL
+            from_js(options, "path options")?
+        };
+
+        let plan = plan_cartesian_path(&self.robot, path, &options)?;
+        Ok(to_js(&plan, "Cartesian plan")?)
+    }
+
+    /// Task space from JS, or the one matching the robot when omitted
+    fn task_space(&self, task_space: JsValue) -> Result<TaskSpace, RoboticsError> {
+        if task_space.is_undefined() || task_space.is_null() {
+            Ok(TaskSpace::for_robot(&self.robot))
+        } else {
+            from_js(task_space, "task space")
         }
     }
 }
+
+/// Timing of a planned trajectory returned to JS
+#[derive(Serialize)]
+struct TrajectorySummary {
+    duration: f64,
+    waypoint_times: Vec<f64>,
+}
+
+/// Frame from a JS `[x, y, z]` translation and an optional `[roll, pitch, yaw]` rotation
+fn frame_from_js(xyz: JsValue, rpy: JsValue, what: &str) -> Result<Transform3D, RoboticsError> {
+    let xyz: [f64; 3] = from_js(xyz, what)?;
+    let rpy: [f64; 3] = if rpy.is_undefined() || rpy.is_null() {
+        [0.0; 3]
+    } else {
+        from_js(rpy, what)?
+    };
+    check_finite(&xyz)?;
+    check_finite(&rpy)?;
+
+    Ok(Transform3D::from_xyz_rpy(xyz, rpy))
+}
+
+/// One value for every joint, given either as a single value or as an array with one per joint
+fn per_joint<T: DeserializeOwned + Clone>(value: JsValue, num_joints: usize, what: &str) -> Result<Vec<T>, RoboticsError> {
+    if js_sys::Array::is_array(&value) {
+        from_js(value, what)
+    } else {
+        Ok(vec![from_js(value, what)?; num_joints])
+    }
+}
+
+/// Deserialize a JS value, reporting failures as `RoboticsError::Parse`
+fn from_js<T: DeserializeOwned>(value: JsValue, what: &str) -> Result<T, RoboticsError> {
+    serde_wasm_bindgen::from_value(value).map_err(|e| RoboticsError::Parse(format!("{}: {}", what, e)))
+}
+
+/// Serialize a value to JS, reporting failures as `RoboticsError::Serialization`
+fn to_js<T: Serialize + ?Sized>(value: &T, what: &str) -> Result<JsValue, RoboticsError> {
+    serde_wasm_bindgen::to_value(value).map_err(|e| RoboticsError::Serialization(format!("{}: {}", what, e)))
+}
@@ -0,0 +1,256 @@
+use crate::dh_parameters::JointType;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+use std::fmt;
+
+/// Limits of a single joint
+///
+/// Position bounds are in radians (revolute) or meters (prismatic); velocity,
+/// acceleration and effort are magnitudes. Unbounded quantities are infinite.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JointLimits {
+    pub lower: f64,
+    pub upper: f64,
+    pub velocity: f64,
+    pub acceleration: f64,
+    pub effort: f64,
+}
+
+impl Default for JointLimits {
+    fn default() -> Self {
+        JointLimits::unbounded()
+    }
+}
+
+impl JointLimits {
+    /// No limits on any quantity
+    pub fn unbounded() -> Self {
+        JointLimits {
+            lower: f64::NEG_INFINITY,
+            upper: f64::INFINITY,
+            velocity: f64::INFINITY,
+            acceleration: f64::INFINITY,
+            effort: f64::INFINITY,
+        }
+    }
+
+    /// Position bounds only
+    pub fn position(lower: f64, upper: f64) -> Self {
+        JointLimits {
+            lower,
+            upper,
+            ..JointLimits::unbounded()
+        }
+    }
+
+    /// Check that the limits describe a usable range
+    ///
+    /// Position bounds may be infinite but not NaN, with `lower <= upper`;
+    /// velocity, acceleration and effort must be positive (or infinite).
+    pub fn validate(&self) -> Result<(), String> {
+        if self.lower.is_nan() || self.upper.is_nan() || self.lower == f64::INFINITY || self.upper == f64::NEG_INFINITY {
+            return Err(format!("invalid position bounds [{}, {}]", self.lower, self.upper));
+        }
+        if self.lower > self.upper {
+            return Err(format!("lower {} is above upper {}", self.lower, self.upper));
+        }
+        for (field, value) in [
+            ("velocity", self.velocity),
+            ("acceleration", self.acceleration),
+            ("effort", self.effort),
+        ] {
+            if value.is_nan() || value <= 0.0 {
+                return Err(format!("{} must be positive, got {}", field, value));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the position range is bounded on both sides
+    pub fn is_bounded(&self) -> bool {
+        self.lower.is_finite() && self.upper.is_finite()
+    }
+
+    /// Whether a position lies within [lower, upper]
+    pub fn contains(&self, value: f64) -> bool {
+        value >= self.lower && value <= self.upper
+    }
+
+    /// Signed distance to the nearest position bound (negative when outside)
+    pub fn distance(&self, value: f64) -> f64 {
+        (value - self.lower).min(self.upper - value)
+    }
+
+    /// Clamp a position into [lower, upper]
+    pub fn clamp(&self, value: f64) -> f64 {
+        value.max(self.lower).min(self.upper)
+    }
+
+    /// Saturate a torque or force at the effort limit
+    ///
+    /// Never panics, unlike `f64::clamp`: a NaN limit leaves the value unchanged.
+    pub fn clamp_effort(&self, effort: f64) -> f64 {
+        effort.max(-self.effort).min(self.effort)
+    }
+
+    /// Whether a velocity magnitude is allowed
+    pub fn allows_velocity(&self, velocity: f64) -> bool {
+        velocity.abs() <= self.velocity
+    }
+}
+
+/// How out-of-range joint values are handled by `RobotArm::set_joint_angles`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LimitPolicy {
+    /// Clamp every value into its range
+    #[default]
+    Clamp,
+    /// Leave the robot unchanged and report the first violation
+    Reject,
+    /// Wrap revolute joints by multiples of 2π into their range (into (-π, π] for
+    /// continuous joints); values that cannot be wrapped into range are clamped
+    Wrap,
+}
+
+impl LimitPolicy {
+    /// Apply the policy to a single joint value
+    pub fn apply(&self, joint: usize, value: f64, limits: &JointLimits, joint_type: JointType) -> Result<f64, LimitViolation> {
+        match self {
+            LimitPolicy::Clamp => Ok(limits.clamp(value)),
+            LimitPolicy::Reject if limits.contains(value) => Ok(value),
+            LimitPolicy::Reject => Err(LimitViolation {
+                joint,
+                value,
+                lower: limits.lower,
+                upper: limits.upper,
+            }),
+            LimitPolicy::Wrap => match joint_type {
+                JointType::Revolute => Ok(limits.clamp(wrap_into(value, limits))),
+                JointType::Prismatic => Ok(limits.clamp(value)),
+            },
+        }
+    }
+}
+
+/// A joint value outside its position limits
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LimitViolation {
+    pub joint: usize,
+    pub value: f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+impl fmt::Display for LimitViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "joint {} value {} is outside its limits [{}, {}]",
+            self.joint, self.value, self.lower, self.upper
+        )
+    }
+}
+
+/// Equivalent angle (value + 2πk) inside the limits, or the one closest to the range
+fn wrap_into(value: f64, limits: &JointLimits) -> f64 {
+    if !value.is_finite() {
+        return value;
+    }
+
+    // Reference angle: wrap into (-π, π], then shift by whole turns towards the range
+    let wrapped = value - 2.0 * PI * ((value - PI) / (2.0 * PI)).ceil();
+    let shifted = if wrapped < limits.lower {
+        wrapped + 2.0 * PI * ((limits.lower - wrapped) / (2.0 * PI)).ceil()
+    } else if wrapped > limits.upper {
+        wrapped - 2.0 * PI * ((wrapped - limits.upper) / (2.0 * PI)).ceil()
+    } else {
+        wrapped
+    };
+
+    if limits.distance(shifted) >= limits.distance(wrapped) {
+        shifted
+    } else {
+        wrapped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f64 = 1e-12;
+
+    #[test]
+    fn test_unbounded_limits_accept_everything() {
+        let limits = JointLimits::unbounded();
+        assert!(!limits.is_bounded());
+        assert!(limits.contains(1e9));
+        assert_eq!(limits.distance(3.0), f64::INFINITY);
+        assert!(limits.allows_velocity(1e6));
+    }
+
+    #[test]
+    fn test_distance_and_clamp() {
+        let limits = JointLimits::position(-1.0, 2.0);
+        assert!((limits.distance(0.0) - 1.0).abs() < EPSILON);
+        assert!((limits.distance(1.5) - 0.5).abs() < EPSILON);
+        assert!((limits.distance(3.0) + 1.0).abs() < EPSILON);
+        assert_eq!(limits.clamp(-5.0), -1.0);
+        assert_eq!(limits.clamp(5.0), 2.0);
+
+        let effort = JointLimits { effort: 3.0, ..limits };
+        assert_eq!(effort.clamp_effort(-5.0), -3.0);
+        assert_eq!(effort.clamp_effort(1.0), 1.0);
+        assert_eq!(JointLimits { effort: f64::NAN, ..limits }.clamp_effort(5.0), 5.0);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(JointLimits::unbounded().validate().is_ok());
+        assert!(JointLimits::position(-1.0, 1.0).validate().is_ok());
+        assert!(JointLimits::position(0.5, 0.5).validate().is_ok());
+
+        assert!(JointLimits::position(1.0, -1.0).validate().is_err());
+        assert!(JointLimits::position(f64::NAN, 1.0).validate().is_err());
+        assert!(JointLimits::position(f64::INFINITY, f64::INFINITY).validate().is_err());
+        assert!(JointLimits::position(f64::NEG_INFINITY, f64::NEG_INFINITY).validate().is_err());
+        for value in [0.0, -1.0, f64::NAN] {
+            let unbounded = JointLimits::unbounded();
+            assert!(JointLimits { velocity: value, ..unbounded }.validate().is_err());
+            assert!(JointLimits { acceleration: value, ..unbounded }.validate().is_err());
+            assert!(JointLimits { effort: value, ..unbounded }.validate().is_err());
+        }
+    }
+
+    #[test]
+    fn test_reject_policy() {
+        let limits = JointLimits::position(-1.0, 1.0);
+        assert_eq!(LimitPolicy::Reject.apply(0, 0.5, &limits, JointType::Revolute), Ok(0.5));
+
+        let violation = LimitPolicy::Reject.apply(2, 1.5, &limits, JointType::Revolute).unwrap_err();
+        assert_eq!(violation.joint, 2);
+        assert_eq!(violation.value, 1.5);
+    }
+
+    #[test]
+    fn test_wrap_policy() {
+        // Continuous joint: wrapped into (-π, π]
+        let continuous = JointLimits::unbounded();
+        let wrapped = LimitPolicy::Wrap.apply(0, 3.0 * PI / 2.0, &continuous, JointType::Revolute).unwrap();
+        assert!((wrapped + PI / 2.0).abs() < EPSILON);
+
+        // Range [0, 2π]: -π/2 is equivalent to 3π/2
+        let positive = JointLimits::position(0.0, 2.0 * PI);
+        let wrapped = LimitPolicy::Wrap.apply(0, -PI / 2.0, &positive, JointType::Revolute).unwrap();
+        assert!((wrapped - 3.0 * PI / 2.0).abs() < EPSILON);
+
+        // No equivalent angle in range: clamped
+        let narrow = JointLimits::position(-0.5, 0.5);
+        assert_eq!(LimitPolicy::Wrap.apply(0, 1.0, &narrow, JointType::Revolute), Ok(0.5));
+
+        // Prismatic joints are never wrapped
+        let prismatic = JointLimits::position(0.0, 0.4);
+        assert_eq!(LimitPolicy::Wrap.apply(0, 0.6, &prismatic, JointType::Prismatic), Ok(0.4));
+    }
+}
@@ -0,0 +1,439 @@
+use crate::error::RoboticsError;
+use crate::geometry3d::{Point3D, Quaternion, Transform3D};
+use crate::jacobian::jacobian;
+use crate::kinematics::{end_effector_transform, joint_frames};
+use crate::robot::RobotArm;
+use nalgebra::{DMatrix, Matrix3, SymmetricEigen, Vector3};
+use serde::{Deserialize, Serialize};
+
+/// Default threshold on σ_min / σ_max below which a configuration counts as singular
+pub const DEFAULT_SINGULARITY_TOLERANCE: f64 = 1e-3;
+
+/// Jacobian entries below this are treated as structurally zero (planar detection)
+const PLANAR_TOLERANCE: f64 = 1e-9;
+
+/// Share of a joint in a degenerate joint motion above which it counts as involved
+const INVOLVEMENT_THRESHOLD: f64 = 0.1;
+
+/// Task-space directions (rows of the geometric Jacobian) the metrics are computed over
+///
+/// The full Jacobian of an arm with fewer joints than task directions is always
+/// rank-deficient, and for planar arms the joint rotations hide the loss of
+/// linear mobility, so the metrics are taken over the directions the arm can
+/// actually control.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TaskSpace {
+    /// Linear velocity in the XY plane (vx, vy)
+    PlanarPosition,
+    /// Planar motion (vx, vy, ωz)
+    Planar,
+    /// Linear velocity (vx, vy, vz)
+    Position,
+    /// Linear and angular velocity (vx, vy, vz, ωx, ωy, ωz)
+    Full,
+}
+
+impl TaskSpace {
+    /// Task space matching the robot's mobility at its current configuration
+    ///
+    /// Planar arms use (vx, vy) with up to two joints and (vx, vy, ωz) otherwise;
+    /// spatial arms use the linear velocity below six joints and the full twist from six.
+    pub fn for_robot(robot: &RobotArm) -> Self {
+        let j = jacobian(robot);
+        let planar = [2, 3, 4].iter().all(|&row| j.row(row).amax() < PLANAR_TOLERANCE);
+
+        match (planar, robot.num_joints()) {
+            (true, n) if n <= 2 => TaskSpace::PlanarPosition,
+            (true, _) => TaskSpace::Planar,
+            (false, n) if n < 6 => TaskSpace::Position,
+            (false, _) => TaskSpace::Full,
+        }
+    }
+
+    /// Rows of the geometric Jacobian spanned by this task space
+    pub fn rows(&self) -> &'static [usize] {
+        match self {
+            TaskSpace::PlanarPosition => &[0, 1],
+            TaskSpace::Planar => &[0, 1, 5],
+            TaskSpace::Position => &[0, 1, 2],
+            TaskSpace::Full => &[0, 1, 2, 3, 4, 5],
+        }
+    }
+}
+
+/// Dexterity measures derived from the singular values of the task Jacobian
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManipulabilityMetrics {
+    /// Task space the metrics were computed over
+    pub task_space: TaskSpace,
+    /// Singular values of the task Jacobian, descending, one per task direction
+    /// (zero for the directions beyond the number of joints)
+    pub singular_values: Vec<f64>,
+    /// Yoshikawa manipulability w = sqrt(det(J Jᵀ)), the product of the singular values
+    /// (zero when the task space has more directions than the arm has joints)
+    pub manipulability: f64,
+    /// σ_max / σ_min (infinite at a singularity)
+    pub condition_number: f64,
+    /// Smallest singular value (distance to the nearest singularity)
+    pub min_singular_value: f64,
+}
+
+/// Velocity manipulability ellipsoid of the end-effector position
+///
+/// The set of linear velocities reachable with unit-norm joint velocities:
+/// radii are the singular values of the linear Jacobian J_v and the axes the
+/// eigenvectors of J_v J_vᵀ.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManipulabilityEllipsoid {
+    /// End-effector position (center of the ellipsoid)
+    pub center: Point3D,
+    /// Semi-axis lengths, descending
+    pub radii: [f64; 3],
+    /// Unit direction of each semi-axis in the base frame (right-handed)
+    pub axes: [[f64; 3]; 3],
+    /// Orientation of the frame whose X, Y, Z axes are `axes`
+    pub quaternion: Quaternion,
+}
+
+/// Kind of a singular configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SingularityKind {
+    /// The arm is fully stretched or folded: the end-effector sits on the workspace
+    /// boundary and cannot move along the line to the first joint
+    Boundary,
+    /// Inside the workspace, e.g. aligned joint axes or a wrist singularity
+    Interior,
+}
+
+/// One lost direction of motion at a singular configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Singularity {
+    pub kind: SingularityKind,
+    /// Singular value of the lost direction
+    pub singular_value: f64,
+    /// Unit task-space direction the end-effector can no longer move along
+    /// (ordered as the rows of the task space)
+    pub direction: Vec<f64>,
+    /// Joints whose motion degenerates (large entries of the right singular vector)
+    pub joints: Vec<usize>,
+}
+
+/// Result of a singularity check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SingularityAnalysis {
+    /// Task space the check was performed in
+    pub task_space: TaskSpace,
+    /// Whether σ_min / σ_max is below the tolerance
+    pub singular: bool,
+    /// Every lost direction (empty when not singular)
+    pub singularities: Vec<Singularity>,
+}
+
+/// Computes the manipulability measures of the robot at its current configuration
+pub fn manipulability(robot: &RobotArm, task_space: TaskSpace) -> Result<ManipulabilityMetrics, RoboticsError> {
+    let singular_values = task_jacobian(robot, task_space)?.singular_values();
+
+    let max = singular_values.max();
+    let min = singular_values.min();
+
+    Ok(ManipulabilityMetrics {
+        task_space,
+        singular_values: singular_values.iter().copied().collect(),
+        manipulability: singular_values.product(),
+        condition_number: if min > 0.0 { max / min } else { f64::INFINITY },
+        min_singular_value: min,
+    })
+}
+
+/// Computes the linear velocity manipulability ellipsoid at the end-effector
+pub fn manipulability_ellipsoid(robot: &RobotArm) -> Result<ManipulabilityEllipsoid, RoboticsError> {
+    let linear = task_jacobian(robot, TaskSpace::Position)?;
+    let product = &linear * linear.transpose();
+    let eigen = SymmetricEigen::new(Matrix3::from_iterator(product.iter().copied()));
+
+    let mut order = [0, 1, 2];
+    order.sort_by(|&a, &b| eigen.eigenvalues[b].total_cmp(&eigen.eigenvalues[a]));
+
+    let mut columns: [Vector3<f64>; 3] = order.map(|i| eigen.eigenvectors.column(i).into_owned());
+    // Keep the axes a proper rotation so the quaternion is well defined
+    if columns[0].cross(&columns[1]).dot(&columns[2]) < 0.0 {
+        columns[2] = -columns[2];
+    }
+    let rotation = Matrix3::from_columns(&columns);
+
+    Ok(ManipulabilityEllipsoid {
+        center: end_effector_transform(robot).position(),
+        radii: order.map(|i| eigen.eigenvalues[i].max(0.0).sqrt()),
+        axes: columns.map(|axis| [axis.x, axis.y, axis.z]),
+        quaternion: Transform3D::from_parts(&rotation, &Vector3::zeros()).quaternion(),
+    })
+}
+
+/// Checks whether the robot is at (or near) a singularity and classifies each lost direction
+///
+/// A direction is lost when its singular value is below `tolerance` * σ_max.
+/// It is a boundary singularity when the lost motion is mostly linear and
+/// points along the line from the first joint to the end-effector, and an
+/// interior singularity otherwise. When the task space has more directions than
+/// the arm has joints, the directions no joint motion can produce are always lost.
+pub fn classify_singularity(
+    robot: &RobotArm,
+    task_space: TaskSpace,
+    tolerance: f64,
+) -> Result<SingularityAnalysis, RoboticsError> {
+    if !(tolerance >= 0.0 && tolerance.is_finite()) {
+        return Err(RoboticsError::InvalidArgument(format!(
+            "singularity tolerance must be non-negative, got {}",
+            tolerance
+        )));
+    }
+
+    let svd = task_jacobian(robot, task_space)?.svd(true, true);
+    let (u, v_t) = match (&svd.u, &svd.v_t) {
+        (Some(u), Some(v_t)) => (u, v_t),
+        _ => unreachable!("SVD was computed with U and Vᵀ"),
+    };
+    let threshold = tolerance * svd.singular_values.max();
+
+    let reach = radial_direction(robot);
+    let rows = task_space.rows();
+    let num_joints = robot.num_joints();
+
+    let singularities: Vec<Singularity> = svd
+        .singular_values
+        .iter()
+        .enumerate()
+        .filter(|&(_, &sigma)| sigma <= threshold)
+        .map(|(k, &sigma)| {
+            let direction = u.column(k);
+            let joint_motion = v_t.row(k);
+
+            // Linear part of the lost task direction
+            let mut linear = Vector3::zeros();
+            for (&row, &value) in rows.iter().zip(direction.iter()) {
+                if row < 3 {
+                    linear[row] = value;
+                }
+            }
+
+            let boundary = reach.is_some_and(|reach| {
+                let linear_norm = linear.norm();
+                linear_norm >= 0.9 && linear.dot(&reach).abs() >= 0.9 * linear_norm
+            });
+
+            Singularity {
+                kind: if boundary {
+                    SingularityKind::Boundary
+                } else {
+                    SingularityKind::Interior
+                },
+                singular_value: sigma,
+                direction: direction.iter().copied().collect(),
+                joints: joint_motion
+                    .iter()
+                    .take(num_joints)
+                    .enumerate()
+                    .filter(|&(_, value)| value.abs() >= INVOLVEMENT_THRESHOLD)
+                    .map(|(i, _)| i)
+                    .collect(),
+            }
+        })
+        .collect();
+
+    Ok(SingularityAnalysis {
+        task_space,
+        singular: !singularities.is_empty(),
+        singularities,
+    })
+}
+
+/// Rows of the geometric Jacobian selected by the task space
+///
+/// Padded with zero columns up to the number of rows, so the SVD yields one
+/// singular value and left singular vector per task direction: the directions
+/// the joints cannot span get a zero singular value.
+fn task_jacobian(robot: &RobotArm, task_space: TaskSpace) -> Result<DMatrix<f64>, RoboticsError> {
+    let n = robot.num_joints();
+    if n == 0 {
+        return Err(RoboticsError::Unsupported("manipulability of a robot without joints".to_string()));
+    }
+
+    let rows = task_space.rows();
+    let selected = jacobian(robot).select_rows(rows);
+    Ok(selected.resize_horizontally(n.max(rows.len()), 0.0))
+}
+
+/// Unit direction from the first joint to the end-effector (None if they coincide)
+fn radial_direction(robot: &RobotArm) -> Option<Vector3<f64>> {
+    let first = joint_frames(robot).first()?.position();
+    let end = end_effector_transform(robot).position();
+
+    Vector3::new(end.x - first.x, end.y - first.y, end.z - first.z).try_normalize(1e-9)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dh_parameters::DHParameter;
+    use std::f64::consts::PI;
+
+    const EPSILON: f64 = 1e-9;
+
+    fn planar_2r(q1: f64, q2: f64) -> RobotArm {
+        let mut robot = RobotArm::planar(vec![2.0, 1.5]);
+        robot.set_joint_angles(vec![q1, q2]).unwrap();
+        robot
+    }
+
+    #[test]
+    fn test_task_space_selection() {
+        assert_eq!(TaskSpace::for_robot(&planar_2r(0.3, 0.4)), TaskSpace::PlanarPosition);
+        assert_eq!(TaskSpace::for_robot(&RobotArm::planar(vec![1.0, 1.0, 1.0])), TaskSpace::Planar);
+
+        let spatial = RobotArm::from_dh_params(vec![
+            DHParameter::revolute(0.0, PI / 2.0, 0.5, 0.0),
+            DHParameter::revolute(1.0, 0.0, 0.0, 0.0),
+            DHParameter::revolute(0.8, 0.0, 0.0, 0.0),
+        ]);
+        assert_eq!(TaskSpace::for_robot(&spatial), TaskSpace::Position);
+    }
+
+    #[test]
+    fn test_planar_2r_yoshikawa_closed_form() {
+        // For a planar 2R arm w = l1 l2 |sin θ2|
+        let q2: f64 = 0.9;
+        let metrics = manipulability(&planar_2r(0.4, q2), TaskSpace::PlanarPosition).unwrap();
+
+        assert_eq!(metrics.singular_values.len(), 2);
+        assert!((metrics.manipulability - 2.0 * 1.5 * q2.sin()).abs() < EPSILON);
+        assert!((metrics.min_singular_value - metrics.singular_values[1]).abs() < EPSILON);
+        assert!(
+            (metrics.condition_number - metrics.singular_values[0] / metrics.singular_values[1]).abs() < EPSILON
+        );
+    }
+
+    #[test]
+    fn test_stretched_arm_is_boundary_singularity() {
+        let robot = planar_2r(0.3, 0.0);
+        let metrics = manipulability(&robot, TaskSpace::PlanarPosition).unwrap();
+        assert!(metrics.manipulability < EPSILON);
+        assert!(metrics.condition_number > 1e9);
+
+        let analysis = classify_singularity(&robot, TaskSpace::PlanarPosition, DEFAULT_SINGULARITY_TOLERANCE).unwrap();
+        assert!(analysis.singular);
+        assert_eq!(analysis.singularities.len(), 1);
+
+        let singularity = &analysis.singularities[0];
+        assert_eq!(singularity.kind, SingularityKind::Boundary);
+        assert_eq!(singularity.joints, vec![0, 1]);
+        // The lost direction is radial: along (cos 0.3, sin 0.3)
+        let radial = singularity.direction[0] * 0.3f64.cos() + singularity.direction[1] * 0.3f64.sin();
+        assert!((radial.abs() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_folded_arm_is_boundary_singularity() {
+        let analysis =
+            classify_singularity(&planar_2r(0.0, PI), TaskSpace::PlanarPosition, DEFAULT_SINGULARITY_TOLERANCE)
+                .unwrap();
+
+        assert!(analysis.singular);
+        assert_eq!(analysis.singularities[0].kind, SingularityKind::Boundary);
+    }
+
+    #[test]
+    fn test_regular_configuration_is_not_singular() {
+        let analysis =
+            classify_singularity(&planar_2r(0.3, 1.2), TaskSpace::PlanarPosition, DEFAULT_SINGULARITY_TOLERANCE)
+                .unwrap();
+
+        assert!(!analysis.singular);
+        assert!(analysis.singularities.is_empty());
+    }
+
+    #[test]
+    fn test_shoulder_singularity_is_interior() {
+        // Elbow arm with the end-effector on the first joint axis: it cannot move sideways
+        let mut robot = RobotArm::from_dh_params(vec![
+            DHParameter::revolute(0.0, PI / 2.0, 0.5, 0.0),
+            DHParameter::revolute(1.0, 0.0, 0.0, 0.0),
+            DHParameter::revolute(0.8, 0.0, 0.0, 0.0),
+        ]);
+        let q2 = PI / 3.0;
+        let q3 = (-q2.cos() / 0.8).acos() - q2;
+        robot.set_joint_angles(vec![0.0, q2, q3]).unwrap();
+
+        let analysis = classify_singularity(&robot, TaskSpace::Position, DEFAULT_SINGULARITY_TOLERANCE).unwrap();
+
+        assert!(analysis.singular);
+        assert_eq!(analysis.singularities.len(), 1);
+        let singularity = &analysis.singularities[0];
+        assert_eq!(singularity.kind, SingularityKind::Interior);
+        assert_eq!(singularity.joints, vec![0]);
+        assert!((singularity.direction[1].abs() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_ellipsoid_matches_singular_values() {
+        let robot = planar_2r(0.4, 0.9);
+        let ellipsoid = manipulability_ellipsoid(&robot).unwrap();
+        let metrics = manipulability(&robot, TaskSpace::PlanarPosition).unwrap();
+
+        assert!((ellipsoid.radii[0] - metrics.singular_values[0]).abs() < EPSILON);
+        assert!((ellipsoid.radii[1] - metrics.singular_values[1]).abs() < EPSILON);
+        // Planar arm: flat ellipsoid, the degenerate axis is Z
+        assert!(ellipsoid.radii[2].abs() < 1e-6);
+        assert!((ellipsoid.axes[2][2].abs() - 1.0).abs() < EPSILON);
+
+        let end = end_effector_transform(&robot).position();
+        assert!((ellipsoid.center.x - end.x).abs() < EPSILON);
+        assert!((ellipsoid.center.y - end.y).abs() < EPSILON);
+
+        // The quaternion encodes the same axes
+        let x_axis = ellipsoid.quaternion.to_rotation_matrix() * Vector3::x();
+        for (row, value) in ellipsoid.axes[0].iter().enumerate() {
+            assert!((x_axis[row] - value).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_task_space_larger_than_joint_space() {
+        // A planar 2R arm cannot move along 4 of the 6 spatial directions at all
+        let robot = planar_2r(0.4, 0.9);
+        let metrics = manipulability(&robot, TaskSpace::Full).unwrap();
+
+        assert_eq!(metrics.singular_values.len(), 6);
+        assert!(metrics.manipulability.abs() < EPSILON);
+        assert!(metrics.min_singular_value.abs() < EPSILON);
+        assert!(metrics.condition_number.is_infinite());
+
+        let analysis = classify_singularity(&robot, TaskSpace::Full, DEFAULT_SINGULARITY_TOLERANCE).unwrap();
+        assert!(analysis.singular);
+        assert_eq!(analysis.singularities.len(), 4);
+        for singularity in &analysis.singularities {
+            assert!(singularity.singular_value.abs() < EPSILON);
+            assert!(singularity.joints.is_empty());
+        }
+
+        // Every task direction is accounted for: reachable and lost directions span the space
+        let mut lost_z = 0.0;
+        for singularity in &analysis.singularities {
+            lost_z += singularity.direction[2].powi(2);
+        }
+        assert!((lost_z - 1.0).abs() < 1e-9);
+
+        // With as many directions as joints the metrics are unchanged
+        let planar = manipulability(&robot, TaskSpace::PlanarPosition).unwrap();
+        assert_eq!(planar.singular_values.len(), 2);
+        assert!(planar.manipulability > 0.0);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let robot = RobotArm::new(vec![]);
+        assert_eq!(manipulability(&robot, TaskSpace::Position).unwrap_err().code(), "UNSUPPORTED");
+
+        let result = classify_singularity(&planar_2r(0.0, 0.0), TaskSpace::PlanarPosition, -1.0);
+        assert_eq!(result.unwrap_err().code(), "INVALID_ARGUMENT");
+    }
+}
@@ -0,0 +1,528 @@
+use crate::cartesian::CartesianTarget;
+use crate::controller::{PidController, PidGains};
+use crate::dynamics::{gravity_torques, inverse_dynamics, mass_matrix};
+use crate::error::{check_dimension, check_finite, RoboticsError};
+use crate::geometry3d::Point3D;
+use crate::jacobian::jacobian;
+use crate::kinematics::end_effector_transform;
+use crate::manipulability::TaskSpace;
+use crate::robot::RobotArm;
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+
+/// Stiffness and damping of a joint-space control law
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JointGains {
+    pub kp: f64,
+    pub kd: f64,
+}
+
+impl Default for JointGains {
+    /// Critically damped at 10 rad/s for computed torque
+    fn default() -> Self {
+        JointGains { kp: 100.0, kd: 20.0 }
+    }
+}
+
+impl JointGains {
+    /// Reason the gains are unusable, if any
+    pub fn validate(&self) -> Result<(), String> {
+        if [self.kp, self.kd].iter().any(|value| !value.is_finite() || *value < 0.0) {
+            return Err("gains must be finite and non-negative".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Gains of the operational-space law
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TaskGains {
+    /// Task stiffness and damping: the end-effector error obeys ë + kd ė + kp e = 0
+    pub kp: f64,
+    pub kd: f64,
+    /// Stiffness and damping towards the posture, acting only in the null space of the task
+    pub posture_kp: f64,
+    pub posture_kd: f64,
+    /// Added to J M⁻¹ Jᵀ before inverting it, so the task-space inertia stays bounded near singularities
+    pub damping: f64,
+}
+
+impl Default for TaskGains {
+    fn default() -> Self {
+        TaskGains {
+            kp: 100.0,
+            kd: 20.0,
+            posture_kp: 25.0,
+            posture_kd: 10.0,
+            damping: 1e-4,
+        }
+    }
+}
+
+impl TaskGains {
+    /// Reason the gains are unusable, if any
+    pub fn validate(&self) -> Result<(), String> {
+        let values = [self.kp, self.kd, self.posture_kp, self.posture_kd];
+        if values.iter().any(|value| !value.is_finite() || *value < 0.0) {
+            return Err("gains must be finite and non-negative".to_string());
+        }
+        if !self.damping.is_finite() || self.damping <= 0.0 {
+            return Err(format!("damping must be positive, got {}", self.damping));
+        }
+        Ok(())
+    }
+}
+
+/// Feedback law turning the simulated joint state into torques
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ControlLaw {
+    /// Independent PID loop on every joint (same gains everywhere), blind to the arm's dynamics
+    Pid(PidGains),
+    /// Joint PD plus gravity compensation: τ = Kp (q_d - q) + Kd (q̇_d - q̇) + g(q)
+    ///
+    /// The gains are torques per unit of error; with zero gains the arm floats.
+    GravityCompensation(JointGains),
+    /// Computed torque (feedback linearization): τ = M(q)(q̈_d + Kd ė + Kp e) + C(q, q̇)q̇ + g(q)
+    ///
+    /// With a perfect model every joint error obeys ë + Kd ė + Kp e = 0.
+    ComputedTorque(JointGains),
+    /// Operational-space control of the end-effector towards the task target
+    ///
+    /// τ = Jᵀ Λ (Kp e - Kd J q̇) + C(q, q̇)q̇ + g(q) + Nᵀ M (Kp' (q_p - q) - Kd' q̇), with
+    /// Λ = (J M⁻¹ Jᵀ)⁻¹ the task-space inertia and Nᵀ = I - Jᵀ Λ J M⁻¹ the dynamically
+    /// consistent null-space projector. The J̇q̇ term is neglected.
+    OperationalSpace(TaskGains),
+}
+
+impl ControlLaw {
+    /// Check the law's gains
+    pub fn validate(&self) -> Result<(), RoboticsError> {
+        let result = match self {
+            ControlLaw::Pid(gains) => gains.validate(),
+            ControlLaw::GravityCompensation(gains) | ControlLaw::ComputedTorque(gains) => gains.validate(),
+            ControlLaw::OperationalSpace(gains) => gains.validate(),
+        };
+        result.map_err(RoboticsError::InvalidArgument)
+    }
+}
+
+/// Joint-space reference: positions with feed-forward velocities and accelerations
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JointReference {
+    pub positions: Vec<f64>,
+    pub velocities: Vec<f64>,
+    pub accelerations: Vec<f64>,
+}
+
+impl JointReference {
+    /// Stand still at the given positions
+    pub fn hold(positions: Vec<f64>) -> Self {
+        let zeros = vec![0.0; positions.len()];
+        JointReference {
+            positions,
+            velocities: zeros.clone(),
+            accelerations: zeros,
+        }
+    }
+}
+
+/// Model-based torque controller for the physics simulation (see `Simulation::step_with`)
+///
+/// The joint-space laws track `reference`, the operational-space law tracks
+/// `target` and, for redundant chains, pulls towards `posture` without
+/// disturbing the end-effector.
+#[derive(Debug, Clone)]
+pub struct ModelController {
+    law: ControlLaw,
+    reference: JointReference,
+    target: CartesianTarget,
+    /// Preferred joint values for the null space (joint velocities are only damped when None)
+    posture: Option<Vec<f64>>,
+    pids: Vec<PidController>,
+}
+
+impl ModelController {
+    /// Controller holding the robot's current joint values and end-effector pose
+    ///
+    /// Fails with `InvalidArgument` if the law's gains are invalid.
+    pub fn new(robot: &RobotArm, law: ControlLaw) -> Result<Self, RoboticsError> {
+        let pose = end_effector_transform(robot);
+        let mut controller = ModelController {
+            law,
+            reference: JointReference::hold(robot.joint_angles.clone()),
+            target: CartesianTarget {
+                position: pose.position(),
+                quaternion: Some(pose.quaternion()),
+            },
+            posture: None,
+            pids: Vec::new(),
+        };
+        controller.set_law(law)?;
+        Ok(controller)
+    }
+
+    pub fn law(&self) -> ControlLaw {
+        self.law
+    }
+
+    /// Switch to another law, keeping the reference, target and posture
+    ///
+    /// The controller is left unchanged if the law's gains are invalid.
+    pub fn set_law(&mut self, law: ControlLaw) -> Result<(), RoboticsError> {
+        law.validate()?;
+        let gains = match law {
+            ControlLaw::Pid(gains) => gains,
+            _ => PidGains::default(),
+        };
+        self.law = law;
+        self.pids = vec![PidController::new(gains); self.reference.positions.len()];
+        Ok(())
+    }
+
+    pub fn reference(&self) -> &JointReference {
+        &self.reference
+    }
+
+    pub fn set_reference(&mut self, reference: JointReference) -> Result<(), RoboticsError> {
+        let num_joints = self.reference.positions.len();
+        for values in [&reference.positions, &reference.velocities, &reference.accelerations] {
+            check_dimension(num_joints, values.len())?;
+            check_finite(values)?;
+        }
+
+        self.reference = reference;
+        Ok(())
+    }
+
+    pub fn target(&self) -> &CartesianTarget {
+        &self.target
+    }
+
+    /// Set the end-effector target (orientation is left free when the quaternion is omitted)
+    pub fn set_target(&mut self, target: CartesianTarget) -> Result<(), RoboticsError> {
+        let Point3D { x, y, z } = target.position;
+        check_finite(&[x, y, z])?;
+        if let Some(q) = &target.quaternion {
+            check_finite(&[q.w, q.x, q.y, q.z])?;
+            if q.norm() < 1e-12 {
+                return Err(RoboticsError::InvalidArgument("target quaternion must be non-zero".to_string()));
+            }
+        }
+
+        self.target = CartesianTarget {
+            position: target.position,
+            quaternion: target.quaternion.map(|q| q.normalized()),
+        };
+        Ok(())
+    }
+
+    pub fn posture(&self) -> Option<&[f64]> {
+        self.posture.as_deref()
+    }
+
+    pub fn set_posture(&mut self, posture: Option<Vec<f64>>) -> Result<(), RoboticsError> {
+        if let Some(posture) = &posture {
+            check_dimension(self.reference.positions.len(), posture.len())?;
+            check_finite(posture)?;
+        }
+
+        self.posture = posture;
+        Ok(())
+    }
+
+    /// Forget the PID state (integrals and derivative filters)
+    pub fn reset(&mut self) {
+        self.pids.iter_mut().for_each(PidController::reset);
+    }
+
+    /// Joint torques for the robot at its current joint values moving at `velocities`
+    ///
+    /// `dt` is the control period (used by the PID law). The torques are not
+    /// clamped to the effort limits; the simulation does that.
+    pub fn torques(&mut self, robot: &RobotArm, velocities: &[f64], dt: f64, gravity: [f64; 3]) -> Result<Vec<f64>, RoboticsError> {
+        let positions = &robot.joint_angles;
+        check_dimension(self.reference.positions.len(), positions.len())?;
+        check_dimension(positions.len(), velocities.len())?;
+        check_finite(velocities)?;
+
+        match self.law {
+            ControlLaw::Pid(_) => Ok(self
+                .pids
+                .iter_mut()
+                .zip(&self.reference.positions)
+                .zip(positions.iter().zip(&robot.limits))
+                .map(|((pid, &setpoint), (&position, limits))| pid.update(setpoint, position, dt, limits.effort))
+                .collect()),
+            ControlLaw::GravityCompensation(gains) => {
+                let gravity = gravity_torques(robot, positions, gravity)?;
+                Ok((0..positions.len())
+                    .map(|i| {
+                        gains.kp * (self.reference.positions[i] - positions[i])
+                            + gains.kd * (self.reference.velocities[i] - velocities[i])
+                            + gravity[i]
+                    })
+                    .collect())
+            }
+            ControlLaw::ComputedTorque(gains) => {
+                let commanded: Vec<f64> = (0..positions.len())
+                    .map(|i| {
+                        self.reference.accelerations[i]
+                            + gains.kd * (self.reference.velocities[i] - velocities[i])
+                            + gains.kp * (self.reference.positions[i] - positions[i])
+                    })
+                    .collect();
+                inverse_dynamics(robot, positions, velocities, &commanded, gravity)
+            }
+            ControlLaw::OperationalSpace(gains) => self.operational_space(robot, velocities, gains, gravity),
+        }
+    }
+
+    fn operational_space(
+        &self,
+        robot: &RobotArm,
+        velocities: &[f64],
+        gains: TaskGains,
+        gravity: [f64; 3],
+    ) -> Result<Vec<f64>, RoboticsError> {
+        let positions = &robot.joint_angles;
+        let n = positions.len();
+        let mass = mass_matrix(robot, positions)?;
+        let mass_inverse = mass.clone().cholesky().map(|cholesky| cholesky.inverse()).ok_or_else(|| {
+            RoboticsError::Unsupported("mass matrix is singular: every joint must move some mass or inertia".to_string())
+        })?;
+        let bias = inverse_dynamics(robot, positions, velocities, &vec![0.0; n], gravity)?;
+
+        // Task directions the arm controls; the orientation rows drop out when the target leaves it free
+        let rows: Vec<usize> = TaskSpace::for_robot(robot)
+            .rows()
+            .iter()
+            .copied()
+            .filter(|&row| row < 3 || self.target.quaternion.is_some())
+            .collect();
+        let full_jacobian = jacobian(robot);
+        let task_jacobian = full_jacobian.select_rows(&rows);
+
+        let current = end_effector_transform(robot);
+        let error = current.pose_error(&self.target.to_transform(&current));
+        let error = DVector::from_iterator(rows.len(), rows.iter().map(|&row| error[row]));
+        let velocity = DVector::from_column_slice(velocities);
+        let command = error * gains.kp - &task_jacobian * &velocity * gains.kd;
+
+        let inverse_inertia = &task_jacobian * &mass_inverse * task_jacobian.transpose()
+            + DMatrix::identity(rows.len(), rows.len()) * gains.damping;
+        let task_inertia = inverse_inertia.cholesky().map(|cholesky| cholesky.inverse()).ok_or_else(|| {
+            RoboticsError::InvalidArgument("task-space inertia is not positive definite (check the damping)".to_string())
+        })?;
+        let task_torques = task_jacobian.transpose() * &task_inertia * command;
+
+        // Posture torques filtered through Nᵀ = I - Jᵀ J̄ᵀ, J̄ = M⁻¹ Jᵀ Λ
+        let posture_acceleration = match &self.posture {
+            Some(posture) => DVector::from_iterator(
+                n,
+                (0..n).map(|i| gains.posture_kp * (posture[i] - positions[i]) - gains.posture_kd * velocities[i]),
+            ),
+            None => -velocity * gains.posture_kd,
+        };
+        let consistent_inverse = &mass_inverse * task_jacobian.transpose() * &task_inertia;
+        let projector = DMatrix::identity(n, n) - task_jacobian.transpose() * consistent_inverse.transpose();
+        let null_torques = projector * (mass * posture_acceleration);
+
+        Ok((0..n).map(|i| task_torques[i] + null_torques[i] + bias[i]).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dh_parameters::DHParameter;
+    use crate::dynamics::LinkInertia;
+    use crate::geometry3d::{Quaternion, Transform3D};
+    use crate::presets::Preset;
+    use crate::simulation::Simulation;
+
+    const GRAVITY: [f64; 3] = [0.0, -9.81, 0.0];
+
+    /// Vertical planar arm in the XY plane with gravity along -Y
+    fn planar_arm(lengths: &[f64]) -> RobotArm {
+        let mut robot = RobotArm::from_dh_params(
+            lengths
+                .iter()
+                .map(|&length| {
+                    let rod = length * length / 12.0;
+                    DHParameter::planar(length).with_inertia(LinkInertia::new(1.0, [-length / 2.0, 0.0, 0.0], [0.0, rod, rod, 0.0, 0.0, 0.0]))
+                })
+                .collect(),
+        );
+        robot.joint_angles = vec![0.4; lengths.len()];
+        robot
+    }
+
+    fn run(robot: &mut RobotArm, controller: &mut ModelController, seconds: f64, gravity: [f64; 3]) -> Simulation {
+        let mut simulation = Simulation::new(robot.num_joints());
+        for _ in 0..(seconds * 100.0).round() as usize {
+            simulation
+                .step_with(robot, 0.01, gravity, |robot, velocities, h| controller.torques(robot, velocities, h, gravity))
+                .unwrap();
+        }
+        simulation
+    }
+
+    #[test]
+    fn test_gravity_compensation_holds_the_arm() {
+        let mut robot = Preset::Puma560.build();
+        robot.joint_angles = vec![0.3, 0.6, -2.4, 0.2, 0.7, 0.1];
+        let start = robot.joint_angles.clone();
+        let mut controller = ModelController::new(&robot, ControlLaw::GravityCompensation(JointGains { kp: 0.0, kd: 0.0 })).unwrap();
+
+        let simulation = run(&mut robot, &mut controller, 1.0, [0.0, 0.0, -9.81]);
+        for (position, start) in robot.joint_angles.iter().zip(&start) {
+            assert!((position - start).abs() < 1e-9);
+        }
+        assert!(simulation.velocities.iter().all(|velocity| velocity.abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_computed_torque_linearizes_the_error_dynamics() {
+        let mut robot = planar_arm(&[1.0, 0.8]);
+        let mut controller = ModelController::new(&robot, ControlLaw::ComputedTorque(JointGains::default())).unwrap();
+        controller.set_reference(JointReference::hold(vec![1.0, -0.5])).unwrap();
+
+        // Critically damped at ω = 10 rad/s: e(t) = e₀ (1 + ωt) e^(-ωt), whatever the coupling
+        let seconds = 0.3;
+        run(&mut robot, &mut controller, seconds, GRAVITY);
+        let decay = (1.0 + 10.0 * seconds) * (-10.0 * seconds).exp();
+        for (i, initial_error) in [0.6, -0.9].iter().enumerate() {
+            let error = controller.reference().positions[i] - robot.joint_angles[i];
+            assert!((error - initial_error * decay).abs() < 2e-3, "joint {}: {} vs {}", i, error, initial_error * decay);
+        }
+    }
+
+    #[test]
+    fn test_pid_law_removes_steady_state_error() {
+        let mut robot = planar_arm(&[1.0, 0.8]);
+        let gains = PidGains {
+            kp: 200.0,
+            ki: 200.0,
+            kd: 30.0,
+            ..PidGains::default()
+        };
+        let mut controller = ModelController::new(&robot, ControlLaw::Pid(gains)).unwrap();
+        controller.set_reference(JointReference::hold(vec![0.8, 0.2])).unwrap();
+
+        run(&mut robot, &mut controller, 5.0, GRAVITY);
+        assert!((robot.joint_angles[0] - 0.8).abs() < 1e-3, "{:?}", robot.joint_angles);
+        assert!((robot.joint_angles[1] - 0.2).abs() < 1e-3, "{:?}", robot.joint_angles);
+    }
+
+    #[test]
+    fn test_operational_space_reaches_pose_target() {
+        let mut robot = Preset::Puma560.build();
+        robot.joint_angles = vec![0.3, 0.6, -2.4, 0.2, 0.7, 0.1];
+        let start = end_effector_transform(&robot);
+        let goal = start
+            .compose(&Transform3D::rotation_z(0.2))
+            .compose(&Transform3D::translation(0.05, -0.05, 0.03));
+        let mut controller = ModelController::new(&robot, ControlLaw::OperationalSpace(TaskGains::default())).unwrap();
+        controller
+            .set_target(CartesianTarget {
+                position: goal.position(),
+                quaternion: Some(goal.quaternion()),
+            })
+            .unwrap();
+
+        run(&mut robot, &mut controller, 1.5, [0.0, 0.0, -9.81]);
+        let error = end_effector_transform(&robot).pose_error(&goal);
+        assert!(error.norm() < 1e-3, "{:?}", error);
+    }
+
+    #[test]
+    fn test_null_space_posture_leaves_task_alone() {
+        // Three joints, two task directions: one degree of redundancy. The
+        // posture reaches the target too, but is far from the start.
+        let mut posed = planar_arm(&[1.0, 0.8, 0.6]);
+        posed.joint_angles = vec![1.0, -0.6, -0.5];
+        let target = CartesianTarget {
+            position: end_effector_transform(&posed).position(),
+            quaternion: None,
+        };
+        let distance_to_posture = |robot: &RobotArm| -> f64 {
+            robot.joint_angles.iter().zip(&posed.joint_angles).map(|(q, p)| (q - p).powi(2)).sum::<f64>().sqrt()
+        };
+
+        let mut free = planar_arm(&[1.0, 0.8, 0.6]);
+        let mut controller = ModelController::new(&free, ControlLaw::OperationalSpace(TaskGains::default())).unwrap();
+        controller.set_target(target).unwrap();
+        run(&mut free, &mut controller, 1.0, GRAVITY);
+
+        let mut guided = planar_arm(&[1.0, 0.8, 0.6]);
+        let mut controller = ModelController::new(&guided, ControlLaw::OperationalSpace(TaskGains::default())).unwrap();
+        controller.set_target(target).unwrap();
+        controller.set_posture(Some(posed.joint_angles.clone())).unwrap();
+        run(&mut guided, &mut controller, 3.0, GRAVITY);
+
+        for robot in [&free, &guided] {
+            let error = end_effector_transform(robot).pose_error(&target.to_transform(&Transform3D::identity()));
+            assert!(error.fixed_rows::<3>(0).norm() < 1e-3, "{:?}", error);
+        }
+        assert!(distance_to_posture(&free) > 0.1, "{:?}", free.joint_angles);
+        assert!(distance_to_posture(&guided) < 1e-2, "{:?}", guided.joint_angles);
+    }
+
+    #[test]
+    fn test_invalid_references_are_rejected() {
+        let robot = planar_arm(&[1.0, 0.8]);
+        let mut controller = ModelController::new(&robot, ControlLaw::ComputedTorque(JointGains::default())).unwrap();
+
+        let error = controller.set_reference(JointReference::hold(vec![0.0])).unwrap_err();
+        assert_eq!(error.code(), "DIMENSION_MISMATCH");
+        let error = controller.set_posture(Some(vec![0.0, f64::NAN])).unwrap_err();
+        assert_eq!(error.code(), "NON_FINITE");
+        let error = controller
+            .set_target(CartesianTarget {
+                position: Point3D::new(1.0, 0.0, 0.0),
+                quaternion: Some(Quaternion::new(0.0, 0.0, 0.0, 0.0)),
+            })
+            .unwrap_err();
+        assert_eq!(error.code(), "INVALID_ARGUMENT");
+
+        // Switching laws keeps the reference
+        controller.set_reference(JointReference::hold(vec![0.5, 0.5])).unwrap();
+        controller.set_law(ControlLaw::Pid(PidGains::default())).unwrap();
+        assert_eq!(controller.reference().positions, vec![0.5, 0.5]);
+    }
+
+    #[test]
+    fn test_invalid_gains_are_rejected() {
+        let robot = planar_arm(&[1.0, 0.8]);
+        let invalid = [
+            ControlLaw::Pid(PidGains {
+                max_torque: -1.0,
+                ..PidGains::default()
+            }),
+            ControlLaw::Pid(PidGains {
+                kp: f64::NAN,
+                ..PidGains::default()
+            }),
+            ControlLaw::GravityCompensation(JointGains { kp: -1.0, kd: 0.0 }),
+            ControlLaw::ComputedTorque(JointGains { kp: 1.0, kd: f64::INFINITY }),
+            ControlLaw::OperationalSpace(TaskGains {
+                posture_kd: -1.0,
+                ..TaskGains::default()
+            }),
+            ControlLaw::OperationalSpace(TaskGains {
+                damping: 0.0,
+                ..TaskGains::default()
+            }),
+        ];
+
+        let law = ControlLaw::ComputedTorque(JointGains::default());
+        let mut controller = ModelController::new(&robot, law).unwrap();
+        for bad in invalid {
+            assert_eq!(ModelController::new(&robot, bad).unwrap_err().code(), "INVALID_ARGUMENT");
+            assert_eq!(controller.set_law(bad).unwrap_err().code(), "INVALID_ARGUMENT");
+            assert_eq!(controller.law(), law);
+        }
+    }
+}
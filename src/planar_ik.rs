@@ -0,0 +1,450 @@
+use crate::dh_parameters::{DHConvention, JointType};
+use crate::error::{check_finite, RoboticsError};
+use crate::geometry3d::Transform3D;
+use crate::robot::RobotArm;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+/// Slack allowed when deciding whether a target lies on the workspace boundary
+const REACH_TOLERANCE: f64 = 1e-9;
+
+/// Elbow branch of a planar 2R/3R solution
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ElbowConfiguration {
+    /// Geometric elbow angle θ2 ≤ 0 (elbow sits counter-clockwise of the base→wrist line)
+    Up,
+    /// Geometric elbow angle θ2 > 0 (elbow sits clockwise of the base→wrist line)
+    Down,
+}
+
+/// One closed-form inverse kinematics solution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanarIkSolution {
+    /// Joint values in radians, wrapped to (-π, π]
+    pub joint_values: Vec<f64>,
+    /// Which elbow branch this solution belongs to
+    pub elbow: ElbowConfiguration,
+}
+
+/// All closed-form solutions for a planar target
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanarIkSolutions {
+    /// False if the target is outside the workspace (then `solutions` is empty)
+    pub reachable: bool,
+    /// Solution branches (two in general, one on the workspace boundary)
+    pub solutions: Vec<PlanarIkSolution>,
+}
+
+impl PlanarIkSolutions {
+    fn unreachable() -> Self {
+        PlanarIkSolutions {
+            reachable: false,
+            solutions: vec![],
+        }
+    }
+}
+
+/// Closed-form inverse kinematics for a planar 2R arm
+///
+/// Solves for the joint angles placing the end-effector at (x, y):
+/// - cos θ2 = (x² + y² - l1² - l2²) / (2 l1 l2)
+/// - θ1 = atan2(y, x) - atan2(l2 sin θ2, l1 + l2 cos θ2)
+pub fn solve_planar_2r(l1: f64, l2: f64, x: f64, y: f64) -> PlanarIkSolutions {
+    let cos_theta2 = (x * x + y * y - l1 * l1 - l2 * l2) / (2.0 * l1 * l2);
+
+    if !cos_theta2.is_finite() || cos_theta2.abs() > 1.0 + REACH_TOLERANCE {
+        return PlanarIkSolutions::unreachable();
+    }
+
+    let cos_theta2 = cos_theta2.clamp(-1.0, 1.0);
+    let theta2_magnitude = cos_theta2.acos();
+
+    // On the boundary (stretched or folded) both branches coincide
+    let branches: &[ElbowConfiguration] = if theta2_magnitude.abs() < REACH_TOLERANCE
+        || (PI - theta2_magnitude).abs() < REACH_TOLERANCE
+    {
+        &[ElbowConfiguration::Up]
+    } else {
+        &[ElbowConfiguration::Up, ElbowConfiguration::Down]
+    };
+
+    let solutions = branches
+        .iter()
+        .map(|&elbow| {
+            let theta2 = match elbow {
+                ElbowConfiguration::Up => -theta2_magnitude,
+                ElbowConfiguration::Down => theta2_magnitude,
+            };
+            let theta1 = y.atan2(x) - (l2 * theta2.sin()).atan2(l1 + l2 * theta2.cos());
+
+            PlanarIkSolution {
+                joint_values: vec![wrap_angle(theta1), wrap_angle(theta2)],
+                elbow,
+            }
+        })
+        .collect();
+
+    PlanarIkSolutions {
+        reachable: true,
+        solutions,
+    }
+}
+
+/// Closed-form inverse kinematics for a planar 3R arm
+///
+/// The target is the end-effector position (x, y) and orientation φ
+/// (angle of the last link with the X axis). The wrist point
+/// (x - l3 cos φ, y - l3 sin φ) is solved as a 2R problem, then θ3 = φ - θ1 - θ2.
+pub fn solve_planar_3r(l1: f64, l2: f64, l3: f64, x: f64, y: f64, phi: f64) -> PlanarIkSolutions {
+    let wrist_x = x - l3 * phi.cos();
+    let wrist_y = y - l3 * phi.sin();
+
+    let mut result = solve_planar_2r(l1, l2, wrist_x, wrist_y);
+    for solution in result.solutions.iter_mut() {
+        let theta3 = phi - solution.joint_values[0] - solution.joint_values[1];
+        solution.joint_values.push(wrap_angle(theta3));
+    }
+
+    result
+}
+
+/// Closed-form inverse kinematics for a planar 2R or 3R `RobotArm`
+///
+/// Works for simple robots (`RobotArm::new`) and for DH robots whose links are
+/// all revolute with zero twist (`RobotArm::planar` and equivalents), in either
+/// DH convention; DH joint offsets are taken into account. `phi` is required for 3R arms and ignored for 2R.
+/// Base and tool frames may translate the arm and rotate it about Z; the target
+/// and `phi` are then those of the tool center point in the world frame.
+///
+/// Returns `Unsupported` if the robot is not a planar 2R/3R chain, the joints
+/// the closed form relies on coincide (e.g. a Modified DH 2R arm without a tool
+/// offset) or `phi` is missing, `NonFinite` for NaN/infinite targets and `Unreachable` if no branch
+/// reaches the target.
+pub fn solve_planar_ik(robot: &RobotArm, x: f64, y: f64, phi: Option<f64>) -> Result<PlanarIkSolutions, RoboticsError> {
+    check_finite(&[x, y, phi.unwrap_or(0.0)])?;
+
+    let unsupported = || RoboticsError::Unsupported("closed-form IK requires a planar 2R arm, or a planar 3R arm with phi".to_string());
+    let (base_offset, mut lengths, mut offsets) = planar_chain(robot).ok_or_else(unsupported)?;
+
+    let tilted = || RoboticsError::Unsupported("closed-form IK requires base and tool frames that keep the Z axis vertical".to_string());
+    let base = robot.base.compose(&Transform3D::translation(base_offset, 0.0, 0.0));
+    let (base_x, base_y, base_yaw) = planar_frame(&base).ok_or_else(tilted)?;
+    let (tool_x, tool_y, tool_yaw) = planar_frame(&robot.tool).ok_or_else(tilted)?;
+
+    // An in-plane tool offset lengthens and bends the last link: |(l + tx, ty)| at atan2(ty, l + tx)
+    let mut tool_bend = 0.0;
+    if let (Some(length), Some(offset)) = (lengths.last_mut(), offsets.last_mut()) {
+        tool_bend = tool_y.atan2(*length + tool_x);
+        *length = (*length + tool_x).hypot(tool_y);
+        *offset += tool_bend;
+    }
+    // The 2R solution divides by the lengths from the first joint to the wrist
+    if lengths.len() >= 2 && lengths[..2].iter().any(|length| length.abs() < REACH_TOLERANCE) {
+        return Err(RoboticsError::Unsupported(
+            "closed-form IK requires the first joints and the wrist to be apart".to_string(),
+        ));
+    }
+
+    // Target in the base frame
+    let (dx, dy) = (x - base_x, y - base_y);
+    let (local_x, local_y) = (
+        base_yaw.cos() * dx + base_yaw.sin() * dy,
+        -base_yaw.sin() * dx + base_yaw.cos() * dy,
+    );
+    let local_phi = phi.map(|phi| phi - base_yaw - tool_yaw + tool_bend);
+
+    let mut result = match (lengths.as_slice(), local_phi) {
+        ([l1, l2], _) => solve_planar_2r(*l1, *l2, local_x, local_y),
+        ([l1, l2, l3], Some(phi)) => solve_planar_3r(*l1, *l2, *l3, local_x, local_y, phi),
+        _ => return Err(unsupported()),
+    };
+    if !result.reachable {
+        return Err(RoboticsError::Unreachable(format!("({}, {}) is outside the planar workspace", x, y)));
+    }
+
+    // Geometric angles include the DH offsets; joint values exclude them
+    for solution in result.solutions.iter_mut() {
+        for (value, offset) in solution.joint_values.iter_mut().zip(offsets.iter()) {
+            *value = wrap_angle(*value - offset);
+        }
+    }
+
+    Ok(result)
+}
+
+/// Base offset along X, link lengths and joint angle offsets of a planar revolute chain
+///
+/// In the plane a Standard link is Rot(θ) * Trans(a) and a Modified link
+/// Trans(a) * Rot(θ), so any mix of the two is a base translation followed by
+/// Rot(θ_i) * Trans(l_i), where l_i adds up the translations between joints i
+/// and i+1. Returns None if any DH link has a twist or is prismatic.
+fn planar_chain(robot: &RobotArm) -> Option<(f64, Vec<f64>, Vec<f64>)> {
+    match &robot.dh_params {
+        None => Some((0.0, robot.link_lengths.clone(), vec![0.0; robot.link_lengths.len()])),
+        Some(dh_params) => {
+            let is_planar = dh_params
+                .iter()
+                .all(|dh| dh.joint_type == JointType::Revolute && dh.alpha.abs() < REACH_TOLERANCE);
+            if !is_planar {
+                return None;
+            }
+
+            let mut base_offset = 0.0;
+            let mut lengths: Vec<f64> = Vec::with_capacity(dh_params.len());
+            for dh in dh_params {
+                match dh.convention {
+                    DHConvention::Standard => lengths.push(dh.a),
+                    DHConvention::Modified => {
+                        *lengths.last_mut().unwrap_or(&mut base_offset) += dh.a;
+                        lengths.push(0.0);
+                    }
+                }
+            }
+            let offsets = dh_params.iter().map(|dh| dh.joint_offset).collect();
+            Some((base_offset, lengths, offsets))
+        }
+    }
+}
+
+/// In-plane translation and yaw of a frame whose Z axis stays vertical
+///
+/// Returns None for a frame that tilts the Z axis.
+fn planar_frame(frame: &Transform3D) -> Option<(f64, f64, f64)> {
+    let rotation = frame.rotation_matrix();
+    if rotation[(2, 2)] < 1.0 - REACH_TOLERANCE {
+        return None;
+    }
+
+    let position = frame.position();
+    Some((position.x, position.y, rotation[(1, 0)].atan2(rotation[(0, 0)])))
+}
+
+/// Wrap an angle to (-π, π]
+fn wrap_angle(angle: f64) -> f64 {
+    let wrapped = (angle + PI).rem_euclid(2.0 * PI) - PI;
+    if wrapped <= -PI {
+        wrapped + 2.0 * PI
+    } else {
+        wrapped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dh_parameters::DHParameter;
+    use crate::geometry3d::Point3D;
+    use crate::inverse_kinematics::{solve_ik, IkOptions, IkTarget};
+    use crate::kinematics::{end_effector_transform, forward_kinematics};
+
+    const EPSILON: f64 = 1e-9;
+
+    fn approx_eq(a: f64, b: f64) -> bool {
+        (a - b).abs() < EPSILON
+    }
+
+    fn end_effector(robot: &mut RobotArm, joint_values: &[f64]) -> (f64, f64) {
+        robot.set_joint_angles(joint_values.to_vec()).unwrap();
+        let positions = forward_kinematics(robot);
+        let last = positions.last().unwrap();
+        (last.x, last.y)
+    }
+
+    #[test]
+    fn test_2r_two_branches_reach_target() {
+        let result = solve_planar_2r(2.0, 1.5, 1.0, 2.0);
+        assert!(result.reachable);
+        assert_eq!(result.solutions.len(), 2);
+
+        let mut robot = RobotArm::new(vec![2.0, 1.5]);
+        for solution in &result.solutions {
+            let (x, y) = end_effector(&mut robot, &solution.joint_values);
+            assert!(approx_eq(x, 1.0));
+            assert!(approx_eq(y, 2.0));
+        }
+
+        assert_eq!(result.solutions[0].elbow, ElbowConfiguration::Up);
+        assert!(result.solutions[0].joint_values[1] < 0.0);
+        assert_eq!(result.solutions[1].elbow, ElbowConfiguration::Down);
+        assert!(result.solutions[1].joint_values[1] > 0.0);
+    }
+
+    #[test]
+    fn test_2r_known_angles() {
+        // L1 = L2 = 1, target (1, 1): elbow-down is θ1 = 0, θ2 = 90°
+        let result = solve_planar_2r(1.0, 1.0, 1.0, 1.0);
+        let down = &result.solutions[1];
+        assert!(approx_eq(down.joint_values[0], 0.0));
+        assert!(approx_eq(down.joint_values[1], PI / 2.0));
+
+        let up = &result.solutions[0];
+        assert!(approx_eq(up.joint_values[0], PI / 2.0));
+        assert!(approx_eq(up.joint_values[1], -PI / 2.0));
+    }
+
+    #[test]
+    fn test_2r_boundary_single_solution() {
+        let result = solve_planar_2r(2.0, 1.5, 3.5, 0.0);
+        assert!(result.reachable);
+        assert_eq!(result.solutions.len(), 1);
+        assert!(approx_eq(result.solutions[0].joint_values[0], 0.0));
+        assert!(approx_eq(result.solutions[0].joint_values[1], 0.0));
+    }
+
+    #[test]
+    fn test_2r_unreachable_targets() {
+        // Too far
+        let far = solve_planar_2r(2.0, 1.5, 4.0, 0.0);
+        assert!(!far.reachable);
+        assert!(far.solutions.is_empty());
+
+        // Inside the inner hole (|L1 - L2| = 0.5)
+        let near = solve_planar_2r(2.0, 1.5, 0.2, 0.0);
+        assert!(!near.reachable);
+    }
+
+    #[test]
+    fn test_3r_position_and_orientation() {
+        let (l1, l2, l3) = (1.0, 1.0, 0.5);
+        let phi = 0.9;
+        let result = solve_planar_3r(l1, l2, l3, 1.2, 1.1, phi);
+        assert!(result.reachable);
+        assert_eq!(result.solutions.len(), 2);
+
+        let mut robot = RobotArm::new(vec![l1, l2, l3]);
+        for solution in &result.solutions {
+            let (x, y) = end_effector(&mut robot, &solution.joint_values);
+            assert!(approx_eq(x, 1.2));
+            assert!(approx_eq(y, 1.1));
+
+            let total: f64 = solution.joint_values.iter().sum();
+            assert!(approx_eq(wrap_angle(total), phi));
+        }
+    }
+
+    #[test]
+    fn test_solve_for_robot_arm_with_dh_offsets() {
+        let dh_params = vec![
+            DHParameter::revolute(2.0, 0.0, 0.0, 0.3),
+            DHParameter::revolute(1.5, 0.0, 0.0, -0.2),
+        ];
+        let mut robot = RobotArm::from_dh_params(dh_params);
+
+        let result = solve_planar_ik(&robot, 1.0, 2.0, None).unwrap();
+        assert!(result.reachable);
+        for solution in &result.solutions {
+            let (x, y) = end_effector(&mut robot, &solution.joint_values);
+            assert!(approx_eq(x, 1.0));
+            assert!(approx_eq(y, 2.0));
+        }
+    }
+
+    #[test]
+    fn test_solve_for_robot_arm_with_base_and_tool_frames() {
+        let mut robot = RobotArm::planar(vec![1.0, 1.0, 0.5]);
+        robot
+            .set_base_frame(Transform3D::from_xyz_rpy([0.5, -0.3, 0.2], [0.0, 0.0, 0.4]))
+            .unwrap();
+        robot
+            .set_tool_frame(Transform3D::from_xyz_rpy([0.1, 0.2, 0.0], [0.0, 0.0, -0.3]))
+            .unwrap();
+
+        let result = solve_planar_ik(&robot, 1.1, 1.6, Some(1.2)).unwrap();
+        assert!(!result.solutions.is_empty());
+        for solution in &result.solutions {
+            robot.set_joint_angles(solution.joint_values.clone()).unwrap();
+            let tcp = end_effector_transform(&robot);
+            let (_, _, yaw) = tcp.rpy();
+            assert!(approx_eq(tcp.position().x, 1.1));
+            assert!(approx_eq(tcp.position().y, 1.6));
+            assert!(approx_eq(yaw, 1.2));
+        }
+
+        // A tilted tool cannot be handled in closed form
+        robot.set_tool_frame(Transform3D::rotation_x(0.5)).unwrap();
+        assert_eq!(solve_planar_ik(&robot, 1.1, 1.6, Some(1.2)).unwrap_err().code(), "UNSUPPORTED");
+    }
+
+    #[test]
+    fn test_solve_for_modified_dh_chain() {
+        // Modified DH: a describes the previous link, so the tool offset is the last link
+        let mut robot = RobotArm::from_dh_params(vec![
+            DHParameter::revolute(0.5, 0.0, 0.0, 0.2).with_convention(DHConvention::Modified),
+            DHParameter::revolute(1.0, 0.0, 0.0, 0.0).with_convention(DHConvention::Modified),
+        ]);
+        robot.set_tool_frame(Transform3D::translation(0.8, 0.0, 0.0)).unwrap();
+
+        let result = solve_planar_ik(&robot, 0.5, 1.0, None).unwrap();
+        assert_eq!(result.solutions.len(), 2);
+        for solution in &result.solutions {
+            let (x, y) = end_effector(&mut robot, &solution.joint_values);
+            assert!(approx_eq(x, 0.5) && approx_eq(y, 1.0), "({}, {})", x, y);
+        }
+
+        // Mixed conventions: the Modified link's a joins the Standard link before it
+        let mut robot = RobotArm::from_dh_params(vec![
+            DHParameter::revolute(0.7, 0.0, 0.0, 0.0),
+            DHParameter::revolute(0.5, 0.0, 0.0, 0.0),
+            DHParameter::revolute(0.6, 0.0, 0.0, 0.0).with_convention(DHConvention::Modified),
+        ]);
+        let result = solve_planar_ik(&robot, 1.0, 0.9, Some(0.4)).unwrap();
+        assert!(!result.solutions.is_empty());
+        for solution in &result.solutions {
+            let (x, y) = end_effector(&mut robot, &solution.joint_values);
+            assert!(approx_eq(x, 1.0) && approx_eq(y, 0.9), "({}, {})", x, y);
+        }
+
+        // Without a tool the end-effector sits on the last joint: no 2R closed form
+        let robot = RobotArm::from_dh_params(vec![
+            DHParameter::revolute(0.5, 0.0, 0.0, 0.0).with_convention(DHConvention::Modified),
+            DHParameter::revolute(1.0, 0.0, 0.0, 0.0).with_convention(DHConvention::Modified),
+        ]);
+        assert_eq!(solve_planar_ik(&robot, 0.5, 1.0, None).unwrap_err().code(), "UNSUPPORTED");
+    }
+
+    #[test]
+    fn test_solve_for_robot_arm_rejects_non_planar() {
+        let robot = RobotArm::from_dh_params(vec![
+            DHParameter::revolute(0.0, PI / 2.0, 0.5, 0.0),
+            DHParameter::revolute(1.0, 0.0, 0.0, 0.0),
+        ]);
+        assert_eq!(solve_planar_ik(&robot, 1.0, 0.0, None).unwrap_err().code(), "UNSUPPORTED");
+
+        // 3R arm needs an orientation
+        let robot = RobotArm::planar(vec![1.0, 1.0, 0.5]);
+        assert_eq!(solve_planar_ik(&robot, 1.0, 0.0, None).unwrap_err().code(), "UNSUPPORTED");
+
+        // 4R arm has no closed-form solution here
+        let robot = RobotArm::planar(vec![1.0, 1.0, 0.5, 0.5]);
+        assert_eq!(solve_planar_ik(&robot, 1.0, 0.0, Some(0.0)).unwrap_err().code(), "UNSUPPORTED");
+    }
+
+    #[test]
+    fn test_solve_for_robot_arm_reports_unreachable_target() {
+        let robot = RobotArm::planar(vec![2.0, 1.5]);
+        assert_eq!(solve_planar_ik(&robot, 5.0, 0.0, None).unwrap_err().code(), "UNREACHABLE");
+        assert_eq!(solve_planar_ik(&robot, f64::NAN, 0.0, None).unwrap_err().code(), "NON_FINITE");
+    }
+
+    #[test]
+    fn test_numerical_ik_matches_analytic_branch() {
+        let robot = RobotArm::planar(vec![2.0, 1.5]);
+        let analytic = solve_planar_ik(&robot, -0.5, 2.5, None).unwrap();
+
+        let numerical = solve_ik(
+            &robot,
+            &IkTarget::Position(Point3D::new(-0.5, 2.5, 0.0)),
+            &IkOptions::default(),
+        );
+        assert!(numerical.converged);
+
+        let matches_branch = analytic.solutions.iter().any(|solution| {
+            solution
+                .joint_values
+                .iter()
+                .zip(numerical.joint_values.iter())
+                .all(|(&a, &n)| wrap_angle(a - n).abs() < 1e-5)
+        });
+        assert!(matches_branch);
+    }
+}
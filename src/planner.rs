@@ -0,0 +1,494 @@
+use crate::error::{check_dimension, check_finite, RoboticsError};
+use crate::limits::LimitViolation;
+use crate::rng::SplitMix64;
+use crate::robot::RobotArm;
+use crate::workspace::{joint_ranges, validate_prismatic_range, WorkspaceOptions};
+use serde::{Deserialize, Serialize};
+
+/// Largest number of `resolution` (or `step_size`) steps across the joint space,
+/// which bounds the validity checks per segment
+pub const MAX_SEGMENT_STEPS: usize = 10_000;
+
+/// Options for the RRT-Connect planner
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlannerOptions {
+    /// Seed of the sampler: the same seed gives the same plan
+    pub seed: u64,
+    /// Longest joint-space step (Euclidean, rad or m) a tree grows by
+    pub step_size: f64,
+    /// Spacing of the configurations checked along every straight segment
+    pub resolution: f64,
+    /// Samples drawn before giving up
+    pub max_iterations: usize,
+    /// Random shortcut attempts on the found path
+    pub shortcut_attempts: usize,
+    /// Passes pulling every waypoint towards the midpoint of its neighbours
+    pub smoothing_passes: usize,
+    /// Range sampled for prismatic joints without position limits
+    pub prismatic_range: [f64; 2],
+}
+
+impl Default for PlannerOptions {
+    fn default() -> Self {
+        PlannerOptions {
+            seed: 1,
+            step_size: 0.2,
+            resolution: 0.02,
+            max_iterations: 5000,
+            shortcut_attempts: 100,
+            smoothing_passes: 20,
+            prismatic_range: [0.0, 1.0],
+        }
+    }
+}
+
+/// A collision-free joint-space path
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MotionPlan {
+    /// Joint vectors from the start to the goal; every one, and the straight
+    /// segments between them, passed the validity check
+    pub path: Vec<Vec<f64>>,
+    /// Joint-space length of `path`
+    pub length: f64,
+    /// Length of the path as found by the trees, before shortcutting and smoothing
+    pub raw_length: f64,
+    /// Samples drawn
+    pub iterations: usize,
+    /// Configurations in both trees
+    pub tree_size: usize,
+}
+
+/// Plan a path from `start` to `goal` with RRT-Connect
+///
+/// Two trees grow from the start and the goal towards random configurations
+/// within the joint limits (unbounded revolute joints are sampled in [-π, π]),
+/// each trying to connect to the other's newest node. The joined path is then
+/// shortened by random shortcuts and smoothed, keeping every change that stays
+/// valid. `is_valid` decides whether a configuration is allowed (e.g. collision
+/// free); segments are checked every `resolution`.
+///
+/// Fails with `LimitViolation` if the start or goal is outside the joint
+/// limits, `InvalidArgument` if either is invalid or the options are (e.g. a
+/// `resolution` that would take more than `MAX_SEGMENT_STEPS` steps to cross
+/// the joint space), and `Unreachable` if no path is found within
+/// `max_iterations` samples.
+pub fn plan_motion(
+    robot: &RobotArm,
+    start: &[f64],
+    goal: &[f64],
+    options: &PlannerOptions,
+    mut is_valid: impl FnMut(&[f64]) -> bool,
+) -> Result<MotionPlan, RoboticsError> {
+    for (what, configuration) in [("start", start), ("goal", goal)] {
+        check_dimension(robot.num_joints(), configuration.len())?;
+        check_finite(configuration)?;
+        for (joint, (&value, limits)) in configuration.iter().zip(&robot.limits).enumerate() {
+            if !limits.contains(value) {
+                return Err(RoboticsError::LimitViolation(LimitViolation {
+                    joint,
+                    value,
+                    lower: limits.lower,
+                    upper: limits.upper,
+                }));
+            }
+        }
+        if !is_valid(configuration) {
+            return Err(RoboticsError::InvalidArgument(format!("{} configuration is not valid", what)));
+        }
+    }
+    validate_prismatic_range(options.prismatic_range)?;
+
+    let ranges = joint_ranges(
+        robot,
+        &WorkspaceOptions {
+            prismatic_range: options.prismatic_range,
+            ..WorkspaceOptions::default()
+        },
+    );
+    // Every sample and path point lies in the box spanned by the ranges, start and goal
+    let span = ranges
+        .iter()
+        .zip(start.iter().zip(goal))
+        .map(|(&(lower, upper), (&a, &b))| upper.max(a).max(b) - lower.min(a).min(b))
+        .map(|width| width * width)
+        .sum::<f64>()
+        .sqrt();
+    for (name, value) in [("step_size", options.step_size), ("resolution", options.resolution)] {
+        if !value.is_finite() || value <= 0.0 {
+            return Err(RoboticsError::InvalidArgument(format!("{} must be positive, got {}", name, value)));
+        }
+        if span / value > MAX_SEGMENT_STEPS as f64 {
+            return Err(RoboticsError::InvalidArgument(format!(
+                "{} {} needs more than {} steps to cross the joint space",
+                name, value, MAX_SEGMENT_STEPS
+            )));
+        }
+    }
+    let mut rng = SplitMix64::new(options.seed);
+    let mut planner = Planner {
+        options,
+        is_valid: &mut is_valid,
+    };
+
+    let (raw_path, iterations, tree_size) = if planner.segment_is_valid(start, goal) {
+        (vec![start.to_vec(), goal.to_vec()], 0, 2)
+    } else {
+        planner.connect_trees(start, goal, &ranges, &mut rng)?
+    };
+    let raw_length = path_length(&raw_path);
+
+    let path = planner.shortcut(raw_path, &mut rng);
+    let path = planner.smooth(path);
+
+    Ok(MotionPlan {
+        length: path_length(&path),
+        path,
+        raw_length,
+        iterations,
+        tree_size,
+    })
+}
+
+/// Node of an RRT: a configuration and the node it was grown from
+struct Node {
+    configuration: Vec<f64>,
+    parent: Option<usize>,
+}
+
+/// Outcome of growing a tree towards a configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Growth {
+    /// The tree reached the configuration
+    Reached,
+    /// The tree moved one step closer
+    Advanced,
+    /// The step was invalid
+    Trapped,
+}
+
+struct Planner<'a, F: FnMut(&[f64]) -> bool> {
+    options: &'a PlannerOptions,
+    is_valid: &'a mut F,
+}
+
+impl<F: FnMut(&[f64]) -> bool> Planner<'_, F> {
+    /// Grow a tree from each end until they meet; returns the joined path,
+    /// the iterations used and the total tree size
+    fn connect_trees(
+        &mut self,
+        start: &[f64],
+        goal: &[f64],
+        ranges: &[(f64, f64)],
+        rng: &mut SplitMix64,
+    ) -> Result<(Vec<Vec<f64>>, usize, usize), RoboticsError> {
+        let root = |configuration: &[f64]| {
+            vec![Node {
+                configuration: configuration.to_vec(),
+                parent: None,
+            }]
+        };
+        let mut trees = [root(start), root(goal)];
+
+        for iteration in 1..=self.options.max_iterations {
+            // Trees take turns exploring; index 0 is the one growing this round
+            let (grow, other) = if iteration % 2 == 1 { (0, 1) } else { (1, 0) };
+            let sample: Vec<f64> = ranges.iter().map(|&(lower, upper)| rng.range(lower, upper)).collect();
+
+            if self.extend(&mut trees[grow], &sample) == Growth::Trapped {
+                continue;
+            }
+            let newest = trees[grow].last().unwrap().configuration.clone();
+            if self.connect(&mut trees[other], &newest) != Growth::Reached {
+                continue;
+            }
+
+            // Both trees now end at the same configuration
+            let mut from_start = branch(&trees[0]);
+            let mut from_goal = branch(&trees[1]);
+            from_start.reverse();
+            from_goal.remove(0);
+            from_start.extend(from_goal);
+
+            return Ok((from_start, iteration, trees[0].len() + trees[1].len()));
+        }
+
+        Err(RoboticsError::Unreachable(format!(
+            "no path found in {} iterations",
+            self.options.max_iterations
+        )))
+    }
+
+    /// Move the tree one step from its nearest node towards `target`
+    fn extend(&mut self, tree: &mut Vec<Node>, target: &[f64]) -> Growth {
+        let nearest = tree
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (i, distance(&node.configuration, target)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+            .unwrap();
+        let from = &tree[nearest].configuration;
+        let gap = distance(from, target);
+
+        let (configuration, growth) = if gap <= self.options.step_size {
+            (target.to_vec(), Growth::Reached)
+        } else {
+            (interpolate(from, target, self.options.step_size / gap), Growth::Advanced)
+        };
+        if !self.segment_is_valid(from, &configuration) {
+            return Growth::Trapped;
+        }
+
+        tree.push(Node {
+            configuration,
+            parent: Some(nearest),
+        });
+        growth
+    }
+
+    /// Step the tree towards `target` until it gets there or is blocked
+    fn connect(&mut self, tree: &mut Vec<Node>, target: &[f64]) -> Growth {
+        loop {
+            match self.extend(tree, target) {
+                Growth::Advanced => continue,
+                growth => return growth,
+            }
+        }
+    }
+
+    /// Replace random stretches of the path by straight segments where valid
+    fn shortcut(&mut self, mut path: Vec<Vec<f64>>, rng: &mut SplitMix64) -> Vec<Vec<f64>> {
+        for _ in 0..self.options.shortcut_attempts {
+            if path.len() < 3 {
+                break;
+            }
+            let i = (rng.next_u64() % path.len() as u64) as usize;
+            let j = (rng.next_u64() % path.len() as u64) as usize;
+            let (i, j) = (i.min(j), i.max(j));
+            if j - i < 2 {
+                continue;
+            }
+            if self.segment_is_valid(&path[i], &path[j]) {
+                path.drain(i + 1..j);
+            }
+        }
+        path
+    }
+
+    /// Round the corners of the path
+    ///
+    /// The path is resampled every `step_size`, then each interior waypoint is
+    /// moved halfway to the midpoint of its neighbours whenever the two
+    /// segments through it stay valid. The midpoint of two configurations
+    /// within the joint limits is within them too.
+    fn smooth(&mut self, path: Vec<Vec<f64>>) -> Vec<Vec<f64>> {
+        if self.options.smoothing_passes == 0 || path.len() < 3 {
+            return path;
+        }
+
+        let mut dense = vec![path[0].clone()];
+        for pair in path.windows(2) {
+            let steps = (distance(&pair[0], &pair[1]) / self.options.step_size).ceil().max(1.0) as usize;
+            dense.extend((1..=steps).map(|k| interpolate(&pair[0], &pair[1], k as f64 / steps as f64)));
+        }
+
+        for _ in 0..self.options.smoothing_passes {
+            for k in 1..dense.len() - 1 {
+                let midpoint = interpolate(&dense[k - 1], &dense[k + 1], 0.5);
+                let candidate = interpolate(&dense[k], &midpoint, 0.5);
+                if self.segment_is_valid(&dense[k - 1], &candidate) && self.segment_is_valid(&candidate, &dense[k + 1]) {
+                    dense[k] = candidate;
+                }
+            }
+        }
+        dense
+    }
+
+    /// Whether `to` and every configuration on the way from `from` are valid
+    /// (`from` is assumed valid)
+    fn segment_is_valid(&mut self, from: &[f64], to: &[f64]) -> bool {
+        let steps = (distance(from, to) / self.options.resolution).ceil().max(1.0) as usize;
+        (1..=steps).all(|k| (self.is_valid)(&interpolate(from, to, k as f64 / steps as f64)))
+    }
+}
+
+/// Configurations from a tree's newest node back to its root
+fn branch(tree: &[Node]) -> Vec<Vec<f64>> {
+    let mut path = Vec::new();
+    let mut index = Some(tree.len() - 1);
+    while let Some(i) = index {
+        path.push(tree[i].configuration.clone());
+        index = tree[i].parent;
+    }
+    path
+}
+
+fn distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| (a - b) * (a - b)).sum::<f64>().sqrt()
+}
+
+fn interpolate(from: &[f64], to: &[f64], t: f64) -> Vec<f64> {
+    from.iter().zip(to).map(|(a, b)| a + (b - a) * t).collect()
+}
+
+/// Total joint-space length of a piecewise linear path
+pub fn path_length(path: &[Vec<f64>]) -> f64 {
+    path.windows(2).map(|pair| distance(&pair[0], &pair[1])).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision::{CollisionModel, Obstacle, Shape};
+    use crate::limits::JointLimits;
+    use std::f64::consts::PI;
+
+    /// Planar 2R arm with a box right in front of it, so swinging from one side to
+    /// the other has to go around the back
+    fn blocked_arm() -> (RobotArm, CollisionModel) {
+        let mut robot = RobotArm::planar(vec![1.0, 1.0]);
+        robot.limits = vec![JointLimits::position(-PI, PI); 2];
+        let mut model = CollisionModel::for_robot(&robot, 0.05);
+        model.obstacles.push(Obstacle {
+            name: "wall".to_string(),
+            shape: Shape::Box {
+                center: [1.5, 0.0, 0.0],
+                rpy: [0.0; 3],
+                half_extents: [0.3, 0.3, 0.5],
+            },
+        });
+        (robot, model)
+    }
+
+    fn collision_free<'a>(robot: &'a RobotArm, model: &'a CollisionModel) -> impl FnMut(&[f64]) -> bool + 'a {
+        move |configuration| !model.check(robot, configuration).unwrap().in_collision
+    }
+
+    /// Collision checks are slow in debug builds; check segments more coarsely
+    fn coarse() -> PlannerOptions {
+        PlannerOptions {
+            resolution: 0.05,
+            smoothing_passes: 5,
+            ..PlannerOptions::default()
+        }
+    }
+
+    /// The path joins start and goal, and every segment is valid at the planner's resolution
+    fn assert_path_is_valid(
+        plan: &MotionPlan,
+        start: &[f64],
+        goal: &[f64],
+        resolution: f64,
+        mut is_valid: impl FnMut(&[f64]) -> bool,
+    ) {
+        assert_eq!(plan.path.first().unwrap(), start);
+        assert_eq!(plan.path.last().unwrap(), goal);
+        for pair in plan.path.windows(2) {
+            let steps = (distance(&pair[0], &pair[1]) / resolution).ceil() as usize;
+            for k in 0..=steps {
+                assert!(is_valid(&interpolate(&pair[0], &pair[1], k as f64 / steps.max(1) as f64)), "{:?}", pair);
+            }
+        }
+    }
+
+    #[test]
+    fn test_plans_around_an_obstacle() {
+        let (robot, model) = blocked_arm();
+        let (start, goal) = ([1.2, 0.0], [-1.2, 0.0]);
+        // The straight swing sweeps through the wall
+        assert!(model.check(&robot, &[0.0, 0.0]).unwrap().in_collision);
+
+        let options = coarse();
+        let plan = plan_motion(&robot, &start, &goal, &options, collision_free(&robot, &model)).unwrap();
+        assert_path_is_valid(&plan, &start, &goal, options.resolution, collision_free(&robot, &model));
+        assert!(plan.iterations > 0);
+        assert!(plan.length <= plan.raw_length + 1e-9);
+        assert!(plan.path.iter().flatten().all(|value| value.abs() <= PI));
+    }
+
+    #[test]
+    fn test_same_seed_same_plan() {
+        let (robot, model) = blocked_arm();
+        let (start, goal) = ([1.2, 0.0], [-1.2, 0.0]);
+        let plan = |seed| {
+            let options = PlannerOptions {
+                seed,
+                smoothing_passes: 0,
+                ..coarse()
+            };
+            plan_motion(&robot, &start, &goal, &options, collision_free(&robot, &model)).unwrap()
+        };
+
+        assert_eq!(plan(7).path, plan(7).path);
+        assert_ne!(plan(7).path, plan(8).path);
+    }
+
+    #[test]
+    fn test_free_space_gives_straight_line() {
+        let robot = RobotArm::planar(vec![1.0, 1.0, 1.0]);
+        let (start, goal) = ([0.0, 0.5, -0.5], [1.0, -0.5, 0.5]);
+
+        let plan = plan_motion(&robot, &start, &goal, &PlannerOptions::default(), |_| true).unwrap();
+        assert_eq!(plan.path, vec![start.to_vec(), goal.to_vec()]);
+        assert_eq!(plan.iterations, 0);
+        assert!((plan.length - 3f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_shortcuts_and_smoothing_shorten_the_path() {
+        // A 4-DOF arm has to get a joint past a band of forbidden values
+        let robot = RobotArm::planar(vec![0.5; 4]);
+        let start = [-1.0, 0.0, 0.0, 0.0];
+        let goal = [1.0, 0.0, 0.0, 0.0];
+        let is_valid = |q: &[f64]| q[0].abs() > 0.3 || q[1] > 1.0;
+
+        let raw = PlannerOptions {
+            shortcut_attempts: 0,
+            smoothing_passes: 0,
+            ..PlannerOptions::default()
+        };
+        let raw = plan_motion(&robot, &start, &goal, &raw, is_valid).unwrap();
+        let refined = plan_motion(&robot, &start, &goal, &PlannerOptions::default(), is_valid).unwrap();
+
+        let resolution = PlannerOptions::default().resolution;
+        assert_path_is_valid(&raw, &start, &goal, resolution, is_valid);
+        assert_path_is_valid(&refined, &start, &goal, resolution, is_valid);
+        assert!((raw.length - raw.raw_length).abs() < 1e-12);
+        assert!(refined.length < 0.8 * raw.length, "{} vs {}", refined.length, raw.length);
+    }
+
+    #[test]
+    fn test_invalid_requests_are_rejected() {
+        let (robot, model) = blocked_arm();
+        let options = PlannerOptions::default();
+
+        let error = plan_motion(&robot, &[0.0, 0.0], &[1.2, 0.0], &options, collision_free(&robot, &model)).unwrap_err();
+        assert_eq!(error.code(), "INVALID_ARGUMENT");
+        let error = plan_motion(&robot, &[4.0, 0.0], &[1.2, 0.0], &options, |_| true).unwrap_err();
+        assert_eq!(error.code(), "LIMIT_VIOLATION");
+        let error = plan_motion(&robot, &[1.2], &[1.2, 0.0], &options, |_| true).unwrap_err();
+        assert_eq!(error.code(), "DIMENSION_MISMATCH");
+
+        // Options that would make the search practically unbounded
+        let invalid = [
+            PlannerOptions { resolution: 1e-9, ..options },
+            PlannerOptions { step_size: 1e-9, ..options },
+            PlannerOptions { resolution: 0.0, ..options },
+            PlannerOptions { prismatic_range: [1.0, 0.0], ..options },
+            PlannerOptions { prismatic_range: [0.0, f64::INFINITY], ..options },
+        ];
+        for invalid in invalid {
+            let error = plan_motion(&robot, &[-1.0, 0.0], &[1.0, 0.0], &invalid, |_| true).unwrap_err();
+            assert_eq!(error.code(), "INVALID_ARGUMENT", "{:?}", invalid);
+        }
+
+        // A wall across the whole joint space cannot be passed
+        let few = PlannerOptions {
+            max_iterations: 200,
+            ..options
+        };
+        let error = plan_motion(&robot, &[-1.0, 0.0], &[1.0, 0.0], &few, |q| q[0].abs() > 0.1).unwrap_err();
+        assert_eq!(error.code(), "UNREACHABLE");
+    }
+}
@@ -0,0 +1,467 @@
+use crate::dh_parameters::{DHConvention, DHParameter, JointType};
+use crate::dynamics::LinkInertia;
+use crate::error::RoboticsError;
+use crate::geometry3d::Transform3D;
+use crate::limits::JointLimits;
+use crate::robot::RobotArm;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+/// Built-in robot models (DH parameters, joint limits and a home pose)
+///
+/// Lengths are in meters. Revolute limits are in radians, prismatic in meters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Preset {
+    /// Unimation PUMA 560, 6R with an offset spherical wrist
+    Puma560,
+    /// Stanford arm (Scheinman), RRP + spherical wrist
+    Stanford,
+    /// SCARA, RRPR with a vertical quill
+    Scara,
+    /// Universal Robots UR5, 6R collaborative arm
+    Ur5,
+    /// Universal Robots UR10, 6R collaborative arm
+    Ur10,
+    /// KUKA KR 6 R900 style 6R industrial arm
+    KukaKr6,
+    /// Franka Emika Panda style 7R redundant arm (Modified DH)
+    Panda,
+    /// Cylindrical RPP robot: base rotation, vertical lift, radial reach
+    Cylindrical,
+    /// Cartesian PPP gantry: vertical Z axis, then X and Y
+    Cartesian,
+}
+
+/// Summary of a preset for listing in the UI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresetInfo {
+    /// Identifier accepted by `Preset::from_name`
+    pub name: String,
+    pub description: String,
+    pub num_joints: usize,
+    pub joint_types: Vec<JointType>,
+    /// Joint values the robot starts at
+    pub home: Vec<f64>,
+}
+
+impl Preset {
+    pub const ALL: [Preset; 9] = [
+        Preset::Puma560,
+        Preset::Stanford,
+        Preset::Scara,
+        Preset::Ur5,
+        Preset::Ur10,
+        Preset::KukaKr6,
+        Preset::Panda,
+        Preset::Cylindrical,
+        Preset::Cartesian,
+    ];
+
+    /// Identifier used from JS (e.g. "puma560")
+    pub fn name(self) -> &'static str {
+        match self {
+            Preset::Puma560 => "puma560",
+            Preset::Stanford => "stanford",
+            Preset::Scara => "scara",
+            Preset::Ur5 => "ur5",
+            Preset::Ur10 => "ur10",
+            Preset::KukaKr6 => "kuka_kr6",
+            Preset::Panda => "panda",
+            Preset::Cylindrical => "cylindrical",
+            Preset::Cartesian => "cartesian",
+        }
+    }
+
+    /// Look up a preset by its identifier (case-insensitive)
+    pub fn from_name(name: &str) -> Result<Self, RoboticsError> {
+        Preset::ALL
+            .iter()
+            .copied()
+            .find(|preset| preset.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                let names: Vec<&str> = Preset::ALL.iter().map(|preset| preset.name()).collect();
+                RoboticsError::InvalidArgument(format!(
+                    "unknown robot preset '{}', expected one of: {}",
+                    name,
+                    names.join(", ")
+                ))
+            })
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Preset::Puma560 => "Unimation PUMA 560 (6R, offset spherical wrist)",
+            Preset::Stanford => "Stanford arm (RRP with spherical wrist)",
+            Preset::Scara => "SCARA (RRPR, vertical quill)",
+            Preset::Ur5 => "Universal Robots UR5 (6R, 850 mm reach)",
+            Preset::Ur10 => "Universal Robots UR10 (6R, 1300 mm reach)",
+            Preset::KukaKr6 => "KUKA KR 6 R900 style industrial arm (6R)",
+            Preset::Panda => "Franka Emika Panda style redundant arm (7R, Modified DH)",
+            Preset::Cylindrical => "Cylindrical robot (RPP)",
+            Preset::Cartesian => "Cartesian gantry (PPP)",
+        }
+    }
+
+    /// Link parameters of the model
+    pub fn dh_params(self) -> Vec<DHParameter> {
+        match self {
+            Preset::Puma560 => vec![
+                DHParameter::revolute(0.0, FRAC_PI_2, 0.0, 0.0),
+                DHParameter::revolute(0.4318, 0.0, 0.0, 0.0),
+                DHParameter::revolute(0.0203, -FRAC_PI_2, 0.15005, 0.0),
+                DHParameter::revolute(0.0, FRAC_PI_2, 0.4318, 0.0),
+                DHParameter::revolute(0.0, -FRAC_PI_2, 0.0, 0.0),
+                DHParameter::revolute(0.0, 0.0, 0.0, 0.0),
+            ],
+            Preset::Stanford => vec![
+                DHParameter::revolute(0.0, -FRAC_PI_2, 0.412, 0.0),
+                DHParameter::revolute(0.0, FRAC_PI_2, 0.154, 0.0),
+                DHParameter::prismatic(0.0203, 0.0, 0.0, -FRAC_PI_2),
+                DHParameter::revolute(0.0, -FRAC_PI_2, 0.0, 0.0),
+                DHParameter::revolute(0.0, FRAC_PI_2, 0.0, 0.0),
+                DHParameter::revolute(0.0, 0.0, 0.263, 0.0),
+            ],
+            Preset::Scara => vec![
+                DHParameter::revolute(0.325, 0.0, 0.387, 0.0),
+                DHParameter::revolute(0.275, PI, 0.0, 0.0),
+                DHParameter::prismatic(0.0, 0.0, 0.0, 0.0),
+                DHParameter::revolute(0.0, 0.0, 0.0, 0.0),
+            ],
+            Preset::Ur5 => universal_robot(0.089159, -0.425, -0.39225, 0.10915, 0.09465, 0.0823),
+            Preset::Ur10 => universal_robot(0.1273, -0.612, -0.5723, 0.163941, 0.1157, 0.0922),
+            Preset::KukaKr6 => vec![
+                DHParameter::revolute(0.025, -FRAC_PI_2, 0.400, 0.0),
+                DHParameter::revolute(0.455, 0.0, 0.0, 0.0),
+                DHParameter::revolute(0.035, -FRAC_PI_2, 0.0, -FRAC_PI_2),
+                DHParameter::revolute(0.0, FRAC_PI_2, 0.420, 0.0),
+                DHParameter::revolute(0.0, -FRAC_PI_2, 0.0, 0.0),
+                DHParameter::revolute(0.0, 0.0, 0.080, 0.0),
+            ],
+            Preset::Panda => [
+                (0.0, 0.0, 0.333),
+                (0.0, -FRAC_PI_2, 0.0),
+                (0.0, FRAC_PI_2, 0.316),
+                (0.0825, FRAC_PI_2, 0.0),
+                (-0.0825, -FRAC_PI_2, 0.384),
+                (0.0, FRAC_PI_2, 0.0),
+                (0.088, FRAC_PI_2, 0.0),
+            ]
+            .iter()
+            .map(|&(a, alpha, d)| DHParameter::revolute(a, alpha, d, 0.0).with_convention(DHConvention::Modified))
+            .collect(),
+            Preset::Cylindrical => vec![
+                DHParameter::revolute(0.0, 0.0, 0.5, 0.0),
+                DHParameter::prismatic(0.0, FRAC_PI_2, 0.0, FRAC_PI_2),
+                DHParameter::prismatic(0.0, 0.0, 0.0, 0.0),
+            ],
+            Preset::Cartesian => vec![
+                DHParameter::prismatic(0.0, FRAC_PI_2, 0.0, FRAC_PI_2),
+                DHParameter::prismatic(0.0, FRAC_PI_2, 0.0, FRAC_PI_2),
+                DHParameter::prismatic(0.0, 0.0, 0.0, 0.0),
+            ],
+        }
+    }
+
+    /// Joint limits of the model (position, plus velocity where the manufacturer publishes it)
+    pub fn limits(self) -> Vec<JointLimits> {
+        match self {
+            Preset::Puma560 => vec![
+                degrees(-160.0, 160.0),
+                degrees(-45.0, 225.0),
+                degrees(-225.0, 45.0),
+                degrees(-110.0, 170.0),
+                degrees(-100.0, 100.0),
+                degrees(-266.0, 266.0),
+            ],
+            Preset::Stanford => vec![
+                degrees(-170.0, 170.0),
+                degrees(-170.0, 170.0),
+                JointLimits::position(0.3048, 1.27),
+                degrees(-170.0, 170.0),
+                degrees(-90.0, 90.0),
+                degrees(-170.0, 170.0),
+            ],
+            Preset::Scara => vec![
+                degrees(-105.0, 105.0),
+                degrees(-157.5, 157.5),
+                JointLimits::position(0.0, 0.21),
+                degrees(-360.0, 360.0),
+            ],
+            Preset::Ur5 => with_velocities(vec![JointLimits::position(-2.0 * PI, 2.0 * PI); 6], &[PI; 6]),
+            Preset::Ur10 => with_velocities(
+                vec![JointLimits::position(-2.0 * PI, 2.0 * PI); 6],
+                &[2.0 * PI / 3.0, 2.0 * PI / 3.0, PI, PI, PI, PI],
+            ),
+            Preset::KukaKr6 => with_velocities(
+                vec![
+                    degrees(-170.0, 170.0),
+                    degrees(-190.0, 45.0),
+                    degrees(-120.0, 156.0),
+                    degrees(-185.0, 185.0),
+                    degrees(-120.0, 120.0),
+                    degrees(-350.0, 350.0),
+                ],
+                &[360.0, 300.0, 360.0, 381.0, 388.0, 615.0].map(f64::to_radians),
+            ),
+            Preset::Panda => with_velocities(
+                vec![
+                    JointLimits::position(-2.8973, 2.8973),
+                    JointLimits::position(-1.7628, 1.7628),
+                    JointLimits::position(-2.8973, 2.8973),
+                    JointLimits::position(-3.0718, -0.0698),
+                    JointLimits::position(-2.8973, 2.8973),
+                    JointLimits::position(-0.0175, 3.7525),
+                    JointLimits::position(-2.8973, 2.8973),
+                ],
+                &[2.175, 2.175, 2.175, 2.175, 2.61, 2.61, 2.61],
+            ),
+            Preset::Cylindrical => vec![
+                JointLimits::position(-PI, PI),
+                JointLimits::position(0.0, 1.0),
+                JointLimits::position(0.2, 1.0),
+            ],
+            Preset::Cartesian => vec![
+                JointLimits::position(0.0, 1.0),
+                JointLimits::position(0.0, 1.5),
+                JointLimits::position(0.0, 1.0),
+            ],
+        }
+    }
+
+    /// Joint values of the home (ready) pose
+    pub fn home(self) -> Vec<f64> {
+        match self {
+            Preset::Puma560 => vec![0.0, FRAC_PI_2, -FRAC_PI_2, 0.0, 0.0, 0.0],
+            Preset::Stanford => vec![0.0, 0.0, 0.5, 0.0, 0.0, 0.0],
+            Preset::Scara => vec![0.0, 0.0, 0.1, 0.0],
+            Preset::Ur5 | Preset::Ur10 => vec![0.0, -FRAC_PI_2, 0.0, -FRAC_PI_2, 0.0, 0.0],
+            Preset::KukaKr6 => vec![0.0, -FRAC_PI_2, FRAC_PI_2, 0.0, 0.0, 0.0],
+            Preset::Panda => vec![0.0, -FRAC_PI_4, 0.0, -3.0 * FRAC_PI_4, 0.0, FRAC_PI_2, FRAC_PI_4],
+            Preset::Cylindrical => vec![0.0, 0.5, 0.5],
+            Preset::Cartesian => vec![0.5, 0.75, 0.5],
+        }
+    }
+
+    /// Fixed flange offset after the last joint (identity unless the DH table stops short of the flange)
+    pub fn tool(self) -> Transform3D {
+        match self {
+            Preset::Panda => Transform3D::translation(0.0, 0.0, 0.107),
+            _ => Transform3D::identity(),
+        }
+    }
+
+    /// Published link mass properties, in the DH link frames (None where unknown)
+    ///
+    /// PUMA 560: Armstrong, Khatib and Burdick (1986) as tabulated by Corke.
+    /// UR5: masses and centers of mass from Universal Robots' DH table (no
+    /// inertia tensors are published, so the links are point masses).
+    pub fn inertias(self) -> Vec<Option<LinkInertia>> {
+        match self {
+            Preset::Puma560 => vec![
+                Some(LinkInertia::new(0.0, [0.0, 0.0, 0.0], [0.0, 0.35, 0.0, 0.0, 0.0, 0.0])),
+                Some(LinkInertia::new(17.4, [-0.3638, 0.006, 0.2275], [0.13, 0.524, 0.539, 0.0, 0.0, 0.0])),
+                Some(LinkInertia::new(4.8, [-0.0203, -0.0141, 0.070], [0.066, 0.086, 0.0125, 0.0, 0.0, 0.0])),
+                Some(LinkInertia::new(0.82, [0.0, 0.019, 0.0], [1.8e-3, 1.3e-3, 1.8e-3, 0.0, 0.0, 0.0])),
+                Some(LinkInertia::new(0.34, [0.0, 0.0, 0.0], [0.3e-3, 0.4e-3, 0.3e-3, 0.0, 0.0, 0.0])),
+                Some(LinkInertia::new(0.09, [0.0, 0.0, 0.032], [0.15e-3, 0.15e-3, 0.04e-3, 0.0, 0.0, 0.0])),
+            ],
+            Preset::Ur5 => vec![
+                Some(LinkInertia::point_mass(3.7, [0.0, -0.02561, 0.00193])),
+                Some(LinkInertia::point_mass(8.393, [0.2125, 0.0, 0.11336])),
+                Some(LinkInertia::point_mass(2.33, [0.15, 0.0, 0.0265])),
+                Some(LinkInertia::point_mass(1.219, [0.0, -0.0018, 0.01634])),
+                Some(LinkInertia::point_mass(1.219, [0.0, 0.0018, 0.01634])),
+                Some(LinkInertia::point_mass(0.1879, [0.0, 0.0, -0.001159])),
+            ],
+            _ => vec![None; self.dh_params().len()],
+        }
+    }
+
+    /// Build the robot with its name, limits, flange offset, link inertias and
+    /// home pose (also the start pose)
+    pub fn build(self) -> RobotArm {
+        let dh_params = self
+            .dh_params()
+            .into_iter()
+            .zip(self.inertias())
+            .map(|(dh, inertia)| DHParameter { inertia, ..dh })
+            .collect();
+        let mut robot = RobotArm::from_dh_params(dh_params);
+        robot.name = self.name().to_string();
+        robot.limits = self.limits();
+        robot.tool = self.tool();
+        robot.home = self.home();
+        robot.joint_angles = self.home();
+        robot
+    }
+
+    pub fn info(self) -> PresetInfo {
+        let dh_params = self.dh_params();
+
+        PresetInfo {
+            name: self.name().to_string(),
+            description: self.description().to_string(),
+            num_joints: dh_params.len(),
+            joint_types: dh_params.iter().map(|dh| dh.joint_type).collect(),
+            home: self.home(),
+        }
+    }
+}
+
+/// Summaries of every built-in preset
+pub fn list_presets() -> Vec<PresetInfo> {
+    Preset::ALL.iter().map(|preset| preset.info()).collect()
+}
+
+/// Universal Robots layout: shoulder, two parallel links along -X and a three-axis wrist
+fn universal_robot(d1: f64, a2: f64, a3: f64, d4: f64, d5: f64, d6: f64) -> Vec<DHParameter> {
+    vec![
+        DHParameter::revolute(0.0, FRAC_PI_2, d1, 0.0),
+        DHParameter::revolute(a2, 0.0, 0.0, 0.0),
+        DHParameter::revolute(a3, 0.0, 0.0, 0.0),
+        DHParameter::revolute(0.0, FRAC_PI_2, d4, 0.0),
+        DHParameter::revolute(0.0, -FRAC_PI_2, d5, 0.0),
+        DHParameter::revolute(0.0, 0.0, d6, 0.0),
+    ]
+}
+
+/// Revolute position limits given in degrees
+fn degrees(lower: f64, upper: f64) -> JointLimits {
+    JointLimits::position(lower.to_radians(), upper.to_radians())
+}
+
+/// Set the velocity limit of every joint
+fn with_velocities(mut limits: Vec<JointLimits>, velocities: &[f64]) -> Vec<JointLimits> {
+    for (limits, &velocity) in limits.iter_mut().zip(velocities) {
+        limits.velocity = velocity;
+    }
+    limits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kinematics::end_effector_transform;
+    use nalgebra::Matrix3;
+
+    const EPSILON: f64 = 1e-4;
+
+    fn pose_at(preset: Preset, joint_values: &[f64]) -> Transform3D {
+        let mut robot = preset.build();
+        robot.set_joint_angles(joint_values.to_vec()).unwrap();
+        end_effector_transform(&robot)
+    }
+
+    fn assert_position(transform: &Transform3D, expected: [f64; 3]) {
+        let position = transform.position();
+        let actual = [position.x, position.y, position.z];
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert!((a - e).abs() < EPSILON, "position {:?}, expected {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_every_preset_is_consistent() {
+        for preset in Preset::ALL {
+            let robot = preset.build();
+            let info = preset.info();
+
+            assert_eq!(Preset::from_name(preset.name()).unwrap(), preset);
+            assert_eq!(robot.num_joints(), info.num_joints);
+            assert_eq!(robot.limits.len(), info.num_joints);
+            assert_eq!(robot.joint_angles, preset.home());
+            assert!(robot.is_within_limits(), "{} home pose violates its limits", preset.name());
+            assert!(robot.limits.iter().all(|limits| limits.is_bounded()));
+        }
+        assert_eq!(list_presets().len(), Preset::ALL.len());
+    }
+
+    #[test]
+    fn test_unknown_preset_is_rejected() {
+        assert_eq!(Preset::from_name("PUMA560").unwrap(), Preset::Puma560);
+        assert_eq!(Preset::from_name("r2d2").unwrap_err().code(), "INVALID_ARGUMENT");
+    }
+
+    #[test]
+    fn test_puma560_reference_poses() {
+        // Corke, Robotics Toolbox: fkine(qz) = [I, (0.4521, -0.1500, 0.4318)]
+        let zero = pose_at(Preset::Puma560, &[0.0; 6]);
+        assert_position(&zero, [0.4521, -0.15005, 0.4318]);
+        assert!((zero.rotation_matrix() - Matrix3::identity()).abs().max() < EPSILON);
+
+        // Ready pose qr: the arm points straight up, 0.4318 + 0.4318 above the shoulder
+        let ready = pose_at(Preset::Puma560, &Preset::Puma560.home());
+        assert_position(&ready, [0.0203, -0.15005, 0.8636]);
+    }
+
+    #[test]
+    fn test_stanford_reference_pose() {
+        // Shoulder offset along Y, boom extended 0.5 m and the tool 0.263 m above the wrist
+        let home = pose_at(Preset::Stanford, &Preset::Stanford.home());
+        assert_position(&home, [0.0, 0.154 - 0.0203, 0.412 + 0.5 + 0.263]);
+    }
+
+    #[test]
+    fn test_scara_reference_poses() {
+        let stretched = pose_at(Preset::Scara, &[0.0, 0.0, 0.1, 0.0]);
+        assert_position(&stretched, [0.6, 0.0, 0.287]);
+        // The quill points down
+        assert!((stretched.rotation_matrix()[(2, 2)] + 1.0).abs() < EPSILON);
+
+        let bent = pose_at(Preset::Scara, &[FRAC_PI_2, -FRAC_PI_2, 0.2, 0.0]);
+        assert_position(&bent, [0.275, 0.325, 0.187]);
+    }
+
+    #[test]
+    fn test_universal_robots_zero_pose() {
+        // Zero pose: x = a2 + a3, y = -(d4 + d6), z = d1 - d5
+        assert_position(&pose_at(Preset::Ur5, &[0.0; 6]), [-0.81725, -0.19145, -0.005491]);
+        assert_position(&pose_at(Preset::Ur10, &[0.0; 6]), [-1.1843, -0.256141, 0.0116]);
+
+        // Upright home pose: both links and the wrist stand on the shoulder
+        assert_position(
+            &pose_at(Preset::Ur5, &Preset::Ur5.home()),
+            [0.0, -0.19145, 0.089159 + 0.425 + 0.39225 + 0.09465],
+        );
+    }
+
+    #[test]
+    fn test_kuka_reference_poses() {
+        // Stretched forward: reach 0.025 + 0.455 + 0.420 + 0.080, tool Z along world X
+        let stretched = pose_at(Preset::KukaKr6, &[0.0; 6]);
+        assert_position(&stretched, [0.98, 0.0, 0.435]);
+        assert!((stretched.rotation_matrix()[(0, 2)] - 1.0).abs() < EPSILON);
+
+        // HOME {0, -90, 90, 0, 0, 0}: upper arm vertical, forearm horizontal
+        assert_position(&pose_at(Preset::KukaKr6, &Preset::KukaKr6.home()), [0.525, 0.0, 0.89]);
+    }
+
+    #[test]
+    fn test_panda_reference_poses() {
+        // Zero pose (outside the range of joints 4 and 6, so the limits are lifted): all
+        // links stacked, flange 0.088 forward of the axis and pointing down
+        let mut robot = Preset::Panda.build();
+        robot.limits = vec![JointLimits::unbounded(); 7];
+        robot.set_joint_angles(vec![0.0; 7]).unwrap();
+        let zero = end_effector_transform(&robot);
+        assert_position(&zero, [0.088, 0.0, 0.333 + 0.316 + 0.384 - 0.107]);
+        assert!((zero.rotation_matrix()[(2, 2)] + 1.0).abs() < EPSILON);
+
+        // Ready pose: flange about 0.31 m in front of the base, pointing down
+        let ready = pose_at(Preset::Panda, &Preset::Panda.home());
+        assert_position(&ready, [0.30689, 0.0, 0.59034]);
+        assert!((ready.rotation_matrix()[(2, 2)] + 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_cylindrical_and_cartesian_reference_poses() {
+        let theta: f64 = 0.7;
+        assert_position(
+            &pose_at(Preset::Cylindrical, &[theta, 0.3, 0.8]),
+            [0.8 * theta.cos(), 0.8 * theta.sin(), 0.8],
+        );
+
+        // Joints move along Z, then X, then Y
+        assert_position(&pose_at(Preset::Cartesian, &[0.2, 0.9, 0.4]), [0.9, 0.4, 0.2]);
+    }
+}
@@ -1,19 +1,45 @@
-use crate::dh_parameters::DHParameter;
+use crate::dh_parameters::{DHParameter, JointType};
+use crate::dynamics::LinkInertia;
+use crate::error::{check_dimension, check_finite, RoboticsError};
+use crate::geometry3d::Transform3D;
+use crate::limits::{JointLimits, LimitPolicy};
+use crate::screw_model::ScrewModel;
 
 /// Robot arm configuration
 ///
-/// Can be configured in two ways:
+/// Can be configured in three ways:
 /// 1. Simple planar robot: link_lengths + joint_angles (Phase 1/2 style)
 /// 2. DH parameters: Full Denavit-Hartenberg specification (Phase 2b+)
+/// 3. Screw axes: Product of exponentials model (joint screws + home configuration)
+#[derive(Debug, Clone)]
 pub struct RobotArm {
     /// Optional DH parameters (if using DH convention)
     pub dh_params: Option<Vec<DHParameter>>,
+    /// Optional screw-axis model (if using product of exponentials)
+    pub screw_model: Option<ScrewModel>,
     /// Simple link lengths (used if dh_params is None)
     pub link_lengths: Vec<f64>,
     /// Current joint values (angles for revolute, distances for prismatic)
     pub joint_angles: Vec<f64>,
+    /// Per-joint position, velocity, acceleration and effort limits (unbounded by default)
+    pub limits: Vec<JointLimits>,
+    /// How `set_joint_angles` handles values outside the position limits
+    pub limit_policy: LimitPolicy,
+    /// Pose of the robot base in the world frame (identity by default)
+    pub base: Transform3D,
+    /// Tool center point in the last link frame (identity by default)
+    pub tool: Transform3D,
+    /// Display name
+    pub name: String,
+    /// Name of every joint ("joint_1", "joint_2", ... by default)
+    pub joint_names: Vec<String>,
+    /// Home (ready) pose, all zeros by default
+    pub home: Vec<f64>,
 }
 
+/// Name given to robots that were not loaded from a description
+const DEFAULT_NAME: &str = "robot";
+
 impl RobotArm {
     /// Create a simple planar robot from link lengths
     ///
@@ -23,8 +49,16 @@ impl RobotArm {
         let num_joints = link_lengths.len();
         RobotArm {
             dh_params: None,
+            screw_model: None,
             link_lengths,
             joint_angles: vec![0.0; num_joints],
+            limits: vec![JointLimits::unbounded(); num_joints],
+            limit_policy: LimitPolicy::default(),
+            base: Transform3D::identity(),
+            tool: Transform3D::identity(),
+            name: DEFAULT_NAME.to_string(),
+            joint_names: default_joint_names(num_joints),
+            home: vec![0.0; num_joints],
         }
     }
 
@@ -35,8 +69,16 @@ impl RobotArm {
         let num_joints = dh_params.len();
         RobotArm {
             dh_params: Some(dh_params),
+            screw_model: None,
             link_lengths: vec![], // Not used for DH robots
             joint_angles: vec![0.0; num_joints],
+            limits: vec![JointLimits::unbounded(); num_joints],
+            limit_policy: LimitPolicy::default(),
+            base: Transform3D::identity(),
+            tool: Transform3D::identity(),
+            name: DEFAULT_NAME.to_string(),
+            joint_names: default_joint_names(num_joints),
+            home: vec![0.0; num_joints],
         }
     }
 
@@ -53,28 +95,194 @@ impl RobotArm {
         let num_joints = dh_params.len();
         RobotArm {
             dh_params: Some(dh_params),
+            screw_model: None,
             link_lengths: vec![],
             joint_angles: vec![0.0; num_joints],
+            limits: vec![JointLimits::unbounded(); num_joints],
+            limit_policy: LimitPolicy::default(),
+            base: Transform3D::identity(),
+            tool: Transform3D::identity(),
+            name: DEFAULT_NAME.to_string(),
+            joint_names: default_joint_names(num_joints),
+            home: vec![0.0; num_joints],
+        }
+    }
+
+    /// Create a robot from a product of exponentials (screw axis) model
+    pub fn from_screw_model(screw_model: ScrewModel) -> Self {
+        let num_joints = screw_model.num_joints();
+        RobotArm {
+            dh_params: None,
+            screw_model: Some(screw_model),
+            link_lengths: vec![],
+            joint_angles: vec![0.0; num_joints],
+            limits: vec![JointLimits::unbounded(); num_joints],
+            limit_policy: LimitPolicy::default(),
+            base: Transform3D::identity(),
+            tool: Transform3D::identity(),
+            name: DEFAULT_NAME.to_string(),
+            joint_names: default_joint_names(num_joints),
+            home: vec![0.0; num_joints],
         }
     }
 
     /// Set the joint angles/positions for all joints
-    pub fn set_joint_angles(&mut self, angles: Vec<f64>) {
-        if angles.len() == self.joint_angles.len() {
-            self.joint_angles = angles;
+    ///
+    /// Values are passed through the robot's `limit_policy`. The robot is left
+    /// unchanged if the vector has the wrong length, contains NaN or infinite
+    /// values, or (with `LimitPolicy::Reject`) any value is out of range.
+    pub fn set_joint_angles(&mut self, angles: Vec<f64>) -> Result<(), RoboticsError> {
+        check_dimension(self.joint_angles.len(), angles.len())?;
+        check_finite(&angles)?;
+
+        let values = angles
+            .iter()
+            .zip(self.limits.iter())
+            .enumerate()
+            .map(|(i, (&value, limits))| self.limit_policy.apply(i, value, limits, self.joint_type(i)))
+            .collect::<Result<Vec<f64>, _>>()?;
+
+        self.joint_angles = values;
+        Ok(())
+    }
+
+    /// Set the limits of all joints
+    ///
+    /// The robot is left unchanged if the vector has the wrong length or any
+    /// limits are invalid (see `JointLimits::validate`).
+    pub fn set_joint_limits(&mut self, limits: Vec<JointLimits>) -> Result<(), RoboticsError> {
+        check_dimension(self.joint_angles.len(), limits.len())?;
+        for (i, joint_limits) in limits.iter().enumerate() {
+            joint_limits
+                .validate()
+                .map_err(|reason| RoboticsError::InvalidArgument(format!("joint {}: {}", i, reason)))?;
+        }
+        self.limits = limits;
+        Ok(())
+    }
+
+    /// Check if every joint value is within its position limits
+    pub fn is_within_limits(&self) -> bool {
+        self.joint_angles
+            .iter()
+            .zip(self.limits.iter())
+            .all(|(&value, limits)| limits.contains(value))
+    }
+
+    /// Signed distance of every joint to its nearest position limit (negative when outside)
+    pub fn distance_to_limits(&self) -> Vec<f64> {
+        self.joint_angles
+            .iter()
+            .zip(self.limits.iter())
+            .map(|(&value, limits)| limits.distance(value))
+            .collect()
+    }
+
+    /// Check if joint velocities respect the velocity limits
+    pub fn velocities_within_limits(&self, velocities: &[f64]) -> bool {
+        velocities.len() == self.limits.len()
+            && velocities
+                .iter()
+                .zip(self.limits.iter())
+                .all(|(&velocity, limits)| limits.allows_velocity(velocity))
+    }
+
+    /// Set the name of every joint
+    pub fn set_joint_names(&mut self, names: Vec<String>) -> Result<(), RoboticsError> {
+        check_dimension(self.joint_angles.len(), names.len())?;
+        self.joint_names = names;
+        Ok(())
+    }
+
+    /// Set the home pose (must be finite)
+    pub fn set_home(&mut self, home: Vec<f64>) -> Result<(), RoboticsError> {
+        check_dimension(self.joint_angles.len(), home.len())?;
+        check_finite(&home)?;
+        self.home = home;
+        Ok(())
+    }
+
+    /// Set the mass properties of every link (`None` for a massless link)
+    ///
+    /// Only DH robots carry link inertias. The robot is left unchanged if any
+    /// inertia is not physical (see `LinkInertia::validate`).
+    pub fn set_link_inertias(&mut self, inertias: Vec<Option<LinkInertia>>) -> Result<(), RoboticsError> {
+        check_dimension(self.joint_angles.len(), inertias.len())?;
+        let dh_params = match (&self.screw_model, &mut self.dh_params) {
+            (None, Some(dh_params)) => dh_params,
+            _ => return Err(RoboticsError::Unsupported("link inertias require a DH robot".to_string())),
+        };
+        for (index, inertia) in inertias.iter().enumerate() {
+            if let Some(Err(reason)) = inertia.map(|inertia| inertia.validate()) {
+                return Err(RoboticsError::MalformedDh { index, reason });
+            }
+        }
+
+        for (dh, inertia) in dh_params.iter_mut().zip(inertias) {
+            dh.inertia = inertia;
+        }
+        Ok(())
+    }
+
+    /// Mass properties of every link (all `None` for robots without DH links)
+    pub fn link_inertias(&self) -> Vec<Option<LinkInertia>> {
+        match &self.dh_params {
+            Some(dh_params) => dh_params.iter().map(|dh| dh.inertia).collect(),
+            None => vec![None; self.num_joints()],
         }
     }
 
+    /// Mount the robot: pose of its base frame in the world frame
+    ///
+    /// The transform must be finite; forward kinematics, Jacobians and IK are all
+    /// expressed in the world frame afterwards.
+    pub fn set_base_frame(&mut self, base: Transform3D) -> Result<(), RoboticsError> {
+        check_finite(&base.to_row_major())?;
+        self.base = base;
+        Ok(())
+    }
+
+    /// Attach a tool: pose of the tool center point (TCP) in the last link frame
+    ///
+    /// The transform must be finite; the end-effector reported by forward
+    /// kinematics and controlled by IK becomes the TCP.
+    pub fn set_tool_frame(&mut self, tool: Transform3D) -> Result<(), RoboticsError> {
+        check_finite(&tool.to_row_major())?;
+        self.tool = tool;
+        Ok(())
+    }
+
     /// Get the number of joints in the robot
     pub fn num_joints(&self) -> usize {
         self.joint_angles.len()
     }
 
+    /// Get the type of a joint (simple planar robots only have revolute joints)
+    pub fn joint_type(&self, index: usize) -> JointType {
+        if let Some(model) = &self.screw_model {
+            return if model.is_prismatic(index) {
+                JointType::Prismatic
+            } else {
+                JointType::Revolute
+            };
+        }
+
+        self.dh_params
+            .as_ref()
+            .and_then(|dh_params| dh_params.get(index))
+            .map_or(JointType::Revolute, |dh| dh.joint_type)
+    }
+
     /// Check if this robot uses DH parameters
     pub fn uses_dh_params(&self) -> bool {
         self.dh_params.is_some()
     }
 
+    /// Check if this robot uses a screw-axis (product of exponentials) model
+    pub fn uses_screw_model(&self) -> bool {
+        self.screw_model.is_some()
+    }
+
     /// Get DH parameters with current joint values applied
     ///
     /// Returns None if robot doesn't use DH parameters
@@ -89,6 +297,11 @@ impl RobotArm {
     }
 }
 
+/// "joint_1" ... "joint_n"
+fn default_joint_names(num_joints: usize) -> Vec<String> {
+    (1..=num_joints).map(|i| format!("joint_{}", i)).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -105,18 +318,121 @@ mod tests {
     #[test]
     fn test_set_joint_angles() {
         let mut robot = RobotArm::new(vec![2.0, 1.5]);
-        robot.set_joint_angles(vec![1.57, 0.78]);
+        robot.set_joint_angles(vec![1.57, 0.78]).unwrap();
         assert_eq!(robot.joint_angles, vec![1.57, 0.78]);
     }
 
     #[test]
     fn test_set_joint_angles_wrong_length() {
         let mut robot = RobotArm::new(vec![2.0, 1.5]);
-        robot.set_joint_angles(vec![1.57]);
+        let result = robot.set_joint_angles(vec![1.57]);
+        assert_eq!(result, Err(RoboticsError::DimensionMismatch { expected: 2, actual: 1 }));
         // Should not update if length doesn't match
         assert_eq!(robot.joint_angles, vec![0.0, 0.0]);
     }
 
+    #[test]
+    fn test_set_joint_angles_non_finite() {
+        let mut robot = RobotArm::new(vec![2.0, 1.5]);
+        let result = robot.set_joint_angles(vec![0.5, f64::NAN]);
+        assert_eq!(result.unwrap_err().code(), "NON_FINITE");
+        assert_eq!(robot.joint_angles, vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_base_and_tool_frames() {
+        let mut robot = RobotArm::new(vec![2.0, 1.5]);
+        assert_eq!(robot.base.matrix(), Transform3D::identity().matrix());
+        assert_eq!(robot.tool.matrix(), Transform3D::identity().matrix());
+
+        let tool = Transform3D::translation(0.1, 0.0, 0.2);
+        robot.set_tool_frame(tool).unwrap();
+        assert_eq!(robot.tool.matrix(), tool.matrix());
+
+        let result = robot.set_base_frame(Transform3D::translation(f64::NAN, 0.0, 0.0));
+        assert_eq!(result.unwrap_err().code(), "NON_FINITE");
+        assert_eq!(robot.base.matrix(), Transform3D::identity().matrix());
+    }
+
+    #[test]
+    fn test_set_link_inertias() {
+        let mut robot = RobotArm::planar(vec![1.0, 0.5]);
+        assert_eq!(robot.link_inertias(), vec![None, None]);
+
+        let inertia = LinkInertia::point_mass(2.0, [-0.5, 0.0, 0.0]);
+        robot.set_link_inertias(vec![Some(inertia), None]).unwrap();
+        assert_eq!(robot.link_inertias(), vec![Some(inertia), None]);
+
+        // Invalid values leave the robot unchanged
+        let result = robot.set_link_inertias(vec![None, Some(LinkInertia::point_mass(f64::NAN, [0.0; 3]))]);
+        assert!(matches!(result, Err(RoboticsError::MalformedDh { index: 1, .. })));
+        assert_eq!(robot.link_inertias(), vec![Some(inertia), None]);
+
+        let result = RobotArm::new(vec![1.0]).set_link_inertias(vec![Some(inertia)]);
+        assert_eq!(result.unwrap_err().code(), "UNSUPPORTED");
+    }
+
+    #[test]
+    fn test_limit_policies() {
+        let mut robot = RobotArm::new(vec![2.0, 1.5]);
+        robot.set_joint_limits(vec![JointLimits::position(-1.0, 1.0), JointLimits::unbounded()]).unwrap();
+
+        // Clamp (default)
+        robot.set_joint_angles(vec![2.0, 4.0]).unwrap();
+        assert_eq!(robot.joint_angles, vec![1.0, 4.0]);
+
+        // Reject leaves the robot unchanged
+        robot.limit_policy = LimitPolicy::Reject;
+        let violation = robot.set_joint_angles(vec![0.5, 0.0]).and(robot.set_joint_angles(vec![-1.5, 0.0]));
+        assert!(matches!(violation, Err(RoboticsError::LimitViolation(v)) if v.joint == 0));
+        assert_eq!(robot.joint_angles, vec![0.5, 0.0]);
+
+        // Wrap: the continuous second joint is brought back into (-π, π]
+        robot.limit_policy = LimitPolicy::Wrap;
+        robot.set_joint_angles(vec![0.0, 4.0]).unwrap();
+        assert!((robot.joint_angles[1] - (4.0 - 2.0 * std::f64::consts::PI)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_limit_queries() {
+        let mut robot = RobotArm::new(vec![2.0, 1.5]);
+        robot.set_joint_limits(vec![
+            JointLimits { velocity: 1.0, ..JointLimits::position(-1.0, 1.0) },
+            JointLimits::position(0.0, 2.0),
+        ]).unwrap();
+        robot.set_joint_angles(vec![0.25, 1.5]).unwrap();
+
+        assert!(robot.is_within_limits());
+        assert_eq!(robot.distance_to_limits(), vec![0.75, 0.5]);
+        assert!(robot.velocities_within_limits(&[-0.5, 10.0]));
+        assert!(!robot.velocities_within_limits(&[1.5, 0.0]));
+
+        robot.joint_angles[1] = -0.5;
+        assert!(!robot.is_within_limits());
+        assert_eq!(robot.distance_to_limits()[1], -0.5);
+    }
+
+    #[test]
+    fn test_invalid_limits_are_rejected() {
+        let mut robot = RobotArm::new(vec![2.0, 1.5]);
+        let valid = JointLimits::position(-1.0, 1.0);
+        let invalid = [
+            JointLimits::position(1.0, -1.0),
+            JointLimits::position(f64::NAN, 1.0),
+            JointLimits { velocity: 0.0, ..valid },
+            JointLimits { acceleration: -2.0, ..valid },
+            JointLimits { effort: -1.0, ..valid },
+            JointLimits { effort: f64::NAN, ..valid },
+        ];
+
+        for limits in invalid {
+            let error = robot.set_joint_limits(vec![valid, limits]).unwrap_err();
+            assert_eq!(error.code(), "INVALID_ARGUMENT");
+            assert!(error.to_string().contains("joint 1"), "{}", error);
+        }
+        assert_eq!(robot.limits, vec![JointLimits::unbounded(); 2]);
+    }
+
     #[test]
     fn test_planar_robot_with_dh() {
         let robot = RobotArm::planar(vec![2.0, 1.5]);
@@ -148,6 +464,34 @@ mod tests {
         assert_eq!(params[1].a, 1.5);
     }
 
+    #[test]
+    fn test_joint_types() {
+        let robot = RobotArm::from_dh_params(vec![
+            DHParameter::revolute(1.0, 0.0, 0.0, 0.0),
+            DHParameter::prismatic(0.0, 0.0, 0.5, 0.0),
+        ]);
+        assert_eq!(robot.joint_type(0), JointType::Revolute);
+        assert_eq!(robot.joint_type(1), JointType::Prismatic);
+
+        let simple = RobotArm::new(vec![2.0, 1.5]);
+        assert_eq!(simple.joint_type(1), JointType::Revolute);
+    }
+
+    #[test]
+    fn test_screw_model_robot_creation() {
+        let model = ScrewModel::from_dh_params(&[
+            DHParameter::revolute(1.0, 0.0, 0.0, 0.0),
+            DHParameter::prismatic(0.0, 0.0, 0.5, 0.0),
+        ]);
+
+        let robot = RobotArm::from_screw_model(model);
+        assert_eq!(robot.num_joints(), 2);
+        assert!(robot.uses_screw_model());
+        assert!(!robot.uses_dh_params());
+        assert_eq!(robot.joint_type(0), JointType::Revolute);
+        assert_eq!(robot.joint_type(1), JointType::Prismatic);
+    }
+
     #[test]
     fn test_simple_robot_no_dh() {
         let robot = RobotArm::new(vec![2.0, 1.5]);
@@ -0,0 +1,312 @@
+use crate::dh_parameters::{DHParameter, JointType};
+use crate::geometry3d::Transform3D;
+use crate::kinematics::{joint_frames, link_frames};
+use crate::robot::RobotArm;
+use nalgebra::{DMatrix, Vector3, Vector6};
+
+/// Robot described by joint screw axes (product of exponentials, Lynch & Park)
+///
+/// Each screw axis S_i = (ω_i, v_i) is expressed in the space (base) frame at the
+/// home configuration (all joint values zero):
+/// - Revolute: ω_i is the unit axis direction, v_i = -ω_i × q_i for a point q_i on the axis
+/// - Prismatic: ω_i = 0, v_i is the unit direction of motion
+///
+/// Space-frame forward kinematics: T(θ) = e^[S1]θ1 · ... · e^[Sn]θn · M
+#[derive(Debug, Clone)]
+pub struct ScrewModel {
+    /// Screw axes in the space frame, ordered (ω, v)
+    pub screw_axes: Vec<Vector6<f64>>,
+    /// Home pose of the frame at the end of every link; the last one is M (end-effector)
+    pub link_homes: Vec<Transform3D>,
+}
+
+impl ScrewModel {
+    /// Create a model from screw axes and the home pose of every link frame
+    ///
+    /// `link_homes[i]` is the frame at the end of link i+1, so the last entry is
+    /// the end-effector home configuration M.
+    pub fn new(screw_axes: Vec<Vector6<f64>>, link_homes: Vec<Transform3D>) -> Self {
+        ScrewModel {
+            screw_axes,
+            link_homes,
+        }
+    }
+
+    /// Create a model from screw axes and the end-effector home configuration M
+    ///
+    /// Intermediate link frames are placed on the next joint axis (closest point
+    /// to the base origin), which is where joints are drawn.
+    pub fn from_home(screw_axes: Vec<Vector6<f64>>, home: Transform3D) -> Self {
+        let mut link_homes = Vec::with_capacity(screw_axes.len());
+        let mut previous = Transform3D::identity();
+
+        for next_axis in screw_axes.iter().skip(1) {
+            let omega = Vector3::new(next_axis[0], next_axis[1], next_axis[2]);
+            let v = Vector3::new(next_axis[3], next_axis[4], next_axis[5]);
+
+            let frame = if omega.norm() > 1e-12 {
+                // Closest point to the origin on the axis: q = ω × v / |ω|²
+                let point = omega.cross(&v) / omega.norm_squared();
+                Transform3D::translation(point.x, point.y, point.z)
+            } else {
+                previous
+            };
+            link_homes.push(frame);
+            previous = frame;
+        }
+
+        if !screw_axes.is_empty() {
+            link_homes.push(home);
+        }
+
+        ScrewModel {
+            screw_axes,
+            link_homes,
+        }
+    }
+
+    /// Convert a DH chain (any convention) to screw axes
+    ///
+    /// The joint axes and link frames are taken at zero joint values, so DH joint
+    /// offsets are baked into the home configuration.
+    pub fn from_dh_params(dh_params: &[DHParameter]) -> Self {
+        let robot = RobotArm::from_dh_params(dh_params.to_vec());
+
+        let screw_axes = joint_frames(&robot)
+            .iter()
+            .enumerate()
+            .map(|(i, frame)| {
+                let axis: Vector3<f64> = frame.rotation_matrix().column(2).into_owned();
+                let origin = frame.position();
+                let point = Vector3::new(origin.x, origin.y, origin.z);
+
+                match robot.joint_type(i) {
+                    JointType::Revolute => {
+                        let v = -axis.cross(&point);
+                        Vector6::new(axis.x, axis.y, axis.z, v.x, v.y, v.z)
+                    }
+                    JointType::Prismatic => {
+                        Vector6::new(0.0, 0.0, 0.0, axis.x, axis.y, axis.z)
+                    }
+                }
+            })
+            .collect();
+
+        let link_homes = link_frames(&robot).into_iter().skip(1).collect();
+
+        ScrewModel {
+            screw_axes,
+            link_homes,
+        }
+    }
+
+    /// Number of joints
+    pub fn num_joints(&self) -> usize {
+        self.screw_axes.len()
+    }
+
+    /// End-effector home configuration M
+    pub fn home(&self) -> Transform3D {
+        self.link_homes.last().copied().unwrap_or_else(Transform3D::identity)
+    }
+
+    /// Whether joint `index` is prismatic (zero angular part)
+    pub fn is_prismatic(&self, index: usize) -> bool {
+        let axis = &self.screw_axes[index];
+        Vector3::new(axis[0], axis[1], axis[2]).norm() < 1e-12
+    }
+
+    /// Screw axes expressed in the end-effector frame: B_i = [Ad_M⁻¹] S_i
+    pub fn body_axes(&self) -> Vec<Vector6<f64>> {
+        let adjoint = self.home().inverse().adjoint();
+        self.screw_axes.iter().map(|axis| adjoint * axis).collect()
+    }
+
+    /// Space-frame forward kinematics: T = e^[S1]θ1 · ... · e^[Sn]θn · M
+    pub fn forward_kinematics_space(&self, joint_values: &[f64]) -> Transform3D {
+        self.exponential_product(joint_values).compose(&self.home())
+    }
+
+    /// Body-frame forward kinematics: T = M · e^[B1]θ1 · ... · e^[Bn]θn
+    pub fn forward_kinematics_body(&self, joint_values: &[f64]) -> Transform3D {
+        self.body_axes()
+            .iter()
+            .zip(joint_values)
+            .fold(self.home(), |frame, (axis, &value)| {
+                frame.compose(&Transform3D::exp(&(axis * value)))
+            })
+    }
+
+    /// World frame of every link: T_i = e^[S1]θ1 · ... · e^[Si]θi · M_i
+    pub fn link_frames(&self, joint_values: &[f64]) -> Vec<Transform3D> {
+        let mut product = Transform3D::identity();
+
+        self.screw_axes
+            .iter()
+            .zip(joint_values)
+            .zip(self.link_homes.iter())
+            .map(|((axis, &value), home)| {
+                product = product.compose(&Transform3D::exp(&(axis * value)));
+                product.compose(home)
+            })
+            .collect()
+    }
+
+    /// Space Jacobian (6×N, rows ordered ω then v)
+    ///
+    /// Column i is [Ad_{e^[S1]θ1 · ... · e^[S(i-1)]θ(i-1)}] S_i.
+    pub fn space_jacobian(&self, joint_values: &[f64]) -> DMatrix<f64> {
+        let mut jacobian = DMatrix::zeros(6, self.num_joints());
+        let mut product = Transform3D::identity();
+
+        for (i, (axis, &value)) in self.screw_axes.iter().zip(joint_values).enumerate() {
+            let column = product.adjoint() * axis;
+            jacobian.fixed_view_mut::<6, 1>(0, i).copy_from(&column);
+            product = product.compose(&Transform3D::exp(&(axis * value)));
+        }
+
+        jacobian
+    }
+
+    /// Body Jacobian (6×N, rows ordered ω then v)
+    ///
+    /// Column i is [Ad_{e^-[Bn]θn · ... · e^-[B(i+1)]θ(i+1)}] B_i.
+    pub fn body_jacobian(&self, joint_values: &[f64]) -> DMatrix<f64> {
+        let body_axes = self.body_axes();
+        let mut jacobian = DMatrix::zeros(6, self.num_joints());
+        let mut product = Transform3D::identity();
+
+        for i in (0..body_axes.len()).rev() {
+            let column = product.adjoint() * body_axes[i];
+            jacobian.fixed_view_mut::<6, 1>(0, i).copy_from(&column);
+            product = product.compose(&Transform3D::exp(&(body_axes[i] * -joint_values[i])));
+        }
+
+        jacobian
+    }
+
+    fn exponential_product(&self, joint_values: &[f64]) -> Transform3D {
+        self.screw_axes
+            .iter()
+            .zip(joint_values)
+            .fold(Transform3D::identity(), |frame, (axis, &value)| {
+                frame.compose(&Transform3D::exp(&(axis * value)))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dh_parameters::DHConvention;
+    use crate::kinematics::end_effector_transform;
+    use std::f64::consts::PI;
+
+    const EPSILON: f64 = 1e-9;
+
+    fn transform_approx_eq(a: &Transform3D, b: &Transform3D) -> bool {
+        (a.matrix() - b.matrix()).abs().max() < EPSILON
+    }
+
+    fn dh_chain() -> Vec<DHParameter> {
+        vec![
+            DHParameter::revolute(0.0, PI / 2.0, 0.4, 0.1),
+            DHParameter::revolute(0.8, 0.0, 0.0, -PI / 2.0),
+            DHParameter::prismatic(0.0, -PI / 2.0, 0.2, 0.0),
+            DHParameter::revolute(0.3, PI / 2.0, 0.1, 0.0),
+        ]
+    }
+
+    /// Planar 3R example from Lynch & Park (Example 4.1 layout, L1 = L2 = L3 = 1)
+    fn planar_3r() -> ScrewModel {
+        let axes = vec![
+            Vector6::new(0.0, 0.0, 1.0, 0.0, 0.0, 0.0),
+            Vector6::new(0.0, 0.0, 1.0, 0.0, -1.0, 0.0),
+            Vector6::new(0.0, 0.0, 1.0, 0.0, -2.0, 0.0),
+        ];
+        ScrewModel::from_home(axes, Transform3D::translation(3.0, 0.0, 0.0))
+    }
+
+    #[test]
+    fn test_planar_3r_forward_kinematics() {
+        let model = planar_3r();
+        let transform = model.forward_kinematics_space(&[PI / 2.0, -PI / 2.0, 0.0]);
+
+        // Link 1 points up (0, 1), links 2 and 3 point along +X
+        let position = transform.position();
+        assert!((position.x - 2.0).abs() < EPSILON);
+        assert!((position.y - 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_space_and_body_forward_kinematics_agree() {
+        let model = ScrewModel::from_dh_params(&dh_chain());
+        let values = [0.3, -0.7, 0.15, 1.2];
+
+        let space = model.forward_kinematics_space(&values);
+        let body = model.forward_kinematics_body(&values);
+        assert!(transform_approx_eq(&space, &body));
+    }
+
+    #[test]
+    fn test_dh_conversion_matches_dh_forward_kinematics() {
+        for convention in [DHConvention::Standard, DHConvention::Modified] {
+            let dh_params: Vec<DHParameter> = dh_chain().iter().map(|dh| dh.with_convention(convention)).collect();
+            let model = ScrewModel::from_dh_params(&dh_params);
+
+            let mut robot = RobotArm::from_dh_params(dh_params);
+            let values = vec![0.3, -0.7, 0.15, 1.2];
+            robot.set_joint_angles(values.clone()).unwrap();
+
+            let expected = link_frames(&robot);
+            let actual = model.link_frames(&values);
+            for (e, a) in expected.iter().skip(1).zip(actual.iter()) {
+                assert!(transform_approx_eq(e, a));
+            }
+            assert!(transform_approx_eq(&end_effector_transform(&robot), &model.forward_kinematics_space(&values)));
+        }
+    }
+
+    #[test]
+    fn test_prismatic_axis_detected() {
+        let model = ScrewModel::from_dh_params(&dh_chain());
+        assert!(!model.is_prismatic(0));
+        assert!(model.is_prismatic(2));
+    }
+
+    #[test]
+    fn test_space_jacobian_matches_finite_differences() {
+        let model = ScrewModel::from_dh_params(&dh_chain());
+        let values = [0.3, -0.7, 0.15, 1.2];
+        let jacobian = model.space_jacobian(&values);
+        let base = model.forward_kinematics_space(&values);
+
+        let step = 1e-7;
+        for joint in 0..values.len() {
+            let mut moved_values = values;
+            moved_values[joint] += step;
+            let moved = model.forward_kinematics_space(&moved_values);
+
+            // Space twist: log(T' T⁻¹) / step
+            let twist = moved.compose(&base.inverse()).log() / step;
+            for row in 0..6 {
+                assert!((jacobian[(row, joint)] - twist[row]).abs() < 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn test_body_jacobian_is_adjoint_of_space_jacobian() {
+        let model = ScrewModel::from_dh_params(&dh_chain());
+        let values = [0.3, -0.7, 0.15, 1.2];
+
+        let space = model.space_jacobian(&values);
+        let body = model.body_jacobian(&values);
+        let transform = model.forward_kinematics_space(&values);
+
+        // J_b = [Ad_T⁻¹] J_s
+        let adjoint = transform.inverse().adjoint();
+        let expected = DMatrix::from_iterator(6, 6, adjoint.iter().copied()) * space;
+        assert!((body - expected).abs().max() < EPSILON);
+    }
+}
@@ -0,0 +1,374 @@
+use crate::dynamics::forward_dynamics;
+use crate::error::{check_dimension, check_finite, RoboticsError};
+use crate::robot::RobotArm;
+use crate::trajectory::TrajectoryPoint;
+use serde::{Deserialize, Serialize};
+
+/// Largest number of integration steps a single `step` call may take
+pub const MAX_SUBSTEPS: usize = 100_000;
+
+/// Numerical integration scheme for the joint state
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Integrator {
+    /// Symplectic Euler: update the velocity first, then move with the new
+    /// velocity (one dynamics evaluation per step, energy stays bounded)
+    #[default]
+    SemiImplicitEuler,
+    /// Classic 4th-order Runge-Kutta (four dynamics evaluations per step)
+    Rk4,
+}
+
+/// Options for the physics simulation
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SimulationOptions {
+    pub integrator: Integrator,
+    /// Largest integration step (seconds); longer `step` calls are subdivided
+    pub max_step: f64,
+}
+
+impl Default for SimulationOptions {
+    fn default() -> Self {
+        SimulationOptions {
+            integrator: Integrator::default(),
+            max_step: 1e-3,
+        }
+    }
+}
+
+impl SimulationOptions {
+    /// Check that `max_step` is positive
+    pub fn validate(&self) -> Result<(), RoboticsError> {
+        if !self.max_step.is_finite() || self.max_step <= 0.0 {
+            return Err(RoboticsError::InvalidArgument(format!(
+                "max_step must be positive, got {}",
+                self.max_step
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Time-stepping forward dynamics of a robot arm
+///
+/// The joint positions live in the robot (`joint_angles`); the simulation keeps
+/// the joint velocities and the simulated time. Applied torques are clamped to
+/// the joints' effort limits, and joints stop dead at their position limits.
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    pub options: SimulationOptions,
+    /// Simulated time (seconds)
+    pub time: f64,
+    /// Joint velocities (rad/s or m/s)
+    pub velocities: Vec<f64>,
+}
+
+impl Simulation {
+    /// A robot at rest at time zero
+    pub fn new(num_joints: usize) -> Self {
+        Simulation {
+            options: SimulationOptions::default(),
+            time: 0.0,
+            velocities: vec![0.0; num_joints],
+        }
+    }
+
+    /// Stop every joint and rewind the clock (positions are left as they are)
+    pub fn reset(&mut self) {
+        self.time = 0.0;
+        self.velocities.iter_mut().for_each(|velocity| *velocity = 0.0);
+    }
+
+    /// Advance the robot by `dt` seconds under constant applied `torques`
+    ///
+    /// The interval is split into steps of at most `options.max_step`. Gravity
+    /// is a world-frame acceleration. Returns the new joint state, with the
+    /// accelerations the torques produce at the end of the interval. On error
+    /// the robot and the simulation are left unchanged; intervals needing more
+    /// than `MAX_SUBSTEPS` steps are rejected.
+    pub fn step(
+        &mut self,
+        robot: &mut RobotArm,
+        torques: &[f64],
+        dt: f64,
+        gravity: [f64; 3],
+    ) -> Result<TrajectoryPoint, RoboticsError> {
+        check_dimension(robot.num_joints(), torques.len())?;
+        check_finite(torques)?;
+
+        self.step_with(robot, dt, gravity, |_, _, _| Ok(torques.to_vec()))
+    }
+
+    /// Advance the robot by `dt` seconds under torques chosen by a controller
+    ///
+    /// `control(robot, velocities, h)` is called at the start of every
+    /// integration step of `h` seconds, with the robot at that step's joint
+    /// positions, and its torques are held for the whole step. Otherwise the
+    /// same as `step`; the accelerations returned are those of the last torques.
+    pub fn step_with(
+        &mut self,
+        robot: &mut RobotArm,
+        dt: f64,
+        gravity: [f64; 3],
+        mut control: impl FnMut(&RobotArm, &[f64], f64) -> Result<Vec<f64>, RoboticsError>,
+    ) -> Result<TrajectoryPoint, RoboticsError> {
+        check_dimension(robot.num_joints(), self.velocities.len())?;
+        if !dt.is_finite() || dt <= 0.0 {
+            return Err(RoboticsError::InvalidArgument(format!("time step must be positive, got {}", dt)));
+        }
+        self.options.validate()?;
+        let substeps = (dt / self.options.max_step).ceil().max(1.0);
+        if substeps > MAX_SUBSTEPS as f64 {
+            return Err(RoboticsError::InvalidArgument(format!(
+                "time step {} needs more than {} steps of {}",
+                dt, MAX_SUBSTEPS, self.options.max_step
+            )));
+        }
+        let substeps = substeps as usize;
+
+        let start = robot.joint_angles.clone();
+        let mut velocities = self.velocities.clone();
+        let h = dt / substeps as f64;
+        let mut torques = Vec::new();
+
+        for _ in 0..substeps {
+            match self.substep(robot, &mut velocities, &mut control, h, gravity) {
+                Ok(applied) => torques = applied,
+                Err(error) => {
+                    robot.joint_angles = start;
+                    return Err(error);
+                }
+            }
+        }
+
+        let final_accelerations = match forward_dynamics(robot, &robot.joint_angles, &velocities, &torques, gravity) {
+            Ok(accelerations) => accelerations,
+            Err(error) => {
+                robot.joint_angles = start;
+                return Err(error);
+            }
+        };
+        self.velocities = velocities;
+        self.time += dt;
+
+        Ok(TrajectoryPoint {
+            time: self.time,
+            positions: robot.joint_angles.clone(),
+            velocities: self.velocities.clone(),
+            accelerations: final_accelerations,
+        })
+    }
+
+    /// One integration step of `h` seconds; returns the (effort-clamped) torques applied
+    fn substep(
+        &self,
+        robot: &mut RobotArm,
+        velocities: &mut [f64],
+        control: &mut impl FnMut(&RobotArm, &[f64], f64) -> Result<Vec<f64>, RoboticsError>,
+        h: f64,
+        gravity: [f64; 3],
+    ) -> Result<Vec<f64>, RoboticsError> {
+        let torques = control(robot, velocities, h)?;
+        check_dimension(robot.num_joints(), torques.len())?;
+        check_finite(&torques)?;
+        let torques: Vec<f64> = torques
+            .iter()
+            .zip(&robot.limits)
+            .map(|(&torque, limits)| limits.clamp_effort(torque))
+            .collect();
+
+        let mut positions = robot.joint_angles.clone();
+        let accelerations = |positions: &[f64], velocities: &[f64]| {
+            forward_dynamics(robot, positions, velocities, &torques, gravity)
+        };
+
+        match self.options.integrator {
+            Integrator::SemiImplicitEuler => {
+                let qdd = accelerations(&positions, velocities)?;
+                for i in 0..positions.len() {
+                    velocities[i] += h * qdd[i];
+                    positions[i] += h * velocities[i];
+                }
+            }
+            Integrator::Rk4 => {
+                let k1 = (velocities.to_vec(), accelerations(&positions, velocities)?);
+                let (q2, v2) = advance(&positions, velocities, &k1, h / 2.0);
+                let k2 = (v2.clone(), accelerations(&q2, &v2)?);
+                let (q3, v3) = advance(&positions, velocities, &k2, h / 2.0);
+                let k3 = (v3.clone(), accelerations(&q3, &v3)?);
+                let (q4, v4) = advance(&positions, velocities, &k3, h);
+                let k4 = (v4.clone(), accelerations(&q4, &v4)?);
+
+                for i in 0..positions.len() {
+                    positions[i] += h / 6.0 * (k1.0[i] + 2.0 * k2.0[i] + 2.0 * k3.0[i] + k4.0[i]);
+                    velocities[i] += h / 6.0 * (k1.1[i] + 2.0 * k2.1[i] + 2.0 * k3.1[i] + k4.1[i]);
+                }
+            }
+        }
+        stop_at_limits(robot, &mut positions, velocities);
+        robot.joint_angles = positions;
+
+        Ok(torques)
+    }
+}
+
+/// State after moving for `h` seconds with the derivative `(q̇, q̈)` (one RK4 stage)
+fn advance(positions: &[f64], velocities: &[f64], derivative: &(Vec<f64>, Vec<f64>), h: f64) -> (Vec<f64>, Vec<f64>) {
+    let positions = positions.iter().zip(&derivative.0).map(|(q, qd)| q + h * qd).collect();
+    let velocities = velocities.iter().zip(&derivative.1).map(|(qd, qdd)| qd + h * qdd).collect();
+    (positions, velocities)
+}
+
+/// Clamp positions into the joint limits, removing any velocity into the limit
+fn stop_at_limits(robot: &RobotArm, positions: &mut [f64], velocities: &mut [f64]) {
+    for ((position, velocity), limits) in positions.iter_mut().zip(velocities.iter_mut()).zip(&robot.limits) {
+        if *position < limits.lower {
+            *position = limits.lower;
+            *velocity = velocity.max(0.0);
+        } else if *position > limits.upper {
+            *position = limits.upper;
+            *velocity = velocity.min(0.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dh_parameters::DHParameter;
+    use crate::dynamics::{mass_matrix, LinkInertia};
+    use crate::kinematics::link_frames;
+    use crate::limits::JointLimits;
+
+    const G: f64 = 9.81;
+
+    fn lift(mass: f64) -> RobotArm {
+        RobotArm::from_dh_params(vec![
+            DHParameter::prismatic(0.0, 0.0, 0.0, 0.0).with_inertia(LinkInertia::point_mass(mass, [0.0; 3])),
+        ])
+    }
+
+    /// Vertical double pendulum: planar arm in the XY plane with gravity along -Y
+    fn double_pendulum() -> RobotArm {
+        let mut robot = RobotArm::from_dh_params(vec![
+            DHParameter::planar(1.0).with_inertia(LinkInertia::new(1.0, [-0.5, 0.0, 0.0], [0.0, 0.08, 0.08, 0.0, 0.0, 0.0])),
+            DHParameter::planar(0.8).with_inertia(LinkInertia::point_mass(0.7, [0.0; 3])),
+        ]);
+        robot.joint_angles = vec![0.3, 1.2];
+        robot
+    }
+
+    fn energy(robot: &RobotArm, velocities: &[f64]) -> f64 {
+        let mass = mass_matrix(robot, &robot.joint_angles).unwrap();
+        let velocity = nalgebra::DVector::from_column_slice(velocities);
+        let kinetic = 0.5 * (velocity.transpose() * mass * &velocity)[(0, 0)];
+
+        let frames = link_frames(robot);
+        let potential: f64 = robot
+            .dh_params
+            .as_ref()
+            .unwrap()
+            .iter()
+            .zip(&frames[1..])
+            .map(|(dh, frame)| {
+                let inertia = dh.inertia.unwrap();
+                let center = frame.rotation_matrix() * inertia.center() + frame.translation_vector();
+                inertia.mass * G * center.y
+            })
+            .sum();
+
+        kinetic + potential
+    }
+
+    #[test]
+    fn test_free_fall_matches_closed_form() {
+        for integrator in [Integrator::SemiImplicitEuler, Integrator::Rk4] {
+            let mut robot = lift(2.0);
+            let mut simulation = Simulation::new(1);
+            simulation.options.integrator = integrator;
+
+            let state = simulation.step(&mut robot, &[0.0], 0.5, [0.0, 0.0, -G]).unwrap();
+
+            // Semi-implicit Euler overshoots by g·h·t/2 (h = 1 ms); RK4 is exact for constant acceleration
+            let tolerance = if integrator == Integrator::Rk4 { 1e-12 } else { 5e-3 };
+            assert!((state.positions[0] + 0.5 * G * 0.25).abs() < tolerance, "{:?}", state);
+            assert!((state.velocities[0] + G * 0.5).abs() < 1e-12);
+            assert!((state.accelerations[0] + G).abs() < 1e-12);
+            assert!((simulation.time - 0.5).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_double_pendulum_conserves_energy() {
+        // Relative drift over one second of chaotic swinging: RK4 is near exact,
+        // symplectic Euler oscillates around the true energy without growing
+        for (integrator, tolerance) in [(Integrator::Rk4, 1e-7), (Integrator::SemiImplicitEuler, 3e-2)] {
+            let mut robot = double_pendulum();
+            let mut simulation = Simulation::new(2);
+            simulation.options.integrator = integrator;
+            let initial = energy(&robot, &simulation.velocities);
+
+            for _ in 0..60 {
+                simulation.step(&mut robot, &[0.0, 0.0], 1.0 / 60.0, [0.0, -G, 0.0]).unwrap();
+                let drift = ((energy(&robot, &simulation.velocities) - initial) / initial).abs();
+                assert!(drift < tolerance, "{:?} drifted by {}", integrator, drift);
+            }
+            assert!(simulation.velocities.iter().any(|velocity| velocity.abs() > 1.0));
+        }
+    }
+
+    #[test]
+    fn test_limits_stop_joints_and_clamp_effort() {
+        let mut robot = lift(1.0);
+        robot.limits = vec![JointLimits {
+            effort: 5.0,
+            ..JointLimits::position(-0.1, 1.0)
+        }];
+        let mut simulation = Simulation::new(1);
+
+        // 5 N cannot hold 1 kg: the joint falls onto its lower stop and stays there
+        let state = simulation.step(&mut robot, &[100.0], 1.0, [0.0, 0.0, -G]).unwrap();
+        assert_eq!(state.positions, vec![-0.1]);
+        assert_eq!(state.velocities, vec![0.0]);
+        assert!((state.accelerations[0] - (5.0 - G)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_steps_leave_robot_unchanged() {
+        let mut robot = double_pendulum();
+        let mut simulation = Simulation::new(2);
+
+        for dt in [0.0, -0.1, f64::NAN] {
+            let error = simulation.step(&mut robot, &[0.0, 0.0], dt, [0.0, -G, 0.0]).unwrap_err();
+            assert_eq!(error.code(), "INVALID_ARGUMENT");
+        }
+        let error = simulation.step(&mut robot, &[0.0], 0.01, [0.0, -G, 0.0]).unwrap_err();
+        assert_eq!(error.code(), "DIMENSION_MISMATCH");
+
+        let mut massless = RobotArm::planar(vec![1.0, 1.0]);
+        let error = simulation.step(&mut massless, &[0.0, 0.0], 0.01, [0.0, -G, 0.0]).unwrap_err();
+        assert_eq!(error.code(), "UNSUPPORTED");
+        assert_eq!(massless.joint_angles, vec![0.0, 0.0]);
+
+        // Far too many integration steps
+        let error = simulation.step(&mut robot, &[0.0, 0.0], 1e9, [0.0, -G, 0.0]).unwrap_err();
+        assert_eq!(error.code(), "INVALID_ARGUMENT");
+        simulation.options.max_step = 0.0;
+        let error = simulation.step(&mut robot, &[0.0, 0.0], 0.01, [0.0, -G, 0.0]).unwrap_err();
+        assert_eq!(error.code(), "INVALID_ARGUMENT");
+
+        assert_eq!(robot.joint_angles, vec![0.3, 1.2]);
+        assert_eq!(simulation.time, 0.0);
+    }
+
+    #[test]
+    fn test_bad_effort_limits_do_not_panic() {
+        let mut robot = lift(1.0);
+        let mut simulation = Simulation::new(1);
+
+        for effort in [-1.0, f64::NAN] {
+            robot.limits = vec![JointLimits { effort, ..JointLimits::unbounded() }];
+            assert!(simulation.step(&mut robot, &[100.0], 0.01, [0.0, 0.0, -G]).is_ok());
+        }
+    }
+}
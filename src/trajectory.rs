@@ -0,0 +1,527 @@
+use crate::error::{check_dimension, check_finite, RoboticsError};
+use crate::limits::LimitViolation;
+use crate::robot::RobotArm;
+use serde::{Deserialize, Serialize};
+
+/// Peak |ṡ| of the rest-to-rest quintic s(τ) = 10τ³ - 15τ⁴ + 6τ⁵ (at τ = 1/2)
+const QUINTIC_PEAK_VELOCITY: f64 = 15.0 / 8.0;
+
+/// Peak |s̈| of the rest-to-rest quintic (at τ = 1/2 ± √3/6)
+const QUINTIC_PEAK_ACCELERATION: f64 = 5.773_502_691_896_258; // 10 / √3
+
+/// Time scaling used for every segment between two waypoints
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Profile {
+    /// Cubic polynomial, zero velocity at both ends
+    Cubic,
+    /// Quintic polynomial, zero velocity and acceleration at both ends
+    #[default]
+    Quintic,
+    /// Constant acceleration, cruise, constant deceleration
+    Trapezoidal,
+    /// Trapezoidal velocity with constant-jerk ramps (triangular acceleration pulses)
+    SCurve,
+}
+
+/// Options for trajectory generation
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrajectoryOptions {
+    pub profile: Profile,
+    /// Velocity limit used for joints without a finite one
+    pub default_velocity: f64,
+    /// Acceleration limit used for joints without a finite one
+    pub default_acceleration: f64,
+    /// Lower bound on the duration of every segment (seconds)
+    pub min_segment_duration: f64,
+}
+
+impl Default for TrajectoryOptions {
+    fn default() -> Self {
+        TrajectoryOptions {
+            profile: Profile::default(),
+            default_velocity: 1.0,
+            default_acceleration: 2.0,
+            min_segment_duration: 0.0,
+        }
+    }
+}
+
+/// Motion of one joint within a segment
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct JointMotion {
+    /// Signed displacement over the segment
+    pub distance: f64,
+    /// Cruise speed (trapezoidal and S-curve profiles, zero otherwise)
+    pub cruise_velocity: f64,
+    /// Duration of the acceleration (and of the deceleration) phase
+    pub ramp_time: f64,
+}
+
+/// Rest-to-rest motion between two consecutive waypoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Segment {
+    /// Time at which the segment starts
+    pub start_time: f64,
+    /// Common duration of all joints (the slowest joint sets it)
+    pub duration: f64,
+    /// Joint values at the start of the segment
+    pub start: Vec<f64>,
+    pub joints: Vec<JointMotion>,
+}
+
+/// Joint state at one instant of a trajectory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrajectoryPoint {
+    pub time: f64,
+    pub positions: Vec<f64>,
+    pub velocities: Vec<f64>,
+    pub accelerations: Vec<f64>,
+}
+
+/// Time-parameterized joint trajectory through a list of waypoints
+///
+/// The arm comes to rest at every waypoint. All joints of a segment start and
+/// stop together, each with its own cruise speed, so the motion stays
+/// coordinated while every joint respects its velocity and acceleration limits.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JointTrajectory {
+    pub profile: Profile,
+    pub segments: Vec<Segment>,
+    /// Final waypoint (held after the end of the trajectory)
+    pub end: Vec<f64>,
+    /// Total duration (seconds)
+    pub duration: f64,
+}
+
+impl JointTrajectory {
+    /// Time at which each waypoint is reached
+    pub fn waypoint_times(&self) -> Vec<f64> {
+        let mut times: Vec<f64> = self.segments.iter().map(|segment| segment.start_time).collect();
+        times.push(self.duration);
+        times
+    }
+
+    /// Joint state at time `t` (clamped to [0, duration])
+    pub fn sample(&self, t: f64) -> TrajectoryPoint {
+        let t = t.clamp(0.0, self.duration);
+        let num_joints = self.end.len();
+
+        // Last segment starting at or before t (segments are sorted by start time)
+        let index = self.segments.partition_point(|segment| segment.start_time <= t);
+        let segment = match index.checked_sub(1).and_then(|i| self.segments.get(i)) {
+            Some(segment) if t < segment.start_time + segment.duration => segment,
+            _ => {
+                return TrajectoryPoint {
+                    time: t,
+                    positions: if t <= 0.0 {
+                        self.segments.first().map_or_else(|| self.end.clone(), |s| s.start.clone())
+                    } else {
+                        self.end.clone()
+                    },
+                    velocities: vec![0.0; num_joints],
+                    accelerations: vec![0.0; num_joints],
+                }
+            }
+        };
+
+        let local = t - segment.start_time;
+        let mut point = TrajectoryPoint {
+            time: t,
+            positions: Vec::with_capacity(num_joints),
+            velocities: Vec::with_capacity(num_joints),
+            accelerations: Vec::with_capacity(num_joints),
+        };
+        for (&start, motion) in segment.start.iter().zip(&segment.joints) {
+            let (position, velocity, acceleration) = evaluate(self.profile, motion, segment.duration, local);
+            point.positions.push(start + position);
+            point.velocities.push(velocity);
+            point.accelerations.push(acceleration);
+        }
+        point
+    }
+}
+
+/// Generates a synchronized joint trajectory through `waypoints`
+///
+/// Each segment gets the shortest duration for which every joint stays within
+/// its velocity and acceleration limits (joints without finite limits use the
+/// defaults from `options`), at least `min_segment_duration`. Waypoints must
+/// match the robot's joint count and lie within its position limits.
+pub fn plan_trajectory(
+    robot: &RobotArm,
+    waypoints: &[Vec<f64>],
+    options: &TrajectoryOptions,
+) -> Result<JointTrajectory, RoboticsError> {
+    if waypoints.len() < 2 {
+        return Err(RoboticsError::InvalidArgument(format!(
+            "a trajectory needs at least two waypoints, got {}",
+            waypoints.len()
+        )));
+    }
+    for (name, value) in [
+        ("default velocity", options.default_velocity),
+        ("default acceleration", options.default_acceleration),
+    ] {
+        if !(value > 0.0 && value.is_finite()) {
+            return Err(RoboticsError::InvalidArgument(format!("{} must be positive, got {}", name, value)));
+        }
+    }
+    if !(options.min_segment_duration >= 0.0 && options.min_segment_duration.is_finite()) {
+        return Err(RoboticsError::InvalidArgument(format!(
+            "minimum segment duration must be non-negative, got {}",
+            options.min_segment_duration
+        )));
+    }
+
+    for waypoint in waypoints {
+        check_dimension(robot.num_joints(), waypoint.len())?;
+        check_finite(waypoint)?;
+        for (joint, (&value, limits)) in waypoint.iter().zip(&robot.limits).enumerate() {
+            if !limits.contains(value) {
+                return Err(LimitViolation {
+                    joint,
+                    value,
+                    lower: limits.lower,
+                    upper: limits.upper,
+                }
+                .into());
+            }
+        }
+    }
+
+    let bounds: Vec<(f64, f64)> = robot
+        .limits
+        .iter()
+        .map(|limits| {
+            let velocity = if limits.velocity.is_finite() { limits.velocity } else { options.default_velocity };
+            let acceleration = if limits.acceleration.is_finite() {
+                limits.acceleration
+            } else {
+                options.default_acceleration
+            };
+            (velocity, acceleration)
+        })
+        .collect();
+
+    let mut segments = Vec::with_capacity(waypoints.len() - 1);
+    let mut start_time = 0.0;
+
+    for pair in waypoints.windows(2) {
+        let distances: Vec<f64> = pair[0].iter().zip(&pair[1]).map(|(a, b)| b - a).collect();
+
+        let duration = distances
+            .iter()
+            .zip(&bounds)
+            .map(|(&distance, &(velocity, acceleration))| {
+                minimum_duration(options.profile, distance.abs(), velocity, acceleration)
+            })
+            .fold(options.min_segment_duration, f64::max);
+
+        let joints = distances
+            .iter()
+            .zip(&bounds)
+            .map(|(&distance, &(_, acceleration))| joint_motion(options.profile, distance, duration, acceleration))
+            .collect();
+
+        segments.push(Segment {
+            start_time,
+            duration,
+            start: pair[0].clone(),
+            joints,
+        });
+        start_time += duration;
+    }
+
+    Ok(JointTrajectory {
+        profile: options.profile,
+        segments,
+        end: waypoints[waypoints.len() - 1].clone(),
+        duration: start_time,
+    })
+}
+
+/// Ramp time as a multiple of cruise velocity / acceleration limit
+///
+/// A trapezoid reaches the cruise speed v at the limit a in v/a; the S-curve's
+/// triangular acceleration pulse peaks at a and averages a/2, taking 2v/a.
+fn ramp_factor(profile: Profile) -> f64 {
+    match profile {
+        Profile::SCurve => 2.0,
+        _ => 1.0,
+    }
+}
+
+/// Shortest rest-to-rest duration covering `distance` (≥ 0) within the limits
+fn minimum_duration(profile: Profile, distance: f64, velocity: f64, acceleration: f64) -> f64 {
+    if distance == 0.0 {
+        return 0.0;
+    }
+
+    match profile {
+        Profile::Cubic => (1.5 * distance / velocity).max((6.0 * distance / acceleration).sqrt()),
+        Profile::Quintic => (QUINTIC_PEAK_VELOCITY * distance / velocity)
+            .max((QUINTIC_PEAK_ACCELERATION * distance / acceleration).sqrt()),
+        Profile::Trapezoidal | Profile::SCurve => {
+            let k = ramp_factor(profile);
+            if distance >= k * velocity * velocity / acceleration {
+                // Reaches the velocity limit and cruises
+                distance / velocity + k * velocity / acceleration
+            } else {
+                // Accelerates and immediately decelerates
+                2.0 * (k * distance / acceleration).sqrt()
+            }
+        }
+    }
+}
+
+/// Profile of one joint stretched to the common segment `duration`
+///
+/// For the ramped profiles the joint keeps its acceleration limit and lowers its
+/// cruise speed: distance = v (T - k v / a) gives v = a (T - √(T² - 4kd/a)) / 2k.
+fn joint_motion(profile: Profile, distance: f64, duration: f64, acceleration: f64) -> JointMotion {
+    let magnitude = distance.abs();
+
+    match profile {
+        Profile::Trapezoidal | Profile::SCurve if magnitude > 0.0 && duration > 0.0 => {
+            let k = ramp_factor(profile);
+            let discriminant = (duration * duration - 4.0 * k * magnitude / acceleration).max(0.0);
+            let cruise_velocity = acceleration * (duration - discriminant.sqrt()) / (2.0 * k);
+
+            JointMotion {
+                distance,
+                cruise_velocity,
+                ramp_time: (k * cruise_velocity / acceleration).min(duration / 2.0),
+            }
+        }
+        _ => JointMotion {
+            distance,
+            cruise_velocity: 0.0,
+            ramp_time: 0.0,
+        },
+    }
+}
+
+/// Position offset, velocity and acceleration of a joint at local time `t`
+fn evaluate(profile: Profile, motion: &JointMotion, duration: f64, t: f64) -> (f64, f64, f64) {
+    if motion.distance == 0.0 || duration <= 0.0 {
+        return (0.0, 0.0, 0.0);
+    }
+
+    match profile {
+        Profile::Cubic | Profile::Quintic => {
+            let tau = (t / duration).clamp(0.0, 1.0);
+            let (s, ds, dds) = match profile {
+                Profile::Cubic => (
+                    tau * tau * (3.0 - 2.0 * tau),
+                    6.0 * tau * (1.0 - tau),
+                    6.0 - 12.0 * tau,
+                ),
+                _ => (
+                    tau * tau * tau * (10.0 + tau * (-15.0 + 6.0 * tau)),
+                    30.0 * tau * tau * (1.0 - tau) * (1.0 - tau),
+                    60.0 * tau * (1.0 - tau) * (1.0 - 2.0 * tau),
+                ),
+            };
+            (
+                motion.distance * s,
+                motion.distance * ds / duration,
+                motion.distance * dds / (duration * duration),
+            )
+        }
+        Profile::Trapezoidal | Profile::SCurve => {
+            let sign = motion.distance.signum();
+            let (v, ramp) = (motion.cruise_velocity, motion.ramp_time);
+            let t = t.clamp(0.0, duration);
+
+            let (position, velocity, acceleration) = if t < ramp {
+                ramp_phase(profile, v, ramp, t)
+            } else if t <= duration - ramp {
+                // The ramp covers v · ramp / 2 for both profiles
+                (v * ramp / 2.0 + v * (t - ramp), v, 0.0)
+            } else {
+                let (p, vel, acc) = ramp_phase(profile, v, ramp, duration - t);
+                (motion.distance.abs() - p, vel, -acc)
+            };
+
+            (sign * position, sign * velocity, sign * acceleration)
+        }
+    }
+}
+
+/// State `t` seconds into a ramp from rest to `cruise_velocity` lasting `ramp`
+fn ramp_phase(profile: Profile, cruise_velocity: f64, ramp: f64, t: f64) -> (f64, f64, f64) {
+    match profile {
+        Profile::SCurve => {
+            // Acceleration rises linearly to 2v/ramp at mid-ramp, then falls back to zero
+            let jerk = 4.0 * cruise_velocity / (ramp * ramp);
+            if t <= ramp / 2.0 {
+                (jerk * t * t * t / 6.0, jerk * t * t / 2.0, jerk * t)
+            } else {
+                let u = ramp - t;
+                (
+                    cruise_velocity * ramp / 2.0 - cruise_velocity * u + jerk * u * u * u / 6.0,
+                    cruise_velocity - jerk * u * u / 2.0,
+                    jerk * u,
+                )
+            }
+        }
+        _ => {
+            let acceleration = cruise_velocity / ramp;
+            (acceleration * t * t / 2.0, acceleration * t, acceleration)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::limits::JointLimits;
+
+    const EPSILON: f64 = 1e-9;
+    const PROFILES: [Profile; 4] = [Profile::Cubic, Profile::Quintic, Profile::Trapezoidal, Profile::SCurve];
+
+    fn limited_robot() -> RobotArm {
+        let mut robot = RobotArm::planar(vec![1.0, 1.0, 0.5]);
+        robot
+            .set_joint_limits(vec![
+                JointLimits { velocity: 1.0, acceleration: 2.0, ..JointLimits::position(-3.0, 3.0) },
+                JointLimits { velocity: 0.5, acceleration: 1.0, ..JointLimits::position(-3.0, 3.0) },
+                JointLimits::unbounded(),
+            ])
+            .unwrap();
+        robot
+    }
+
+    fn waypoints() -> Vec<Vec<f64>> {
+        vec![vec![0.0, 0.0, 0.0], vec![1.5, -0.4, 0.3], vec![-0.5, 0.2, 0.3]]
+    }
+
+    fn options(profile: Profile) -> TrajectoryOptions {
+        TrajectoryOptions {
+            profile,
+            ..TrajectoryOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_passes_through_waypoints() {
+        let robot = limited_robot();
+        for profile in PROFILES {
+            let trajectory = plan_trajectory(&robot, &waypoints(), &options(profile)).unwrap();
+
+            for (time, waypoint) in trajectory.waypoint_times().iter().zip(waypoints()) {
+                let point = trajectory.sample(*time);
+                for (actual, expected) in point.positions.iter().zip(&waypoint) {
+                    assert!((actual - expected).abs() < EPSILON, "{:?}: {} vs {}", profile, actual, expected);
+                }
+                assert!(point.velocities.iter().all(|v| v.abs() < EPSILON));
+            }
+        }
+    }
+
+    #[test]
+    fn test_respects_velocity_and_acceleration_limits() {
+        let robot = limited_robot();
+        let bounds = [(1.0, 2.0), (0.5, 1.0), (1.0, 2.0)];
+
+        for profile in PROFILES {
+            let trajectory = plan_trajectory(&robot, &waypoints(), &options(profile)).unwrap();
+            let mut peak = [(0.0f64, 0.0f64); 3];
+
+            for step in 0..=4000 {
+                let point = trajectory.sample(trajectory.duration * step as f64 / 4000.0);
+                for joint in 0..3 {
+                    let (velocity, acceleration) = bounds[joint];
+                    assert!(point.velocities[joint].abs() <= velocity + 1e-9);
+                    assert!(point.accelerations[joint].abs() <= acceleration + 1e-9);
+                    peak[joint].0 = peak[joint].0.max(point.velocities[joint].abs() / velocity);
+                    peak[joint].1 = peak[joint].1.max(point.accelerations[joint].abs() / acceleration);
+                }
+            }
+
+            // Time-optimal: some joint saturates one of its limits
+            assert!(peak.iter().any(|&(v, a)| v > 0.99 || a > 0.99), "{:?} is not tight", profile);
+        }
+    }
+
+    #[test]
+    fn test_joints_are_synchronized() {
+        let robot = limited_robot();
+        let trajectory = plan_trajectory(&robot, &waypoints(), &options(Profile::Trapezoidal)).unwrap();
+        let segment = &trajectory.segments[0];
+
+        // Joint 0 moves furthest but joint 1 is slower: both finish at the same time
+        let almost_done = trajectory.sample(segment.duration - 1e-3);
+        assert!(almost_done.velocities.iter().all(|v| v.abs() < 0.01));
+
+        let halfway = trajectory.sample(segment.duration / 2.0);
+        assert!(halfway.velocities.iter().all(|v| v.abs() > 0.0));
+    }
+
+    #[test]
+    fn test_trapezoidal_minimum_time() {
+        // d = 1.5, v = 1, a = 2: 0.25 rad per ramp, cruise 1.0 rad → T = 1.5 + 0.5 = 2.0
+        assert!((minimum_duration(Profile::Trapezoidal, 1.5, 1.0, 2.0) - 2.0).abs() < EPSILON);
+        // Short move: triangular profile 2 √(d / a)
+        assert!((minimum_duration(Profile::Trapezoidal, 0.2, 1.0, 2.0) - 2.0 * 0.1f64.sqrt()).abs() < EPSILON);
+        // S-curve ramps take twice as long
+        assert!((minimum_duration(Profile::SCurve, 1.5, 1.0, 2.0) - 2.5).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_motion_is_continuous() {
+        let robot = limited_robot();
+        for profile in PROFILES {
+            let trajectory = plan_trajectory(&robot, &waypoints(), &options(profile)).unwrap();
+            let dt = 1e-5;
+
+            for step in 1..200 {
+                let t = trajectory.duration * step as f64 / 200.0;
+                let (before, after) = (trajectory.sample(t - dt), trajectory.sample(t + dt));
+                let point = trajectory.sample(t);
+                for joint in 0..3 {
+                    let velocity = (after.positions[joint] - before.positions[joint]) / (2.0 * dt);
+                    assert!((velocity - point.velocities[joint]).abs() < 1e-4, "{:?} at {}", profile, t);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sampling_outside_the_trajectory() {
+        let robot = limited_robot();
+        let trajectory = plan_trajectory(&robot, &waypoints(), &options(Profile::Quintic)).unwrap();
+
+        assert_eq!(trajectory.sample(-1.0).positions, waypoints()[0]);
+        assert_eq!(trajectory.sample(trajectory.duration + 5.0).positions, waypoints()[2]);
+    }
+
+    #[test]
+    fn test_minimum_segment_duration() {
+        let robot = limited_robot();
+        let options = TrajectoryOptions {
+            min_segment_duration: 10.0,
+            ..TrajectoryOptions::default()
+        };
+        let trajectory = plan_trajectory(&robot, &waypoints(), &options).unwrap();
+
+        assert_eq!(trajectory.waypoint_times(), vec![0.0, 10.0, 20.0]);
+    }
+
+    #[test]
+    fn test_invalid_waypoints() {
+        let robot = limited_robot();
+        let opts = TrajectoryOptions::default();
+
+        assert_eq!(plan_trajectory(&robot, &[vec![0.0; 3]], &opts).unwrap_err().code(), "INVALID_ARGUMENT");
+        assert_eq!(
+            plan_trajectory(&robot, &[vec![0.0; 3], vec![0.0; 2]], &opts).unwrap_err(),
+            RoboticsError::DimensionMismatch { expected: 3, actual: 2 }
+        );
+        assert_eq!(
+            plan_trajectory(&robot, &[vec![0.0; 3], vec![4.0, 0.0, 0.0]], &opts).unwrap_err().code(),
+            "LIMIT_VIOLATION"
+        );
+    }
+}
@@ -0,0 +1,484 @@
+use crate::error::RoboticsError;
+use crate::geometry3d::Transform3D;
+use crate::limits::JointLimits;
+use crate::robot::RobotArm;
+use crate::screw_model::ScrewModel;
+use nalgebra::{Vector3, Vector6};
+
+/// URDF joint type
+///
+/// Floating and planar joints are not supported by the serial-chain model.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UrdfJointType {
+    /// Rotation about the axis, bounded by limits
+    Revolute,
+    /// Rotation about the axis without limits
+    Continuous,
+    /// Translation along the axis
+    Prismatic,
+    /// Rigid connection, folded into the neighbouring transforms
+    Fixed,
+}
+
+impl UrdfJointType {
+    /// Whether the joint contributes a degree of freedom
+    pub fn is_movable(&self) -> bool {
+        !matches!(self, UrdfJointType::Fixed)
+    }
+}
+
+/// Joint limits from a URDF `<limit>` element (radians or meters, N·m or N, rad/s or m/s)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UrdfLimit {
+    pub lower: f64,
+    pub upper: f64,
+    pub effort: f64,
+    pub velocity: f64,
+}
+
+/// A joint of a URDF robot
+#[derive(Debug, Clone)]
+pub struct UrdfJoint {
+    pub name: String,
+    pub joint_type: UrdfJointType,
+    pub parent: String,
+    pub child: String,
+    /// Pose of the joint frame in the parent link frame (`<origin xyz rpy>`)
+    pub origin: Transform3D,
+    /// Unit joint axis in the joint frame (`<axis xyz>`, defaults to X)
+    pub axis: Vector3<f64>,
+    /// Limits, if a `<limit>` element is present
+    pub limit: Option<UrdfLimit>,
+}
+
+/// Serial-chain robot parsed from a URDF description
+#[derive(Debug, Clone)]
+pub struct UrdfRobot {
+    /// Robot name (`<robot name>`)
+    pub name: String,
+    /// Joints ordered from the root link to the tip link, fixed joints included
+    pub joints: Vec<UrdfJoint>,
+}
+
+impl UrdfRobot {
+    /// Parse a URDF document describing a serial chain
+    ///
+    /// Joints may appear in any order in the file; the chain is rebuilt from
+    /// parent/child link names starting at the root link. Branching trees,
+    /// disconnected joints and unsupported joint types are rejected.
+    pub fn parse(xml: &str) -> Result<Self, RoboticsError> {
+        parse_robot(xml).map_err(|e| RoboticsError::Parse(format!("URDF: {}", e)))
+    }
+
+    /// Movable (non-fixed) joints in chain order
+    pub fn movable_joints(&self) -> impl Iterator<Item = &UrdfJoint> {
+        self.joints.iter().filter(|joint| joint.joint_type.is_movable())
+    }
+
+    /// Number of degrees of freedom
+    pub fn num_joints(&self) -> usize {
+        self.movable_joints().count()
+    }
+
+    /// Convert the chain to a product of exponentials model
+    ///
+    /// Each movable joint gives a screw axis at the home configuration. Link
+    /// frames are placed at the next movable joint, and the end-effector is the
+    /// frame of the tip link (after any trailing fixed joints).
+    pub fn to_screw_model(&self) -> ScrewModel {
+        let mut frame = Transform3D::identity();
+        let mut screw_axes = Vec::new();
+        let mut joint_homes = Vec::new();
+
+        for joint in &self.joints {
+            frame = frame.compose(&joint.origin);
+            if !joint.joint_type.is_movable() {
+                continue;
+            }
+
+            let axis = frame.rotation_matrix() * joint.axis;
+            let origin = frame.position();
+            let point = Vector3::new(origin.x, origin.y, origin.z);
+
+            screw_axes.push(match joint.joint_type {
+                UrdfJointType::Prismatic => Vector6::new(0.0, 0.0, 0.0, axis.x, axis.y, axis.z),
+                _ => {
+                    let v = -axis.cross(&point);
+                    Vector6::new(axis.x, axis.y, axis.z, v.x, v.y, v.z)
+                }
+            });
+            joint_homes.push(frame);
+        }
+
+        let mut link_homes: Vec<Transform3D> = joint_homes.into_iter().skip(1).collect();
+        link_homes.push(frame);
+
+        ScrewModel::new(screw_axes, link_homes)
+    }
+
+    /// Limits of every movable joint
+    ///
+    /// Continuous joints have no position bounds; joints without a `<limit>`
+    /// element are unbounded. URDF does not specify acceleration limits.
+    pub fn joint_limits(&self) -> Vec<JointLimits> {
+        self.movable_joints()
+            .map(|joint| match (joint.joint_type, joint.limit) {
+                (UrdfJointType::Continuous, Some(limit)) => JointLimits {
+                    velocity: limit.velocity,
+                    effort: limit.effort,
+                    ..JointLimits::unbounded()
+                },
+                (_, Some(limit)) => JointLimits {
+                    lower: limit.lower,
+                    upper: limit.upper,
+                    velocity: limit.velocity,
+                    effort: limit.effort,
+                    ..JointLimits::unbounded()
+                },
+                (_, None) => JointLimits::unbounded(),
+            })
+            .collect()
+    }
+
+    /// Build a robot arm (screw-axis mode) from the chain, with its name, joint names and limits
+    ///
+    /// Fails with `Parse` if a joint's limits are invalid (see `JointLimits::validate`).
+    pub fn to_robot_arm(&self) -> Result<RobotArm, RoboticsError> {
+        let mut robot = RobotArm::from_screw_model(self.to_screw_model());
+        robot.name = self.name.clone();
+        robot.joint_names = self.movable_joints().map(|joint| joint.name.clone()).collect();
+
+        let limits = self.joint_limits();
+        for (joint, limits) in self.movable_joints().zip(&limits) {
+            limits
+                .validate()
+                .map_err(|reason| RoboticsError::Parse(format!("URDF: Joint '{}': <limit>: {}", joint.name, reason)))?;
+        }
+        robot.set_joint_limits(limits)?;
+        Ok(robot)
+    }
+}
+
+fn parse_robot(xml: &str) -> Result<UrdfRobot, String> {
+    let document = roxmltree::Document::parse(xml).map_err(|e| format!("Invalid URDF XML: {}", e))?;
+    let root = document.root_element();
+    if !root.has_tag_name("robot") {
+        return Err(format!("Expected <robot> root element, found <{}>", root.tag_name().name()));
+    }
+
+    let name = root.attribute("name").unwrap_or_default().to_string();
+    let links: Vec<&str> = root
+        .children()
+        .filter(|node| node.has_tag_name("link"))
+        .map(|node| required_attribute(node, "name"))
+        .collect::<Result<_, _>>()?;
+    let joints: Vec<UrdfJoint> = root
+        .children()
+        .filter(|node| node.has_tag_name("joint"))
+        .map(parse_joint)
+        .collect::<Result<_, _>>()?;
+
+    Ok(UrdfRobot {
+        name,
+        joints: order_chain(&links, joints)?,
+    })
+}
+
+fn parse_joint(node: roxmltree::Node) -> Result<UrdfJoint, String> {
+    let name = required_attribute(node, "name")?.to_string();
+    let joint_type = match required_attribute(node, "type")? {
+        "revolute" => UrdfJointType::Revolute,
+        "continuous" => UrdfJointType::Continuous,
+        "prismatic" => UrdfJointType::Prismatic,
+        "fixed" => UrdfJointType::Fixed,
+        other => return Err(format!("Joint '{}': unsupported joint type '{}'", name, other)),
+    };
+
+    let link_of = |tag: &str| -> Result<String, String> {
+        let element = child_element(node, tag).ok_or_else(|| format!("Joint '{}': missing <{}>", name, tag))?;
+        Ok(required_attribute(element, "link")?.to_string())
+    };
+    let parent = link_of("parent")?;
+    let child = link_of("child")?;
+
+    let origin = match child_element(node, "origin") {
+        Some(element) => {
+            let xyz = parse_vector(element, "xyz")?;
+            let rpy = parse_vector(element, "rpy")?;
+            // URDF rpy is fixed-axis X, Y, Z: R = Rz(yaw) · Ry(pitch) · Rx(roll)
+            Transform3D::from_xyz_rpy(xyz.into(), rpy.into())
+        }
+        None => Transform3D::identity(),
+    };
+
+    let axis = match child_element(node, "axis") {
+        Some(element) => {
+            required_attribute(element, "xyz")?;
+            parse_vector(element, "xyz")?
+        }
+        None => Vector3::x(),
+    };
+    if joint_type.is_movable() && axis.norm() < 1e-12 {
+        return Err(format!("Joint '{}': axis must be non-zero", name));
+    }
+    let axis = if joint_type.is_movable() { axis.normalize() } else { axis };
+
+    let limit = child_element(node, "limit")
+        .map(|element| -> Result<UrdfLimit, String> {
+            // Missing bounds are zero as in the URDF spec; missing effort/velocity are unbounded
+            Ok(UrdfLimit {
+                lower: parse_number(element, "lower", 0.0)?,
+                upper: parse_number(element, "upper", 0.0)?,
+                effort: parse_number(element, "effort", f64::INFINITY)?,
+                velocity: parse_number(element, "velocity", f64::INFINITY)?,
+            })
+        })
+        .transpose()?;
+
+    Ok(UrdfJoint {
+        name,
+        joint_type,
+        parent,
+        child,
+        origin,
+        axis,
+        limit,
+    })
+}
+
+/// Order joints from the root link to the tip, rejecting anything that is not a single chain
+fn order_chain(links: &[&str], mut joints: Vec<UrdfJoint>) -> Result<Vec<UrdfJoint>, String> {
+    for joint in &joints {
+        for link in [&joint.parent, &joint.child] {
+            if !links.contains(&link.as_str()) {
+                return Err(format!("Joint '{}' references unknown link '{}'", joint.name, link));
+            }
+        }
+    }
+
+    let roots: Vec<&str> = links
+        .iter()
+        .copied()
+        .filter(|link| !joints.iter().any(|joint| joint.child == *link))
+        .collect();
+    let mut current = match roots.as_slice() {
+        [root] => root.to_string(),
+        [] => return Err("URDF has no root link (kinematic loop)".to_string()),
+        _ => return Err(format!("URDF has several root links: {}", roots.join(", "))),
+    };
+
+    let mut chain = Vec::with_capacity(joints.len());
+    loop {
+        let children: Vec<usize> = (0..joints.len()).filter(|&i| joints[i].parent == current).collect();
+        match children.as_slice() {
+            [] => break,
+            [index] => {
+                let joint = joints.remove(*index);
+                current = joint.child.clone();
+                chain.push(joint);
+            }
+            _ => return Err(format!("Link '{}' has several child joints; only serial chains are supported", current)),
+        }
+    }
+
+    if let Some(joint) = joints.first() {
+        return Err(format!("Joint '{}' is not connected to the chain", joint.name));
+    }
+    Ok(chain)
+}
+
+fn child_element<'a, 'input>(node: roxmltree::Node<'a, 'input>, tag: &str) -> Option<roxmltree::Node<'a, 'input>> {
+    node.children().find(|child| child.has_tag_name(tag))
+}
+
+fn required_attribute<'a>(node: roxmltree::Node<'a, '_>, name: &str) -> Result<&'a str, String> {
+    node.attribute(name)
+        .ok_or_else(|| format!("<{}> is missing the '{}' attribute", node.tag_name().name(), name))
+}
+
+/// Parse a whitespace-separated 3-vector attribute; a missing attribute is zero
+fn parse_vector(node: roxmltree::Node, name: &str) -> Result<Vector3<f64>, String> {
+    let Some(text) = node.attribute(name) else {
+        return Ok(Vector3::zeros());
+    };
+
+    let components: Vec<f64> = text
+        .split_whitespace()
+        .map(|item| finite_number(node, name, item))
+        .collect::<Result<_, _>>()?;
+
+    match components.as_slice() {
+        [x, y, z] => Ok(Vector3::new(*x, *y, *z)),
+        _ => Err(format!("<{}> {}: expected 3 values, found '{}'", node.tag_name().name(), name, text)),
+    }
+}
+
+/// Parse a number attribute, or `default` if it is missing
+fn parse_number(node: roxmltree::Node, name: &str, default: f64) -> Result<f64, String> {
+    node.attribute(name)
+        .map_or(Ok(default), |text| finite_number(node, name, text.trim()))
+}
+
+/// Parse a finite number (NaN and infinities are rejected)
+fn finite_number(node: roxmltree::Node, name: &str, text: &str) -> Result<f64, String> {
+    let tag = node.tag_name().name();
+    match text.parse::<f64>() {
+        Ok(value) if value.is_finite() => Ok(value),
+        Ok(_) => Err(format!("<{}> {}: '{}' is not a finite number", tag, name, text)),
+        Err(e) => Err(format!("<{}> {}: invalid number '{}': {}", tag, name, text, e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kinematics::{end_effector_transform, forward_kinematics};
+    use std::f64::consts::PI;
+
+    const EPSILON: f64 = 1e-9;
+
+    const PLANAR_2R: &str = include_str!("../tests/fixtures/planar_2r.urdf");
+    const SPATIAL_4DOF: &str = include_str!("../tests/fixtures/spatial_4dof.urdf");
+    const BRANCHING: &str = include_str!("../tests/fixtures/branching.urdf");
+
+    #[test]
+    fn test_parse_planar_2r() {
+        let robot = UrdfRobot::parse(PLANAR_2R).unwrap();
+        assert_eq!(robot.name, "planar_2r");
+        assert_eq!(robot.joints.len(), 3);
+        assert_eq!(robot.num_joints(), 2);
+
+        let limit = robot.joints[1].limit.unwrap();
+        assert_eq!(limit.lower, -2.5);
+        assert_eq!(limit.upper, 2.5);
+        assert_eq!(limit.effort, 5.0);
+        assert_eq!(limit.velocity, 3.0);
+        assert!(robot.joints[2].limit.is_none());
+    }
+
+    #[test]
+    fn test_planar_2r_matches_dh_robot() {
+        let mut urdf_robot = UrdfRobot::parse(PLANAR_2R).unwrap().to_robot_arm().unwrap();
+        let mut dh_robot = RobotArm::planar(vec![2.0, 1.5]);
+
+        for values in [vec![0.0, 0.0], vec![0.4, -1.1], vec![PI / 2.0, PI / 3.0]] {
+            urdf_robot.set_joint_angles(values.clone()).unwrap();
+            dh_robot.set_joint_angles(values).unwrap();
+
+            let (a, b) = (end_effector_transform(&urdf_robot), end_effector_transform(&dh_robot));
+            assert!((a.matrix() - b.matrix()).abs().max() < EPSILON);
+
+            let urdf_positions = forward_kinematics(&urdf_robot);
+            let dh_positions = forward_kinematics(&dh_robot);
+            assert_eq!(urdf_positions.len(), dh_positions.len());
+            for (p, q) in urdf_positions.iter().zip(dh_positions.iter()) {
+                assert!((p.x - q.x).abs() < EPSILON);
+                assert!((p.y - q.y).abs() < EPSILON);
+                assert!((p.z - q.z).abs() < EPSILON);
+            }
+        }
+    }
+
+    #[test]
+    fn test_spatial_chain_with_unordered_joints() {
+        let urdf = UrdfRobot::parse(SPATIAL_4DOF).unwrap();
+        let names: Vec<&str> = urdf.joints.iter().map(|joint| joint.name.as_str()).collect();
+        assert_eq!(names, ["base_mount", "shoulder", "elbow", "slide", "wrist_roll", "tool_joint"]);
+        assert_eq!(urdf.joints[3].joint_type, UrdfJointType::Prismatic);
+        assert_eq!(urdf.joints[4].joint_type, UrdfJointType::Continuous);
+        // Axis "2 0 0" is normalized
+        assert!((urdf.joints[3].axis - Vector3::x()).norm() < EPSILON);
+
+        let mut robot = urdf.to_robot_arm().unwrap();
+        assert_eq!(robot.num_joints(), 4);
+
+        // Home: elbow roll turns the forearm's Z axis to -Y, tool offset follows it
+        let home = end_effector_transform(&robot).position();
+        assert!((home.x - 0.6).abs() < EPSILON);
+        assert!((home.y + 0.05).abs() < EPSILON);
+        assert!((home.z - 0.8).abs() < EPSILON);
+
+        robot.set_joint_angles(vec![PI / 2.0, 0.0, 0.2, 0.0]).unwrap();
+        let moved = end_effector_transform(&robot).position();
+        assert!((moved.x - 0.05).abs() < EPSILON);
+        assert!((moved.y - 0.8).abs() < EPSILON);
+        assert!((moved.z - 0.8).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_limits_and_names_attached_to_robot() {
+        let robot = UrdfRobot::parse(SPATIAL_4DOF).unwrap().to_robot_arm().unwrap();
+
+        assert_eq!(robot.limits[0], JointLimits { lower: -3.0, upper: 3.0, velocity: 1.0, effort: 80.0, ..JointLimits::unbounded() });
+        assert_eq!(robot.limits[2].upper, 0.4);
+        // Continuous joint: no position bounds
+        assert!(!robot.limits[3].is_bounded());
+        assert!(robot.is_within_limits());
+
+        assert_eq!(robot.name, "spatial_4dof");
+        assert_eq!(robot.joint_names, vec!["shoulder", "elbow", "slide", "wrist_roll"]);
+    }
+
+    #[test]
+    fn test_branching_tree_is_rejected() {
+        let error = UrdfRobot::parse(BRANCHING).unwrap_err();
+        assert_eq!(error.code(), "PARSE_ERROR");
+        assert!(error.to_string().contains("serial chains"));
+    }
+
+    #[test]
+    fn test_invalid_documents_are_rejected() {
+        assert!(UrdfRobot::parse("<robot").is_err());
+        assert!(UrdfRobot::parse("<model name=\"x\"/>").is_err());
+
+        let floating = r#"<robot name="r"><link name="a"/><link name="b"/>
+            <joint name="j" type="floating"><parent link="a"/><child link="b"/></joint></robot>"#;
+        assert!(UrdfRobot::parse(floating).unwrap_err().to_string().contains("unsupported"));
+
+        let bad_origin = r#"<robot name="r"><link name="a"/><link name="b"/>
+            <joint name="j" type="fixed"><parent link="a"/><child link="b"/><origin xyz="1 2"/></joint></robot>"#;
+        assert!(UrdfRobot::parse(bad_origin).is_err());
+
+        let unknown_link = r#"<robot name="r"><link name="a"/>
+            <joint name="j" type="fixed"><parent link="a"/><child link="b"/></joint></robot>"#;
+        assert!(UrdfRobot::parse(unknown_link).unwrap_err().to_string().contains("unknown link"));
+
+        let not_finite = r#"<robot name="r"><link name="a"/><link name="b"/>
+            <joint name="j" type="fixed"><parent link="a"/><child link="b"/><origin xyz="NaN 0 inf"/></joint></robot>"#;
+        let error = UrdfRobot::parse(not_finite).unwrap_err();
+        assert_eq!(error.code(), "PARSE_ERROR");
+        assert!(error.to_string().contains("<origin> xyz"), "{}", error);
+
+        let infinite_limit = r#"<robot name="r"><link name="a"/><link name="b"/>
+            <joint name="j" type="revolute"><parent link="a"/><child link="b"/><axis xyz="0 0 1"/>
+            <limit lower="-inf" upper="1" effort="1" velocity="1"/></joint></robot>"#;
+        assert!(UrdfRobot::parse(infinite_limit).unwrap_err().to_string().contains("<limit> lower"));
+    }
+
+    fn single_joint(limit: &str) -> String {
+        format!(
+            r#"<robot name="r"><link name="a"/><link name="b"/>
+            <joint name="j" type="revolute"><parent link="a"/><child link="b"/><axis xyz="0 0 1"/>{}</joint></robot>"#,
+            limit
+        )
+    }
+
+    #[test]
+    fn test_invalid_limits_are_rejected() {
+        let inverted = UrdfRobot::parse(&single_joint(r#"<limit lower="1" upper="-1" effort="1" velocity="1"/>"#)).unwrap();
+        let error = inverted.to_robot_arm().unwrap_err();
+        assert_eq!(error.code(), "PARSE_ERROR");
+        assert!(error.to_string().contains("Joint 'j'"), "{}", error);
+
+        let negative = UrdfRobot::parse(&single_joint(r#"<limit lower="-1" upper="1" effort="-5" velocity="1"/>"#)).unwrap();
+        assert_eq!(negative.to_robot_arm().unwrap_err().code(), "PARSE_ERROR");
+    }
+
+    #[test]
+    fn test_missing_effort_and_velocity_are_unbounded() {
+        let urdf = UrdfRobot::parse(&single_joint(r#"<limit lower="-1" upper="1"/>"#)).unwrap();
+        let robot = urdf.to_robot_arm().unwrap();
+
+        assert_eq!(robot.limits, vec![JointLimits::position(-1.0, 1.0)]);
+    }
+}
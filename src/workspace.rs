@@ -0,0 +1,731 @@
+use crate::dh_parameters::JointType;
+use crate::error::RoboticsError;
+use crate::geometry3d::Point3D;
+use crate::kinematics::forward_kinematics;
+use crate::rng::SplitMix64;
+use crate::robot::RobotArm;
+use nalgebra::Vector3;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::f64::consts::PI;
+
+/// Largest number of configurations a sampling may visit
+const MAX_SAMPLES: usize = 2_000_000;
+
+/// Largest number of samples an alpha shape is built from (the triangulation is quadratic)
+const MAX_ALPHA_SHAPE_SAMPLES: usize = 10_000;
+
+/// Relative tolerance for geometric predicates (scaled by the point cloud size)
+const GEOMETRY_TOLERANCE: f64 = 1e-9;
+
+/// How the joint space is sampled
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Sampling {
+    /// Regular grid with `steps` values per joint (endpoints included)
+    Grid { steps: usize },
+    /// `samples` uniformly random configurations from a seeded generator
+    Random { samples: usize, seed: u64 },
+}
+
+/// Options for workspace sampling and analysis
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkspaceOptions {
+    /// Joint-space sampling strategy
+    pub sampling: Sampling,
+    /// Alpha-shape radius for the 2D outer boundary (None skips the alpha shape)
+    pub alpha: Option<f64>,
+    /// Range used for prismatic joints without position limits
+    pub prismatic_range: [f64; 2],
+}
+
+impl Default for WorkspaceOptions {
+    fn default() -> Self {
+        WorkspaceOptions {
+            sampling: Sampling::Random {
+                samples: 2000,
+                seed: 1,
+            },
+            alpha: None,
+            prismatic_range: [0.0, 1.0],
+        }
+    }
+}
+
+/// Triangulated 2D alpha shape of the reachable points (XY plane)
+///
+/// Delaunay triangles whose circumradius exceeds `alpha` are dropped, which
+/// carves out holes and concavities the convex hull would cover.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlphaShape {
+    /// Alpha radius used to filter the triangulation
+    pub alpha: f64,
+    /// Distinct XY points (triangle and edge indices refer to this list)
+    pub points: Vec<[f64; 2]>,
+    /// Kept triangles, counter-clockwise
+    pub triangles: Vec<[usize; 3]>,
+    /// Boundary edges (edges of exactly one kept triangle)
+    pub boundary: Vec<[usize; 2]>,
+    /// Total area of the kept triangles
+    pub area: f64,
+}
+
+impl AlphaShape {
+    /// Check if an XY point lies inside the shape
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        self.triangles.iter().any(|&[a, b, c]| {
+            let (pa, pb, pc) = (self.points[a], self.points[b], self.points[c]);
+            let p = [x, y];
+            cross_2d(pa, pb, p) >= -1e-12 && cross_2d(pb, pc, p) >= -1e-12 && cross_2d(pc, pa, p) >= -1e-12
+        })
+    }
+}
+
+/// 3D convex hull as a closed triangle mesh with outward-facing triangles
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConvexHull3D {
+    /// Hull vertices (face indices refer to this list)
+    pub vertices: Vec<Point3D>,
+    /// Triangles, counter-clockwise when seen from outside
+    pub faces: Vec<[usize; 3]>,
+}
+
+impl ConvexHull3D {
+    /// Enclosed volume
+    pub fn volume(&self) -> f64 {
+        self.faces
+            .iter()
+            .map(|&[a, b, c]| {
+                let (pa, pb, pc) = (to_vector(&self.vertices[a]), to_vector(&self.vertices[b]), to_vector(&self.vertices[c]));
+                pa.dot(&pb.cross(&pc)) / 6.0
+            })
+            .sum()
+    }
+
+    /// Check if a point lies inside the hull (or on its surface)
+    pub fn contains(&self, point: &Point3D) -> bool {
+        let p = to_vector(point);
+        self.faces.iter().all(|&[a, b, c]| {
+            let (pa, pb, pc) = (to_vector(&self.vertices[a]), to_vector(&self.vertices[b]), to_vector(&self.vertices[c]));
+            let normal = (pb - pa).cross(&(pc - pa));
+            normal.dot(&(p - pa)) <= 1e-9 * normal.norm().max(1.0)
+        })
+    }
+}
+
+/// Reach statistics of a sampled workspace (distances from the base frame origin;
+/// the bounding box is in world coordinates)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReachStatistics {
+    /// Number of sampled configurations
+    pub samples: usize,
+    pub min_reach: f64,
+    pub max_reach: f64,
+    pub mean_reach: f64,
+    /// Axis-aligned bounding box of the reachable points
+    pub min: Point3D,
+    pub max: Point3D,
+    /// Area of the XY convex hull
+    pub hull_area: f64,
+    /// Volume of the 3D convex hull (None for planar point clouds)
+    pub hull_volume: Option<f64>,
+    /// Area of the 2D alpha shape (when computed)
+    pub alpha_area: Option<f64>,
+}
+
+/// Sampled workspace with its boundaries and statistics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceAnalysis {
+    /// End-effector position of every sampled configuration
+    pub points: Vec<Point3D>,
+    /// XY convex hull, counter-clockwise
+    pub hull_2d: Vec<[f64; 2]>,
+    /// XY alpha shape (when `alpha` is set)
+    pub alpha_shape: Option<AlphaShape>,
+    /// 3D convex hull (None when the points are coplanar)
+    pub hull_3d: Option<ConvexHull3D>,
+    pub statistics: ReachStatistics,
+}
+
+/// Sampling range of every joint
+///
+/// Bounded joints use their position limits; unbounded revolute joints use
+/// [-π, π] and unbounded prismatic joints `prismatic_range`.
+pub fn joint_ranges(robot: &RobotArm, options: &WorkspaceOptions) -> Vec<(f64, f64)> {
+    robot
+        .limits
+        .iter()
+        .enumerate()
+        .map(|(i, limits)| {
+            let (default_lower, default_upper) = match robot.joint_type(i) {
+                JointType::Revolute => (-PI, PI),
+                JointType::Prismatic => (options.prismatic_range[0], options.prismatic_range[1]),
+            };
+            (
+                if limits.lower.is_finite() { limits.lower } else { default_lower },
+                if limits.upper.is_finite() { limits.upper } else { default_upper },
+            )
+        })
+        .collect()
+}
+
+/// Check that `prismatic_range` is finite with lower < upper
+pub fn validate_prismatic_range([lower, upper]: [f64; 2]) -> Result<(), RoboticsError> {
+    if !(lower.is_finite() && upper.is_finite() && lower < upper) {
+        return Err(RoboticsError::InvalidArgument(format!(
+            "prismatic_range must be finite with lower < upper, got [{}, {}]",
+            lower, upper
+        )));
+    }
+    Ok(())
+}
+
+/// Number of configurations the sampling visits for `num_joints` joints
+///
+/// Fails with `InvalidArgument` for an empty sampling or one above `MAX_SAMPLES`.
+pub fn sample_count(sampling: &Sampling, num_joints: usize) -> Result<usize, RoboticsError> {
+    match *sampling {
+        Sampling::Grid { steps: 0 } => {
+            Err(RoboticsError::InvalidArgument("grid sampling needs at least one step per joint".to_string()))
+        }
+        Sampling::Grid { steps } => match (0..num_joints).try_fold(1usize, |total, _| total.checked_mul(steps)) {
+            Some(total) if total <= MAX_SAMPLES => Ok(total),
+            _ => Err(RoboticsError::InvalidArgument(format!(
+                "grid of {} steps over {} joints exceeds {} samples",
+                steps, num_joints, MAX_SAMPLES
+            ))),
+        },
+        Sampling::Random { samples: 0, .. } => {
+            Err(RoboticsError::InvalidArgument("random sampling needs at least one sample".to_string()))
+        }
+        Sampling::Random { samples, .. } if samples > MAX_SAMPLES => Err(RoboticsError::InvalidArgument(format!(
+            "{} random samples exceed {}",
+            samples, MAX_SAMPLES
+        ))),
+        Sampling::Random { samples, .. } => Ok(samples),
+    }
+}
+
+/// Sample the joint space and return the end-effector position of every configuration
+pub fn sample_workspace(robot: &RobotArm, options: &WorkspaceOptions) -> Result<Vec<Point3D>, RoboticsError> {
+    validate_prismatic_range(options.prismatic_range)?;
+    let total = sample_count(&options.sampling, robot.num_joints())?;
+    let ranges = joint_ranges(robot, options);
+    let mut arm = robot.clone();
+    let mut end_effector = |values: Vec<f64>| {
+        arm.joint_angles = values;
+        let positions = forward_kinematics(&arm);
+        let last = positions.last().expect("forward kinematics includes the base");
+        Point3D::new(last.x, last.y, last.z)
+    };
+
+    match options.sampling {
+        Sampling::Grid { steps } => {
+            let value_at = |(lower, upper): (f64, f64), step: usize| {
+                if steps == 1 {
+                    0.5 * (lower + upper)
+                } else {
+                    lower + (upper - lower) * step as f64 / (steps - 1) as f64
+                }
+            };
+
+            Ok((0..total)
+                .map(|index| {
+                    // Mixed-radix digits of the sample index select each joint's step
+                    let mut remainder = index;
+                    let values = ranges
+                        .iter()
+                        .map(|&range| {
+                            let step = remainder % steps;
+                            remainder /= steps;
+                            value_at(range, step)
+                        })
+                        .collect();
+                    end_effector(values)
+                })
+                .collect())
+        }
+        Sampling::Random { seed, .. } => {
+            let mut rng = SplitMix64::new(seed);
+            Ok((0..total)
+                .map(|_| {
+                    let values = ranges.iter().map(|&(lower, upper)| rng.range(lower, upper)).collect();
+                    end_effector(values)
+                })
+                .collect())
+        }
+    }
+}
+
+/// Sample the workspace and compute its boundaries and reach statistics
+pub fn analyze_workspace(robot: &RobotArm, options: &WorkspaceOptions) -> Result<WorkspaceAnalysis, RoboticsError> {
+    if let Some(alpha) = options.alpha {
+        if !(alpha > 0.0 && alpha.is_finite()) {
+            return Err(RoboticsError::InvalidArgument(format!("alpha must be positive, got {}", alpha)));
+        }
+        let samples = sample_count(&options.sampling, robot.num_joints())?;
+        if samples > MAX_ALPHA_SHAPE_SAMPLES {
+            return Err(RoboticsError::InvalidArgument(format!(
+                "alpha shapes are limited to {} samples, got {}",
+                MAX_ALPHA_SHAPE_SAMPLES, samples
+            )));
+        }
+    }
+
+    let points = sample_workspace(robot, options)?;
+    let planar: Vec<[f64; 2]> = points.iter().map(|p| [p.x, p.y]).collect();
+
+    let hull_2d = convex_hull_2d(&planar);
+    let alpha_shape = options.alpha.map(|alpha| alpha_shape(&planar, alpha));
+    let hull_3d = convex_hull_3d(&points);
+
+    let origin = robot.base.translation_vector();
+    let reaches: Vec<f64> = points.iter().map(|p| (to_vector(p) - origin).norm()).collect();
+    let statistics = ReachStatistics {
+        samples: points.len(),
+        min_reach: reaches.iter().copied().fold(f64::INFINITY, f64::min),
+        max_reach: reaches.iter().copied().fold(0.0, f64::max),
+        mean_reach: reaches.iter().sum::<f64>() / reaches.len() as f64,
+        min: points.iter().fold(Point3D::new(f64::INFINITY, f64::INFINITY, f64::INFINITY), |m, p| {
+            Point3D::new(m.x.min(p.x), m.y.min(p.y), m.z.min(p.z))
+        }),
+        max: points.iter().fold(Point3D::new(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY), |m, p| {
+            Point3D::new(m.x.max(p.x), m.y.max(p.y), m.z.max(p.z))
+        }),
+        hull_area: polygon_area(&hull_2d),
+        hull_volume: hull_3d.as_ref().map(ConvexHull3D::volume),
+        alpha_area: alpha_shape.as_ref().map(|shape| shape.area),
+    };
+
+    Ok(WorkspaceAnalysis {
+        points,
+        hull_2d,
+        alpha_shape,
+        hull_3d,
+        statistics,
+    })
+}
+
+/// 2D convex hull (Andrew's monotone chain), counter-clockwise without collinear points
+pub fn convex_hull_2d(points: &[[f64; 2]]) -> Vec<[f64; 2]> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| a[0].total_cmp(&b[0]).then(a[1].total_cmp(&b[1])));
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    let mut hull: Vec<[f64; 2]> = Vec::with_capacity(2 * sorted.len());
+    for pass in 0..2 {
+        let start = hull.len();
+        let iter: Box<dyn Iterator<Item = &[f64; 2]>> = if pass == 0 {
+            Box::new(sorted.iter())
+        } else {
+            Box::new(sorted.iter().rev())
+        };
+        for &point in iter {
+            while hull.len() >= start + 2 && cross_2d(hull[hull.len() - 2], hull[hull.len() - 1], point) <= 0.0 {
+                hull.pop();
+            }
+            hull.push(point);
+        }
+        // The last point of each chain starts the other one
+        hull.pop();
+    }
+
+    hull
+}
+
+/// 2D alpha shape from a Delaunay triangulation of the points
+pub fn alpha_shape(points: &[[f64; 2]], alpha: f64) -> AlphaShape {
+    let points = distinct_points(points);
+    let triangles: Vec<[usize; 3]> = delaunay(&points)
+        .into_iter()
+        .filter(|&triangle| circumcircle(&points, triangle).is_some_and(|(_, _, r2)| r2.sqrt() <= alpha))
+        .collect();
+
+    // Boundary edges appear in exactly one kept triangle
+    let edges: HashSet<(usize, usize)> = triangles
+        .iter()
+        .flat_map(|&[a, b, c]| [(a, b), (b, c), (c, a)])
+        .collect();
+    let boundary = edges
+        .iter()
+        .filter(|&&(a, b)| !edges.contains(&(b, a)))
+        .map(|&(a, b)| [a, b])
+        .collect();
+
+    let area = triangles
+        .iter()
+        .map(|&[a, b, c]| 0.5 * cross_2d(points[a], points[b], points[c]))
+        .sum();
+
+    AlphaShape {
+        alpha,
+        points,
+        triangles,
+        boundary,
+        area,
+    }
+}
+
+/// 3D convex hull (incremental); None if the points are coplanar
+pub fn convex_hull_3d(points: &[Point3D]) -> Option<ConvexHull3D> {
+    let vectors: Vec<Vector3<f64>> = points.iter().map(to_vector).collect();
+    let scale = vectors.iter().map(|v| v.amax()).fold(0.0, f64::max).max(1.0);
+    let tolerance = GEOMETRY_TOLERANCE * scale;
+
+    // Initial tetrahedron from extreme points
+    let p0 = 0;
+    let p1 = farthest(&vectors, |v| (v - vectors[p0]).norm())?;
+    let direction = (vectors[p1] - vectors[p0]).try_normalize(tolerance)?;
+    let p2 = farthest(&vectors, |v| (v - vectors[p0]).cross(&direction).norm())?;
+    let normal = (vectors[p1] - vectors[p0]).cross(&(vectors[p2] - vectors[p0])).try_normalize(tolerance * tolerance)?;
+    let p3 = farthest(&vectors, |v| normal.dot(&(v - vectors[p0])).abs())?;
+    if normal.dot(&(vectors[p3] - vectors[p0])).abs() <= tolerance {
+        return None;
+    }
+
+    // Faces carry their plane (unit outward normal n, offset n·a) so visibility is one dot product
+    let plane = |[a, b, c]: [usize; 3]| {
+        let normal = (vectors[b] - vectors[a])
+            .cross(&(vectors[c] - vectors[a]))
+            .try_normalize(1e-300)
+            .unwrap_or_else(Vector3::zeros);
+        ([a, b, c], normal, normal.dot(&vectors[a]))
+    };
+
+    let interior = (vectors[p0] + vectors[p1] + vectors[p2] + vectors[p3]) / 4.0;
+    let mut faces: Vec<([usize; 3], Vector3<f64>, f64)> = [[p0, p1, p2], [p0, p3, p1], [p1, p3, p2], [p2, p3, p0]]
+        .into_iter()
+        .map(|[a, b, c]| {
+            let (_, normal, offset) = plane([a, b, c]);
+            if normal.dot(&interior) > offset {
+                plane([a, c, b])
+            } else {
+                plane([a, b, c])
+            }
+        })
+        .collect();
+
+    for (index, point) in vectors.iter().enumerate() {
+        let visible: Vec<bool> = faces.iter().map(|(_, normal, offset)| normal.dot(point) - offset > tolerance).collect();
+        if !visible.contains(&true) {
+            continue;
+        }
+
+        // Horizon: edges of visible faces whose twin belongs to a hidden face
+        let visible_edges: HashSet<(usize, usize)> = faces
+            .iter()
+            .zip(&visible)
+            .filter(|(_, &is_visible)| is_visible)
+            .flat_map(|(&([a, b, c], _, _), _)| [(a, b), (b, c), (c, a)])
+            .collect();
+        let horizon: Vec<(usize, usize)> = visible_edges
+            .iter()
+            .copied()
+            .filter(|&(a, b)| !visible_edges.contains(&(b, a)))
+            .collect();
+
+        let mut kept: Vec<_> = faces
+            .iter()
+            .zip(&visible)
+            .filter(|(_, &is_visible)| !is_visible)
+            .map(|(&face, _)| face)
+            .collect();
+        kept.extend(horizon.iter().map(|&(a, b)| plane([a, b, index])));
+        faces = kept;
+    }
+    let faces: Vec<[usize; 3]> = faces.into_iter().map(|(face, _, _)| face).collect();
+
+    // Compact the vertex list to the points used by the faces
+    let mut used: Vec<usize> = faces.iter().flatten().copied().collect();
+    used.sort_unstable();
+    used.dedup();
+    let remap = |index: usize| used.binary_search(&index).expect("face vertex is in the used list");
+
+    Some(ConvexHull3D {
+        vertices: used.iter().map(|&i| points[i]).collect(),
+        faces: faces.iter().map(|&[a, b, c]| [remap(a), remap(b), remap(c)]).collect(),
+    })
+}
+
+/// Area of a simple polygon (positive when counter-clockwise)
+fn polygon_area(polygon: &[[f64; 2]]) -> f64 {
+    (0..polygon.len())
+        .map(|i| {
+            let (a, b) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+            a[0] * b[1] - b[0] * a[1]
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+/// Delaunay triangulation (Bowyer-Watson), counter-clockwise triangles
+fn delaunay(points: &[[f64; 2]]) -> Vec<[usize; 3]> {
+    if points.len() < 3 {
+        return Vec::new();
+    }
+
+    let (mut min, mut max) = ([f64::INFINITY; 2], [f64::NEG_INFINITY; 2]);
+    for p in points {
+        min = [min[0].min(p[0]), min[1].min(p[1])];
+        max = [max[0].max(p[0]), max[1].max(p[1])];
+    }
+    let size = (max[0] - min[0]).max(max[1] - min[1]).max(1e-12);
+    let center = [(min[0] + max[0]) / 2.0, (min[1] + max[1]) / 2.0];
+
+    // Super triangle enclosing every point, appended after the real points
+    let n = points.len();
+    let mut all = points.to_vec();
+    all.push([center[0] - 20.0 * size, center[1] - 10.0 * size]);
+    all.push([center[0] + 20.0 * size, center[1] - 10.0 * size]);
+    all.push([center[0], center[1] + 20.0 * size]);
+
+    let mut triangles: Vec<([usize; 3], (f64, f64, f64))> = Vec::new();
+    let super_triangle = [n, n + 1, n + 2];
+    triangles.push((super_triangle, circumcircle(&all, super_triangle).expect("super triangle is not degenerate")));
+
+    for index in 0..n {
+        let p = all[index];
+        let (bad, good): (Vec<_>, Vec<_>) = triangles.into_iter().partition(|&(_, (cx, cy, r2))| {
+            let (dx, dy) = (p[0] - cx, p[1] - cy);
+            dx * dx + dy * dy < r2 * (1.0 - 1e-12)
+        });
+        triangles = good;
+
+        // Cavity boundary: edges of bad triangles not shared with another bad triangle
+        let edges: HashSet<(usize, usize)> = bad
+            .iter()
+            .flat_map(|&([a, b, c], _)| [(a, b), (b, c), (c, a)])
+            .collect();
+        for &(a, b) in edges.iter().filter(|&&(a, b)| !edges.contains(&(b, a))) {
+            let triangle = [a, b, index];
+            if let Some(circle) = circumcircle(&all, triangle) {
+                triangles.push((triangle, circle));
+            }
+        }
+    }
+
+    triangles
+        .into_iter()
+        .map(|(triangle, _)| triangle)
+        .filter(|triangle| triangle.iter().all(|&i| i < n))
+        .collect()
+}
+
+/// Circumcenter and squared circumradius; None for degenerate triangles
+fn circumcircle(points: &[[f64; 2]], [a, b, c]: [usize; 3]) -> Option<(f64, f64, f64)> {
+    let (pa, pb, pc) = (points[a], points[b], points[c]);
+    let d = 2.0 * cross_2d(pa, pb, pc);
+    if d.abs() < 1e-300 {
+        return None;
+    }
+
+    let (a2, b2, c2) = (
+        pa[0] * pa[0] + pa[1] * pa[1],
+        pb[0] * pb[0] + pb[1] * pb[1],
+        pc[0] * pc[0] + pc[1] * pc[1],
+    );
+    let cx = (a2 * (pb[1] - pc[1]) + b2 * (pc[1] - pa[1]) + c2 * (pa[1] - pb[1])) / d;
+    let cy = (a2 * (pc[0] - pb[0]) + b2 * (pa[0] - pc[0]) + c2 * (pb[0] - pa[0])) / d;
+    let (dx, dy) = (pa[0] - cx, pa[1] - cy);
+    Some((cx, cy, dx * dx + dy * dy))
+}
+
+/// Remove points closer than the geometric tolerance to an earlier one
+fn distinct_points(points: &[[f64; 2]]) -> Vec<[f64; 2]> {
+    let scale = points.iter().map(|p| p[0].abs().max(p[1].abs())).fold(0.0, f64::max).max(1.0);
+    let cell = GEOMETRY_TOLERANCE * scale;
+
+    let mut seen = HashSet::new();
+    points
+        .iter()
+        .copied()
+        .filter(|p| seen.insert(((p[0] / cell).round() as i64, (p[1] / cell).round() as i64)))
+        .collect()
+}
+
+/// Z component of (b - a) × (c - a): positive when a, b, c turn counter-clockwise
+fn cross_2d(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+/// Index of the point maximizing `measure`, None if all measures vanish
+fn farthest(vectors: &[Vector3<f64>], measure: impl Fn(&Vector3<f64>) -> f64) -> Option<usize> {
+    let (index, value) = vectors
+        .iter()
+        .map(measure)
+        .enumerate()
+        .fold((0, 0.0), |best, (i, value)| if value > best.1 { (i, value) } else { best });
+    (value > 0.0).then_some(index)
+}
+
+fn to_vector(point: &Point3D) -> Vector3<f64> {
+    Vector3::new(point.x, point.y, point.z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dh_parameters::DHParameter;
+    use crate::geometry3d::Transform3D;
+    use crate::limits::JointLimits;
+
+    #[test]
+    fn test_convex_hull_2d_square() {
+        let points = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0], [0.5, 0.5], [0.5, 0.0]];
+        let hull = convex_hull_2d(&points);
+
+        assert_eq!(hull.len(), 4);
+        assert!((polygon_area(&hull) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_convex_hull_3d_cube() {
+        let mut points = Vec::new();
+        for &x in &[0.0, 1.0] {
+            for &y in &[0.0, 1.0] {
+                for &z in &[0.0, 1.0] {
+                    points.push(Point3D::new(x, y, z));
+                }
+            }
+        }
+        points.push(Point3D::new(0.5, 0.5, 0.5));
+
+        let hull = convex_hull_3d(&points).unwrap();
+        assert_eq!(hull.vertices.len(), 8);
+        assert!((hull.volume() - 1.0).abs() < 1e-12);
+        assert!(hull.contains(&Point3D::new(0.2, 0.7, 0.9)));
+        assert!(!hull.contains(&Point3D::new(1.2, 0.5, 0.5)));
+    }
+
+    #[test]
+    fn test_convex_hull_3d_coplanar_is_none() {
+        let points = [Point3D::new(0.0, 0.0, 0.0), Point3D::new(1.0, 0.0, 0.0), Point3D::new(0.0, 1.0, 0.0), Point3D::new(1.0, 1.0, 0.0)];
+        assert!(convex_hull_3d(&points).is_none());
+    }
+
+    #[test]
+    fn test_planar_2r_annulus() {
+        // Reach between |L1 - L2| = 0.5 and L1 + L2 = 3.5: an annulus
+        let robot = RobotArm::planar(vec![2.0, 1.5]);
+        let options = WorkspaceOptions {
+            // Odd step count so q2 = 0 (full reach) is on the grid
+            sampling: Sampling::Grid { steps: 41 },
+            alpha: Some(0.45),
+            ..WorkspaceOptions::default()
+        };
+        let analysis = analyze_workspace(&robot, &options).unwrap();
+        let stats = &analysis.statistics;
+
+        assert_eq!(stats.samples, 41 * 41);
+        assert!((stats.max_reach - 3.5).abs() < 1e-9);
+        assert!((stats.min_reach - 0.5).abs() < 1e-9);
+        assert!(analysis.hull_3d.is_none());
+        assert!(stats.hull_area > 0.97 * PI * 3.5 * 3.5);
+
+        // The alpha shape keeps the hole the convex hull covers
+        let shape = analysis.alpha_shape.unwrap();
+        let annulus = PI * (3.5 * 3.5 - 0.5 * 0.5);
+        assert!((shape.area - annulus).abs() < 0.05 * annulus);
+        assert!(shape.contains(2.0, 0.5));
+        assert!(!shape.contains(0.1, 0.1));
+        assert!(!shape.boundary.is_empty());
+    }
+
+    #[test]
+    fn test_reach_is_measured_from_the_base() {
+        let mut robot = RobotArm::planar(vec![2.0, 1.5]);
+        robot.base = Transform3D::translation(10.0, -4.0, 1.0);
+        let options = WorkspaceOptions {
+            sampling: Sampling::Grid { steps: 41 },
+            ..WorkspaceOptions::default()
+        };
+        let stats = analyze_workspace(&robot, &options).unwrap().statistics;
+
+        assert!((stats.max_reach - 3.5).abs() < 1e-9);
+        assert!((stats.min_reach - 0.5).abs() < 1e-9);
+        assert!((stats.max.x - 13.5).abs() < 1e-9);
+        assert!((stats.min.y + 7.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_random_sampling_respects_limits() {
+        let mut robot = RobotArm::planar(vec![1.0, 1.0]);
+        robot
+            .set_joint_limits(vec![JointLimits::position(0.0, PI / 2.0), JointLimits::position(0.0, 0.0)])
+            .unwrap();
+
+        let options = WorkspaceOptions {
+            sampling: Sampling::Random { samples: 500, seed: 3 },
+            ..WorkspaceOptions::default()
+        };
+        let points = sample_workspace(&robot, &options).unwrap();
+
+        // Straight arm swept over the first quadrant
+        assert_eq!(points.len(), 500);
+        for p in &points {
+            assert!((p.x.hypot(p.y) - 2.0).abs() < 1e-9);
+            assert!(p.x >= -1e-9 && p.y >= -1e-9);
+        }
+        assert_eq!(sample_workspace(&robot, &options).unwrap()[0].x, points[0].x);
+    }
+
+    #[test]
+    fn test_spatial_robot_hull_volume() {
+        // Prismatic column with a revolute arm: a cylinder of radius 1, height 0.5
+        let robot = RobotArm::from_dh_params(vec![
+            DHParameter::revolute(1.0, 0.0, 0.0, 0.0),
+            DHParameter::prismatic(0.0, 0.0, 0.0, 0.0),
+        ]);
+        let options = WorkspaceOptions {
+            sampling: Sampling::Grid { steps: 30 },
+            prismatic_range: [0.0, 0.5],
+            ..WorkspaceOptions::default()
+        };
+        let analysis = analyze_workspace(&robot, &options).unwrap();
+
+        let volume = analysis.statistics.hull_volume.unwrap();
+        assert!((volume - PI * 0.5).abs() < 0.02 * PI * 0.5);
+        assert!(analysis.hull_3d.unwrap().contains(&Point3D::new(0.3, 0.2, 0.25)));
+    }
+
+    #[test]
+    fn test_invalid_options() {
+        let robot = RobotArm::planar(vec![1.0, 1.0]);
+        let grid = WorkspaceOptions {
+            sampling: Sampling::Grid { steps: 0 },
+            ..WorkspaceOptions::default()
+        };
+        assert_eq!(sample_workspace(&robot, &grid).unwrap_err().code(), "INVALID_ARGUMENT");
+
+        let alpha = WorkspaceOptions {
+            alpha: Some(-1.0),
+            ..WorkspaceOptions::default()
+        };
+        assert!(analyze_workspace(&robot, &alpha).is_err());
+
+        // Sample counts are capped, much lower when an alpha shape is requested
+        let random = WorkspaceOptions {
+            sampling: Sampling::Random { samples: 3_000_000, seed: 1 },
+            ..WorkspaceOptions::default()
+        };
+        assert_eq!(sample_workspace(&robot, &random).unwrap_err().code(), "INVALID_ARGUMENT");
+        let dense_alpha = WorkspaceOptions {
+            sampling: Sampling::Random { samples: 12_000, seed: 1 },
+            alpha: Some(0.1),
+            ..WorkspaceOptions::default()
+        };
+        assert_eq!(analyze_workspace(&robot, &dense_alpha).unwrap_err().code(), "INVALID_ARGUMENT");
+        assert!(analyze_workspace(&robot, &WorkspaceOptions { alpha: None, ..dense_alpha }).is_ok());
+
+        for prismatic_range in [[1.0, 0.0], [0.0, f64::INFINITY], [f64::NAN, 1.0]] {
+            let options = WorkspaceOptions {
+                prismatic_range,
+                ..WorkspaceOptions::default()
+            };
+            assert_eq!(sample_workspace(&robot, &options).unwrap_err().code(), "INVALID_ARGUMENT");
+        }
+    }
+}